@@ -0,0 +1,249 @@
+//! Headless runner that prints visualization steps for scripted use.
+//!
+//! A command names a structure, an operation and the values to load, e.g.
+//! `array bubble-sort 5 2 8 1`. Operations that need a parameter, such as a
+//! search target, take it before the values: `array binary-search 8 5 2 8 1`.
+
+use dsav_core::{
+    structures::VisualizableArray,
+    structures::VisualizableBST,
+    structures::VisualizableQueue,
+    structures::VisualizableStack,
+    DsavError,
+    Operation,
+    Step,
+    Visualizable,
+};
+use std::io::Write;
+
+pub const USAGE: &str = "\
+Usage: dsav-cli [--json] <structure> <operation> [parameter] [values...]
+
+Structures and operations:
+  array  bubble-sort | insertion-sort | selection-sort | merge-sort | quick-sort |
+         bucket-sort | heapify | max-subarray | lis | median | remove-duplicates |
+         count-inversions | sum | average | count | reverse | find-duplicate |
+         partition-parity | compress | is-bst |
+         search <target> | search-budget <target> <budget> |
+         binary-search <target> | search-range <target> |
+         two-sum <target> | remove-value <value> | dutch-flag <pivot> |
+         scale <factor>
+  bst    inorder | preorder | postorder | level-order | zigzag | morris |
+         mirror | balanced | level-widths | search <target> | rank <value> |
+         select <k> | path-to-root <value> | closest <target> |
+         neighbors <value> | range <low> <high>
+  stack  push-many
+  queue  enqueue-many
+
+With --json each step is printed as one JSON object per line.";
+
+#[derive(Debug, thiserror::Error)]
+pub enum CliError {
+    #[error("{0}")]
+    Usage(String),
+
+    #[error(transparent)]
+    Dsav(#[from] DsavError),
+
+    #[error("Failed to write output: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Failed to encode step: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+pub type Result<T> = std::result::Result<T, CliError>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Structure {
+    Array,
+    Bst,
+    Stack,
+    Queue,
+}
+
+/// A parsed runner command: which structure to build from `values` and what
+/// to animate on it. Stack and queue commands animate loading the values
+/// themselves, so they carry no operation.
+#[derive(Debug, Clone)]
+pub struct Command {
+    pub structure: Structure,
+    pub operation: Option<Operation>,
+    pub values: Vec<i32>,
+    pub json: bool,
+}
+
+/// What a run produced: how many steps were printed and the structure's
+/// contents afterwards (in-order for the BST).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    pub step_count: usize,
+    pub final_state: Vec<i32>,
+}
+
+fn parse_number(arg: &str) -> Result<i32> {
+    arg.parse()
+        .map_err(|_| CliError::Usage(format!("Expected an integer, got '{}'", arg)))
+}
+
+/// Parse command-line arguments (without the program name).
+pub fn parse_args<S: AsRef<str>>(args: &[S]) -> Result<Command> {
+    let mut json = false;
+    let mut rest = Vec::new();
+    for arg in args {
+        match arg.as_ref() {
+            "--json" => json = true,
+            other => rest.push(other),
+        }
+    }
+
+    let (structure, operation, params) = match rest.as_slice() {
+        [structure, operation, params @ ..] => (*structure, *operation, params),
+        _ => return Err(CliError::Usage("Expected a structure and an operation".to_string())),
+    };
+
+    let mut numbers = params.iter().map(|arg| parse_number(arg)).collect::<Result<Vec<_>>>()?;
+
+    // Operations with a parameter take it as the first number
+    let mut take_parameter = || {
+        if numbers.is_empty() {
+            return Err(CliError::Usage(format!("'{}' needs a parameter", operation)));
+        }
+        Ok(numbers.remove(0))
+    };
+
+    let (structure, operation) = match (structure, operation) {
+        ("array", "bubble-sort") => (Structure::Array, Some(Operation::BubbleSort)),
+        ("array", "insertion-sort") => (Structure::Array, Some(Operation::InsertionSort)),
+        ("array", "selection-sort") => (Structure::Array, Some(Operation::SelectionSort)),
+        ("array", "merge-sort") => (Structure::Array, Some(Operation::MergeSort)),
+        ("array", "quick-sort") => (Structure::Array, Some(Operation::QuickSort)),
+        ("array", "bucket-sort") => (Structure::Array, Some(Operation::BucketSort)),
+        ("array", "heapify") => (Structure::Array, Some(Operation::Heapify)),
+        ("array", "max-subarray") => (Structure::Array, Some(Operation::MaxSubarray)),
+        ("array", "lis") => (Structure::Array, Some(Operation::LongestIncreasingSubsequence)),
+        ("array", "sum") => (Structure::Array, Some(Operation::Sum)),
+        ("array", "average") => (Structure::Array, Some(Operation::Average)),
+        ("array", "count") => (Structure::Array, Some(Operation::Count)),
+        ("array", "partition-parity") => (Structure::Array, Some(Operation::PartitionByParity)),
+        ("array", "reverse") => (Structure::Array, Some(Operation::ReverseArray)),
+        ("array", "median") => (Structure::Array, Some(Operation::Median)),
+        ("array", "remove-duplicates") => (Structure::Array, Some(Operation::RemoveDuplicates)),
+        ("array", "find-duplicate") => (Structure::Array, Some(Operation::FindDuplicate)),
+        ("array", "count-inversions") => (Structure::Array, Some(Operation::CountInversions)),
+        ("array", "compress") => (Structure::Array, Some(Operation::Compress)),
+        ("array", "is-bst") => (Structure::Array, Some(Operation::IsValidBstArray)),
+        ("array", "search") => (Structure::Array, Some(Operation::Search(take_parameter()?))),
+        ("array", "search-budget") => {
+            let target = take_parameter()?;
+            let budget = usize::try_from(take_parameter()?)
+                .map_err(|_| CliError::Usage("'search-budget' needs a non-negative budget".to_string()))?;
+            (Structure::Array, Some(Operation::SearchWithBudget(target, budget)))
+        }
+        ("array", "binary-search") => (Structure::Array, Some(Operation::BinarySearch(take_parameter()?))),
+        ("array", "search-range") => (Structure::Array, Some(Operation::SearchRange(take_parameter()?))),
+        ("array", "remove-value") => (Structure::Array, Some(Operation::RemoveValue(take_parameter()?))),
+        ("array", "dutch-flag") => (Structure::Array, Some(Operation::DutchFlag(take_parameter()?))),
+        ("array", "scale") => (Structure::Array, Some(Operation::Scale(take_parameter()?))),
+        ("array", "two-sum") => (Structure::Array, Some(Operation::TwoSum(take_parameter()?))),
+        ("bst", "inorder") => (Structure::Bst, Some(Operation::Traverse)),
+        ("bst", "preorder") => (Structure::Bst, Some(Operation::PreOrderTraverse)),
+        ("bst", "postorder") => (Structure::Bst, Some(Operation::PostOrderTraverse)),
+        ("bst", "level-order") => (Structure::Bst, Some(Operation::LevelOrderTraverse)),
+        ("bst", "zigzag") => (Structure::Bst, Some(Operation::ZigzagTraverse)),
+        ("bst", "morris") => (Structure::Bst, Some(Operation::MorrisInOrder)),
+        ("bst", "mirror") => (Structure::Bst, Some(Operation::MirrorTree)),
+        ("bst", "balanced") => (Structure::Bst, Some(Operation::IsBalanced)),
+        ("bst", "level-widths") => (Structure::Bst, Some(Operation::ShowLevelWidths)),
+        ("bst", "search") => (Structure::Bst, Some(Operation::Search(take_parameter()?))),
+        ("bst", "path-to-root") => (Structure::Bst, Some(Operation::PathToRoot(take_parameter()?))),
+        ("bst", "closest") => (Structure::Bst, Some(Operation::FindClosest(take_parameter()?))),
+        ("bst", "neighbors") => {
+            (Structure::Bst, Some(Operation::InOrderPredecessorSuccessorPair(take_parameter()?)))
+        }
+        ("bst", "range") => {
+            let low = take_parameter()?;
+            (Structure::Bst, Some(Operation::RangeQuery(low, take_parameter()?)))
+        }
+        ("bst", "rank") => (Structure::Bst, Some(Operation::Rank(take_parameter()?))),
+        ("bst", "select") => {
+            let k = usize::try_from(take_parameter()?)
+                .map_err(|_| CliError::Usage("'select' needs a non-negative k".to_string()))?;
+            (Structure::Bst, Some(Operation::Select(k)))
+        }
+        ("stack", "push-many") => (Structure::Stack, None),
+        ("queue", "enqueue-many") => (Structure::Queue, None),
+        (structure, operation) => {
+            return Err(CliError::Usage(format!("Unknown command '{} {}'", structure, operation)))
+        }
+    };
+
+    Ok(Command {
+        structure,
+        operation,
+        values: numbers,
+        json,
+    })
+}
+
+/// Values of a render state's elements, or nothing for the empty placeholder.
+fn rendered_values(structure: &dyn Visualizable) -> Vec<i32> {
+    let state = structure.render_state();
+    if state.is_empty_placeholder() {
+        return Vec::new();
+    }
+    state.elements.iter().map(|elem| elem.value).collect()
+}
+
+/// Execute `command` and print its steps to `out`, one line per step.
+pub fn run(command: &Command, out: &mut dyn Write) -> Result<Outcome> {
+    let capacity = command.values.len().max(1);
+
+    let (steps, final_state) = match (command.structure, command.operation) {
+        (Structure::Array, Some(operation)) => {
+            let mut array = VisualizableArray::new(capacity);
+            for &value in &command.values {
+                array.push(value)?;
+            }
+            let steps = array.execute_with_steps(operation)?;
+            (steps, rendered_values(&array))
+        }
+        (Structure::Bst, Some(operation)) => {
+            let mut bst = VisualizableBST::new();
+            for &value in &command.values {
+                bst.insert(value);
+            }
+            let steps = bst.execute_with_steps(operation)?;
+            (steps, bst.iter().collect())
+        }
+        (Structure::Stack, _) => {
+            let mut stack = VisualizableStack::with_capacity(capacity);
+            let steps = stack.push_many_with_steps(&command.values);
+            (steps, rendered_values(&stack))
+        }
+        (Structure::Queue, _) => {
+            let mut queue = VisualizableQueue::with_capacity(capacity);
+            let steps = queue.enqueue_many_with_steps(&command.values);
+            (steps, rendered_values(&queue))
+        }
+        (_, None) => return Err(CliError::Usage("Missing operation".to_string())),
+    };
+
+    print_steps(&steps, command.json, out)?;
+
+    Ok(Outcome {
+        step_count: steps.len(),
+        final_state,
+    })
+}
+
+fn print_steps(steps: &[Step], json: bool, out: &mut dyn Write) -> Result<()> {
+    for (i, step) in steps.iter().enumerate() {
+        if json {
+            writeln!(out, "{}", serde_json::to_string(step)?)?;
+        } else {
+            writeln!(out, "{}. {}", i + 1, step.description)?;
+        }
+    }
+    Ok(())
+}
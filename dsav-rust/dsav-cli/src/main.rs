@@ -0,0 +1,28 @@
+//! DSAV CLI - prints visualization steps without the GUI.
+
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        println!("{}", dsav_cli::USAGE);
+        return ExitCode::SUCCESS;
+    }
+
+    let command = match dsav_cli::parse_args(&args) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("Error: {}\n\n{}", e, dsav_cli::USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match dsav_cli::run(&command, &mut std::io::stdout().lock()) {
+        Ok(_) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
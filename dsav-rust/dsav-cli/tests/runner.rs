@@ -0,0 +1,47 @@
+use dsav_cli::{parse_args, run, CliError, Structure};
+
+fn run_args(args: &[&str]) -> (dsav_cli::Outcome, String) {
+    let command = parse_args(args).unwrap();
+    let mut out = Vec::new();
+    let outcome = run(&command, &mut out).unwrap();
+    (outcome, String::from_utf8(out).unwrap())
+}
+
+#[test]
+fn test_bubble_sort_prints_every_step() {
+    let (outcome, output) = run_args(&["array", "bubble-sort", "5", "2", "8", "1"]);
+
+    assert_eq!(outcome.final_state, vec![1, 2, 5, 8]);
+    assert_eq!(output.lines().count(), outcome.step_count);
+    assert!(output.starts_with("1. "));
+}
+
+#[test]
+fn test_json_output_is_one_step_per_line() {
+    let (outcome, output) = run_args(&["--json", "array", "insertion-sort", "3", "1", "2"]);
+
+    let steps: Vec<serde_json::Value> = output.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+    assert_eq!(steps.len(), outcome.step_count);
+    assert!(steps.iter().all(|step| step.get("description").is_some()));
+}
+
+#[test]
+fn test_parameter_comes_before_values() {
+    let command = parse_args(&["array", "binary-search", "8", "1", "2", "8"]).unwrap();
+    assert_eq!(command.structure, Structure::Array);
+    assert_eq!(command.values, vec![1, 2, 8]);
+
+    let (outcome, _) = run_args(&["bst", "inorder", "5", "3", "8"]);
+    assert_eq!(outcome.final_state, vec![3, 5, 8]);
+
+    let (outcome, _) = run_args(&["stack", "push-many", "1", "2", "3"]);
+    assert_eq!(outcome.final_state, vec![1, 2, 3]);
+}
+
+#[test]
+fn test_invalid_commands() {
+    assert!(matches!(parse_args(&["array"]), Err(CliError::Usage(_))));
+    assert!(matches!(parse_args(&["array", "teleport", "1"]), Err(CliError::Usage(_))));
+    assert!(matches!(parse_args(&["array", "search"]), Err(CliError::Usage(_))));
+    assert!(matches!(parse_args(&["array", "bubble-sort", "x"]), Err(CliError::Usage(_))));
+}
@@ -0,0 +1,171 @@
+//! Aggregates computed by a single left-to-right scan, as in a fold/reduce.
+
+use crate::error::{DsavError, Result};
+use crate::recorder::StepRecorder;
+use crate::traits::Step;
+
+/// Which value the scan accumulates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Aggregate {
+    Sum,
+    Average,
+    Count,
+}
+
+impl Aggregate {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Sum => "sum",
+            Self::Average => "average",
+            Self::Count => "count",
+        }
+    }
+}
+
+/// Visit every element once, recording the running total and count.
+/// Totals are kept as i64 so large arrays can't overflow, and always cover
+/// the whole array even once the step limit stops recording.
+fn scan(arr: &[i32], aggregate: Aggregate, steps: &mut StepRecorder) -> (i64, usize) {
+    steps.push(Step {
+        description: format!("Computing the {} of {} element(s)", aggregate.name(), arr.len()),
+        highlight_indices: vec![],
+        active_indices: vec![],
+        metadata: serde_json::json!({
+            "operation": aggregate.name(),
+            "running_total": 0,
+            "running_count": 0,
+            "array_state": arr.to_vec()
+        }),
+        detail: None,
+    });
+
+    let mut total = 0i64;
+    let mut count = 0;
+
+    for (i, &value) in arr.iter().enumerate() {
+        total += value as i64;
+        count += 1;
+
+        // Past the limit the step would be dropped; skip building it
+        if steps.is_truncated() {
+            continue;
+        }
+
+        let description = match aggregate {
+            Aggregate::Count => format!("Counting index {}, count is {}", i, count),
+            _ => format!("Adding {}, running total is {}", value, total),
+        };
+
+        steps.push(Step {
+            description,
+            highlight_indices: vec![i],
+            active_indices: (0..i).collect(),
+            metadata: serde_json::json!({
+                "operation": "accumulate",
+                "index": i,
+                "running_total": total,
+                "running_count": count,
+                "array_state": arr.to_vec()
+            }),
+            detail: None,
+        });
+    }
+
+    (total, count)
+}
+
+fn result_step(description: String, result: serde_json::Value, arr: &[i32]) -> Step {
+    Step {
+        description,
+        highlight_indices: vec![],
+        active_indices: (0..arr.len()).collect(),
+        metadata: serde_json::json!({
+            "result": result,
+            "array_state": arr.to_vec()
+        }),
+        detail: None,
+    }
+}
+
+/// Sum of every element. An empty array sums to 0.
+pub fn sum_with_steps(arr: &[i32]) -> (i64, Vec<Step>) {
+    let mut steps = StepRecorder::new();
+    let (total, _) = scan(arr, Aggregate::Sum, &mut steps);
+
+    steps.push(result_step(format!("Sum is {}", total), total.into(), arr));
+    (total, steps.finish(arr))
+}
+
+/// Mean of the elements as a float. Returns `DsavError::EmptyStructure`
+/// for an empty array, whose average is undefined.
+pub fn average_with_steps(arr: &[i32]) -> Result<(f64, Vec<Step>)> {
+    if arr.is_empty() {
+        return Err(DsavError::EmptyStructure);
+    }
+
+    let mut steps = StepRecorder::new();
+    let (total, count) = scan(arr, Aggregate::Average, &mut steps);
+    let average = total as f64 / count as f64;
+
+    steps.push(result_step(
+        format!("Average is {} / {} = {}", total, count, average),
+        average.into(),
+        arr,
+    ));
+    Ok((average, steps.finish(arr)))
+}
+
+/// Number of elements, counted one by one.
+pub fn count_with_steps(arr: &[i32]) -> (usize, Vec<Step>) {
+    let mut steps = StepRecorder::new();
+    let (_, count) = scan(arr, Aggregate::Count, &mut steps);
+
+    steps.push(result_step(format!("Count is {}", count), count.into(), arr));
+    (count, steps.finish(arr))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aggregates_past_step_limit() {
+        use crate::recorder::set_max_steps;
+
+        let arr: Vec<i32> = (1..=20).collect();
+        set_max_steps(Some(3));
+        let (sum, steps) = sum_with_steps(&arr);
+        let (count, _) = count_with_steps(&arr);
+        let (average, _) = average_with_steps(&arr).unwrap();
+        set_max_steps(None);
+
+        assert_eq!(sum, 210);
+        assert_eq!(count, 20);
+        assert_eq!(average, 10.5);
+        assert_eq!(steps.last().unwrap().metadata["truncated"], true);
+    }
+
+    #[test]
+    fn test_sum_and_average() {
+        let (sum, steps) = sum_with_steps(&[2, 4, 6]);
+        assert_eq!(sum, 12);
+        let totals: Vec<i64> = steps[1..4]
+            .iter()
+            .map(|s| s.metadata["running_total"].as_i64().unwrap())
+            .collect();
+        assert_eq!(totals, vec![2, 6, 12]);
+        assert_eq!(steps.last().unwrap().metadata["result"], 12);
+
+        let (average, steps) = average_with_steps(&[2, 4, 6]).unwrap();
+        assert_eq!(average, 4.0);
+        assert_eq!(steps.last().unwrap().metadata["result"], 4.0);
+    }
+
+    #[test]
+    fn test_count_and_empty() {
+        assert_eq!(count_with_steps(&[2, 4, 6]).0, 3);
+        assert_eq!(count_with_steps(&[]).0, 0);
+        assert_eq!(sum_with_steps(&[]).0, 0);
+        assert!(matches!(average_with_steps(&[]), Err(DsavError::EmptyStructure)));
+    }
+}
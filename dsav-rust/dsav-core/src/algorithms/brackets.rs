@@ -0,0 +1,183 @@
+//! Balanced bracket checking with step-by-step visualization of the stack.
+
+use crate::recorder::StepRecorder;
+use crate::traits::Step;
+
+/// The opening bracket a closing one must match.
+fn opener(close: char) -> Option<char> {
+    match close {
+        ')' => Some('('),
+        ']' => Some('['),
+        '}' => Some('{'),
+        _ => None,
+    }
+}
+
+/// Open brackets on the stack, bottom first, as strings for display.
+fn stack_state(stack: &[(char, usize)]) -> Vec<String> {
+    stack.iter().map(|&(c, _)| c.to_string()).collect()
+}
+
+/// Check that every `(`, `[` and `{` in `input` is closed by its partner in
+/// the right order, e.g. `([]{})` is balanced and `([)]` is not.
+///
+/// Opening brackets are pushed; a closing bracket pops the top and must match
+/// it. Other characters are skipped. Every step's metadata holds the open
+/// brackets in `stack_state`, and the active indices are the stack slots
+/// involved. On failure the last step's metadata holds `mismatch_position`,
+/// the character index of the first bracket that cannot be matched: a closer
+/// with the wrong or no opener, or the earliest opener never closed.
+pub fn check_balanced_with_steps(input: &str) -> (bool, Vec<Step>) {
+    let mut steps = StepRecorder::new();
+    let mut stack: Vec<(char, usize)> = Vec::new();
+
+    steps.push(Step {
+        description: format!("Checking brackets in \"{}\"", input),
+        highlight_indices: vec![],
+        active_indices: vec![],
+        metadata: serde_json::json!({
+            "operation": "check_balanced",
+            "input": input,
+            "stack_state": stack_state(&stack)
+        }),
+        detail: None,
+    });
+
+    let mut mismatch = None;
+    for (position, c) in input.chars().enumerate() {
+        if matches!(c, '(' | '[' | '{') {
+            stack.push((c, position));
+            steps.push(Step {
+                description: format!("Push {} from position {}", c, position),
+                highlight_indices: vec![],
+                active_indices: vec![stack.len() - 1],
+                metadata: serde_json::json!({
+                    "operation": "push",
+                    "position": position,
+                    "stack_state": stack_state(&stack)
+                }),
+                detail: None,
+            });
+            continue;
+        }
+
+        let Some(expected) = opener(c) else {
+            continue;
+        };
+
+        match stack.last() {
+            Some(&(top, _)) if top == expected => {
+                stack.pop();
+                steps.push(Step {
+                    description: format!("{} at position {} closes {}, pop it", c, position, top),
+                    highlight_indices: vec![],
+                    active_indices: vec![stack.len()],
+                    metadata: serde_json::json!({
+                        "operation": "pop",
+                        "position": position,
+                        "stack_state": stack_state(&stack)
+                    }),
+                    detail: None,
+                });
+            }
+            Some(&(top, _)) => {
+                steps.push(Step {
+                    description: format!("{} at position {} does not close {}", c, position, top),
+                    highlight_indices: vec![stack.len() - 1],
+                    active_indices: vec![],
+                    metadata: serde_json::json!({
+                        "operation": "mismatch",
+                        "position": position,
+                        "mismatch_position": position,
+                        "stack_state": stack_state(&stack)
+                    }),
+                    detail: None,
+                });
+                mismatch = Some(position);
+                break;
+            }
+            None => {
+                steps.push(Step {
+                    description: format!("{} at position {} has nothing to close", c, position),
+                    highlight_indices: vec![],
+                    active_indices: vec![],
+                    metadata: serde_json::json!({
+                        "operation": "mismatch",
+                        "position": position,
+                        "mismatch_position": position,
+                        "stack_state": stack_state(&stack)
+                    }),
+                    detail: None,
+                });
+                mismatch = Some(position);
+                break;
+            }
+        }
+    }
+
+    if mismatch.is_none() {
+        if let Some(&(c, position)) = stack.first() {
+            mismatch = Some(position);
+            steps.push(Step {
+                description: format!("End of input, {} at position {} is never closed", c, position),
+                highlight_indices: vec![0],
+                active_indices: vec![],
+                metadata: serde_json::json!({
+                    "operation": "mismatch",
+                    "mismatch_position": position,
+                    "stack_state": stack_state(&stack)
+                }),
+                detail: None,
+            });
+        }
+    }
+
+    let balanced = mismatch.is_none() && stack.is_empty();
+    steps.push(Step {
+        description: match mismatch {
+            Some(position) => format!("Not balanced: first mismatch at position {}", position),
+            None => "Balanced: every bracket is closed in order".to_string(),
+        },
+        highlight_indices: vec![],
+        active_indices: vec![],
+        metadata: serde_json::json!({
+            "balanced": balanced,
+            "mismatch_position": mismatch,
+            "stack_state": stack_state(&stack)
+        }),
+        detail: None,
+    });
+
+    (balanced, steps.finish(&[]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_balanced() {
+        let (balanced, steps) = check_balanced_with_steps("([]{})");
+        assert!(balanced);
+        assert_eq!(steps.iter().filter(|s| s.metadata["operation"] == "push").count(), 3);
+        assert_eq!(steps.last().unwrap().metadata["mismatch_position"], serde_json::Value::Null);
+
+        let (balanced, steps) = check_balanced_with_steps("([)]");
+        assert!(!balanced);
+        assert_eq!(steps.last().unwrap().metadata["mismatch_position"], 2);
+
+        let (balanced, _) = check_balanced_with_steps("");
+        assert!(balanced);
+    }
+
+    #[test]
+    fn test_check_balanced_unclosed_and_stray() {
+        let (balanced, steps) = check_balanced_with_steps("a(b[c]");
+        assert!(!balanced);
+        assert_eq!(steps.last().unwrap().metadata["mismatch_position"], 1);
+
+        let (balanced, steps) = check_balanced_with_steps("())");
+        assert!(!balanced);
+        assert_eq!(steps.last().unwrap().metadata["mismatch_position"], 2);
+    }
+}
@@ -0,0 +1,127 @@
+//! Element-wise comparison of two arrays for diffing demos.
+
+use crate::recorder::StepRecorder;
+use crate::traits::Step;
+
+/// Walk `a` and `b` in parallel, marking each position equal or unequal.
+///
+/// Indices refer to positions in both arrays. Differing positions are
+/// highlighted and matching ones marked active. If the lengths differ, the
+/// longer array's tail is reported as extra and each tail index counts as a
+/// difference. The final step's metadata holds `"differences"` and
+/// `"diff_indices"`.
+pub fn compare_arrays_with_steps(a: &[i32], b: &[i32]) -> Vec<Step> {
+    let mut steps = StepRecorder::new();
+    let common = a.len().min(b.len());
+    let mut diff_indices = Vec::new();
+
+    steps.push(Step {
+        description: format!("Comparing arrays of length {} and {}", a.len(), b.len()),
+        highlight_indices: vec![],
+        active_indices: vec![],
+        metadata: serde_json::json!({
+            "operation": "compare_arrays",
+            "a": a.to_vec(),
+            "b": b.to_vec()
+        }),
+        detail: None,
+    });
+
+    for i in 0..common {
+        if steps.is_truncated() {
+            break;
+        }
+
+        let equal = a[i] == b[i];
+        if !equal {
+            diff_indices.push(i);
+        }
+
+        steps.push(Step {
+            description: if equal {
+                format!("Index {}: {} == {}", i, a[i], b[i])
+            } else {
+                format!("Index {}: {} != {}", i, a[i], b[i])
+            },
+            highlight_indices: if equal { vec![] } else { vec![i] },
+            active_indices: if equal { vec![i] } else { vec![] },
+            metadata: serde_json::json!({
+                "operation": "compare",
+                "index": i,
+                "equal": equal
+            }),
+            detail: None,
+        });
+    }
+
+    let (longer, name) = if a.len() > b.len() { (a, "first") } else { (b, "second") };
+    if longer.len() > common {
+        let tail: Vec<usize> = (common..longer.len()).collect();
+        diff_indices.extend(&tail);
+
+        steps.push(Step {
+            description: format!(
+                "The {} array has {} extra element(s): {:?}",
+                name,
+                tail.len(),
+                &longer[common..]
+            ),
+            highlight_indices: tail,
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": "extra_tail",
+                "array": name,
+                "tail": longer[common..].to_vec()
+            }),
+            detail: None,
+        });
+    }
+
+    steps.push(Step {
+        description: format!("Comparison complete: {} difference(s)", diff_indices.len()),
+        highlight_indices: diff_indices.clone(),
+        active_indices: vec![],
+        metadata: serde_json::json!({
+            "differences": diff_indices.len(),
+            "diff_indices": diff_indices
+        }),
+        detail: None,
+    });
+
+    steps.finish(a)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_equal_arrays() {
+        let steps = compare_arrays_with_steps(&[1, 2, 3], &[1, 2, 3]);
+        let last = steps.last().unwrap();
+
+        assert_eq!(last.metadata["differences"], 0);
+        assert!(steps.iter().filter(|s| s.metadata["operation"] == "compare").all(|s| s.metadata["equal"] == true));
+    }
+
+    #[test]
+    fn test_compare_single_difference() {
+        let steps = compare_arrays_with_steps(&[1, 2, 3], &[1, 9, 3]);
+        let last = steps.last().unwrap();
+
+        assert_eq!(last.metadata["differences"], 1);
+        assert_eq!(last.metadata["diff_indices"], serde_json::json!([1]));
+        assert_eq!(steps[2].metadata["equal"], false);
+        assert_eq!(steps[2].highlight_indices, vec![1]);
+    }
+
+    #[test]
+    fn test_compare_unequal_lengths() {
+        let steps = compare_arrays_with_steps(&[1, 2], &[1, 2, 7, 8]);
+        let last = steps.last().unwrap();
+
+        assert!(steps.iter().any(|s| s.metadata["operation"] == "extra_tail" && s.metadata["array"] == "second"));
+        assert_eq!(last.metadata["differences"], 2);
+        assert_eq!(last.metadata["diff_indices"], serde_json::json!([2, 3]));
+    }
+}
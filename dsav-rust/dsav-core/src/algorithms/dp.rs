@@ -0,0 +1,323 @@
+//! Dynamic programming algorithm implementations with step-by-step visualization.
+
+use crate::error::{DsavError, Result};
+use crate::recorder::StepRecorder;
+use crate::traits::Step;
+
+/// Kadane's algorithm for the maximum subarray sum.
+///
+/// Returns the best sum together with the animation steps. Arrays made up
+/// entirely of negative values yield their largest single element. Sums are
+/// kept as i64 so long runs of large values can't overflow.
+pub fn kadane_with_steps(arr: &[i32]) -> Result<(i64, Vec<Step>)> {
+    let mut steps = Vec::new();
+
+    if arr.is_empty() {
+        return Err(DsavError::EmptyStructure);
+    }
+
+    let mut current_sum = arr[0] as i64;
+    let mut best_sum = arr[0] as i64;
+    let mut current_start = 0;
+    let mut best_start = 0;
+    let mut best_end = 0;
+
+    steps.push(Step {
+        description: format!("Starting Kadane's algorithm with {} at index 0", arr[0]),
+        highlight_indices: vec![0],
+        active_indices: vec![0],
+        metadata: serde_json::json!({
+            "operation": "kadane",
+            "current_sum": current_sum,
+            "best_sum": best_sum,
+            "best_start": best_start,
+            "best_end": best_end
+        }),
+        detail: None,
+    });
+
+    for (i, &value) in arr.iter().enumerate().skip(1) {
+        let value = value as i64;
+        let extended = current_sum + value;
+
+        if value > extended {
+            current_sum = value;
+            current_start = i;
+
+            steps.push(Step {
+                description: format!(
+                    "{} alone beats extending ({}), starting a new subarray at index {}",
+                    value, extended, i
+                ),
+                highlight_indices: vec![i],
+                active_indices: (best_start..=best_end).collect(),
+                metadata: serde_json::json!({
+                    "operation": "restart",
+                    "index": i,
+                    "current_sum": current_sum,
+                    "best_sum": best_sum,
+                    "best_start": best_start,
+                    "best_end": best_end
+                }),
+                detail: None,
+            });
+        } else {
+            current_sum = extended;
+
+            steps.push(Step {
+                description: format!(
+                    "Extending subarray with {}, current sum is now {}",
+                    value, current_sum
+                ),
+                highlight_indices: (current_start..=i).collect(),
+                active_indices: (best_start..=best_end).collect(),
+                metadata: serde_json::json!({
+                    "operation": "extend",
+                    "index": i,
+                    "current_sum": current_sum,
+                    "best_sum": best_sum,
+                    "best_start": best_start,
+                    "best_end": best_end
+                }),
+                detail: None,
+            });
+        }
+
+        if current_sum > best_sum {
+            best_sum = current_sum;
+            best_start = current_start;
+            best_end = i;
+
+            steps.push(Step {
+                description: format!(
+                    "New best sum {} for subarray [{}..={}]",
+                    best_sum, best_start, best_end
+                ),
+                highlight_indices: vec![i],
+                active_indices: (best_start..=best_end).collect(),
+                metadata: serde_json::json!({
+                    "operation": "new_best",
+                    "current_sum": current_sum,
+                    "best_sum": best_sum,
+                    "best_start": best_start,
+                    "best_end": best_end
+                }),
+                detail: None,
+            });
+        }
+    }
+
+    steps.push(Step {
+        description: format!(
+            "Maximum subarray sum is {} (indices {} to {})",
+            best_sum, best_start, best_end
+        ),
+        highlight_indices: vec![],
+        active_indices: (best_start..=best_end).collect(),
+        metadata: serde_json::json!({
+            "current_sum": current_sum,
+            "best_sum": best_sum,
+            "best_start": best_start,
+            "best_end": best_end
+        }),
+        detail: None,
+    });
+
+    Ok((best_sum, steps))
+}
+
+/// Length of the longest strictly increasing subsequence, by the O(n²)
+/// dynamic program.
+///
+/// `dp[i]` is the length of the longest increasing subsequence ending at
+/// index `i`: one more than the best `dp[j]` over earlier `j` with a smaller
+/// value, or 1 if there is none. Each step fills one entry, highlighting the
+/// element being extended with the previous element it extends marked
+/// active, and carries the `dp` array so far. The last steps follow the
+/// recorded predecessors back from the best entry to reconstruct one LIS.
+/// The table is always filled completely, even once the step limit stops
+/// recording.
+pub fn lis_with_steps(arr: &[i32]) -> (usize, Vec<Step>) {
+    let mut steps = StepRecorder::new();
+    let mut dp = vec![1; arr.len()];
+    let mut prev: Vec<Option<usize>> = vec![None; arr.len()];
+
+    steps.push(Step {
+        description: "Each element starts as an increasing subsequence of length 1".to_string(),
+        highlight_indices: vec![],
+        active_indices: vec![],
+        metadata: serde_json::json!({
+            "operation": "lis",
+            "dp": dp,
+            "array_state": arr.to_vec()
+        }),
+        detail: None,
+    });
+
+    for i in 0..arr.len() {
+        for j in 0..i {
+            if arr[j] < arr[i] && dp[j] + 1 > dp[i] {
+                dp[i] = dp[j] + 1;
+                prev[i] = Some(j);
+            }
+        }
+
+        // Past the limit the step would be dropped; skip building it
+        if steps.is_truncated() {
+            continue;
+        }
+
+        steps.push(Step {
+            description: match prev[i] {
+                Some(j) => format!(
+                    "dp[{}] = {}: {} extends the subsequence of length {} ending at {}",
+                    i, dp[i], arr[i], dp[j], arr[j]
+                ),
+                None => format!("dp[{}] = 1: no earlier element is smaller than {}", i, arr[i]),
+            },
+            highlight_indices: vec![i],
+            active_indices: prev[i].into_iter().collect(),
+            metadata: serde_json::json!({
+                "operation": "fill",
+                "index": i,
+                "previous": prev[i],
+                "dp": dp,
+                "array_state": arr.to_vec()
+            }),
+            detail: None,
+        });
+    }
+
+    // Ties keep the first index, so the reconstruction is deterministic
+    let Some((end, &length)) = dp.iter().enumerate().rev().max_by_key(|&(_, &len)| len) else {
+        steps.push(Step {
+            description: "Empty array: the longest increasing subsequence has length 0".to_string(),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "length": 0,
+                "lis": []
+            }),
+            detail: None,
+        });
+        return (0, steps.finish(arr));
+    };
+
+    let mut path = Vec::new();
+    let mut current = Some(end);
+    while let Some(idx) = current {
+        path.push(idx);
+        current = prev[idx];
+        if steps.is_truncated() {
+            continue;
+        }
+
+        steps.push(Step {
+            description: match prev[idx] {
+                Some(j) => format!("{} at index {} came from {} at index {}", arr[idx], idx, arr[j], j),
+                None => format!("{} at index {} starts the subsequence", arr[idx], idx),
+            },
+            highlight_indices: vec![idx],
+            active_indices: path.clone(),
+            metadata: serde_json::json!({
+                "operation": "reconstruct",
+                "index": idx,
+                "dp": dp,
+                "array_state": arr.to_vec()
+            }),
+            detail: None,
+        });
+    }
+    path.reverse();
+
+    let values: Vec<i32> = path.iter().map(|&idx| arr[idx]).collect();
+    steps.push(Step {
+        description: format!("Longest increasing subsequence has length {}: {:?}", length, values),
+        highlight_indices: vec![],
+        active_indices: path.clone(),
+        metadata: serde_json::json!({
+            "length": length,
+            "lis": values,
+            "indices": path,
+            "dp": dp,
+            "array_state": arr.to_vec()
+        }),
+        detail: None,
+    });
+
+    (length, steps.finish(arr))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kadane_classic() {
+        let arr = vec![-2, 1, -3, 4, -1, 2, 1, -5, 4];
+        let (best, steps) = kadane_with_steps(&arr).unwrap();
+        assert_eq!(best, 6);
+
+        let last = steps.last().unwrap();
+        assert_eq!(last.active_indices, vec![3, 4, 5, 6]);
+        assert_eq!(last.metadata["best_sum"], 6);
+    }
+
+    #[test]
+    fn test_kadane_all_negative() {
+        let arr = vec![-8, -3, -6, -2, -5, -4];
+        let (best, steps) = kadane_with_steps(&arr).unwrap();
+        assert_eq!(best, -2);
+        assert_eq!(steps.last().unwrap().active_indices, vec![3]);
+    }
+
+    #[test]
+    fn test_kadane_records_sums_every_step() {
+        let arr = vec![1, -2, 3];
+        let (_, steps) = kadane_with_steps(&arr).unwrap();
+        for step in &steps {
+            assert!(step.metadata.get("current_sum").is_some());
+            assert!(step.metadata.get("best_sum").is_some());
+        }
+    }
+
+    #[test]
+    fn test_kadane_sum_beyond_i32() {
+        let arr = vec![i32::MAX, i32::MAX, -1];
+        let (best, _) = kadane_with_steps(&arr).unwrap();
+        assert_eq!(best, 2 * i32::MAX as i64);
+    }
+
+    #[test]
+    fn test_kadane_empty() {
+        assert!(kadane_with_steps(&[]).is_err());
+    }
+
+    #[test]
+    fn test_lis() {
+        let arr = vec![10, 9, 2, 5, 3, 7, 101, 18];
+        let (length, steps) = lis_with_steps(&arr);
+        assert_eq!(length, 4);
+
+        let last = steps.last().unwrap();
+        assert_eq!(last.metadata["dp"], serde_json::json!([1, 1, 1, 2, 2, 3, 4, 4]));
+        assert_eq!(last.metadata["lis"], serde_json::json!([2, 5, 7, 101]));
+        assert_eq!(last.active_indices, vec![2, 3, 5, 6]);
+
+        assert_eq!(lis_with_steps(&[]).0, 0);
+        assert_eq!(lis_with_steps(&[3, 3, 3]).0, 1);
+    }
+
+    #[test]
+    fn test_lis_past_step_limit() {
+        use crate::recorder::set_max_steps;
+
+        let arr: Vec<i32> = (1..=20).collect();
+        set_max_steps(Some(3));
+        let (length, steps) = lis_with_steps(&arr);
+        set_max_steps(None);
+
+        assert_eq!(length, 20);
+        assert_eq!(steps.last().unwrap().metadata["truncated"], true);
+    }
+}
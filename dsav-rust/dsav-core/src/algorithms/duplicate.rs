@@ -0,0 +1,154 @@
+//! Finding a repeated value with Floyd's cycle detection, reading each value
+//! as a pointer to the next index.
+
+use crate::error::{DsavError, Result};
+use crate::recorder::StepRecorder;
+use crate::traits::Step;
+
+/// Find the duplicate in an array of `n + 1` values drawn from `1..=n`.
+///
+/// Following `i -> arr[i]` from index 0 must enter a cycle, and the cycle's
+/// entrance is an index two elements point at: the duplicate. Phase one
+/// races a slow pointer (one hop) against a fast one (two hops) until they
+/// meet inside the cycle; phase two restarts slow from 0 and moves both one
+/// hop at a time until they meet at the entrance.
+///
+/// Returns `DsavError::InvalidState` if the array has fewer than two
+/// elements or any value falls outside `1..=n`. Both phases always run to
+/// the end, so the answer is right even when the step limit cuts recording short.
+pub fn find_duplicate_with_steps(arr: &[i32]) -> Result<(i32, Vec<Step>)> {
+    if arr.len() < 2 {
+        return Err(DsavError::InvalidState {
+            reason: "Need at least two values to contain a duplicate".to_string(),
+        });
+    }
+
+    let n = arr.len() - 1;
+    if let Some(&bad) = arr.iter().find(|&&value| value < 1 || value as usize > n) {
+        return Err(DsavError::InvalidState {
+            reason: format!("Value {} is outside 1..={}; every value must be a valid index", bad, n),
+        });
+    }
+
+    let next = |index: usize| arr[index] as usize;
+    let mut steps = StepRecorder::new();
+
+    steps.push(Step {
+        description: "Treating each value as a pointer to the next index, starting at index 0".to_string(),
+        highlight_indices: vec![0],
+        active_indices: vec![],
+        metadata: serde_json::json!({
+            "operation": "find_duplicate",
+            "array_state": arr.to_vec()
+        }),
+        detail: None,
+    });
+
+    let mut slow = next(0);
+    let mut fast = next(next(0));
+    loop {
+        steps.push(Step {
+            description: format!("Slow at index {}, fast at index {}", slow, fast),
+            highlight_indices: vec![slow, fast],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": "move",
+                "phase": "find_cycle",
+                "slow": slow,
+                "fast": fast,
+                "array_state": arr.to_vec()
+            }),
+            detail: None,
+        });
+
+        if slow == fast {
+            break;
+        }
+        slow = next(slow);
+        fast = next(next(fast));
+    }
+
+    steps.push(Step {
+        description: format!("Pointers met at index {} inside the cycle; restarting slow from index 0", slow),
+        highlight_indices: vec![0, fast],
+        active_indices: vec![],
+        metadata: serde_json::json!({
+            "operation": "meet",
+            "phase": "find_cycle",
+            "slow": 0,
+            "fast": fast,
+            "array_state": arr.to_vec()
+        }),
+        detail: None,
+    });
+
+    slow = 0;
+    while slow != fast {
+        slow = next(slow);
+        fast = next(fast);
+
+        steps.push(Step {
+            description: format!("Both move one hop: slow at index {}, fast at index {}", slow, fast),
+            highlight_indices: vec![slow, fast],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": "move",
+                "phase": "find_entrance",
+                "slow": slow,
+                "fast": fast,
+                "array_state": arr.to_vec()
+            }),
+            detail: None,
+        });
+    }
+
+    let duplicate = slow as i32;
+    steps.push(Step {
+        description: format!("The cycle starts at index {}, so {} is the duplicate", slow, duplicate),
+        highlight_indices: vec![],
+        active_indices: (0..arr.len()).filter(|&i| arr[i] == duplicate).collect(),
+        metadata: serde_json::json!({
+            "duplicate": duplicate,
+            "array_state": arr.to_vec()
+        }),
+        detail: None,
+    });
+
+    Ok((duplicate, steps.finish(arr)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_duplicate() {
+        let (duplicate, steps) = find_duplicate_with_steps(&[1, 3, 4, 2, 2]).unwrap();
+        assert_eq!(duplicate, 2);
+        assert_eq!(steps.last().unwrap().active_indices, vec![3, 4]);
+        assert!(steps.iter().any(|s| s.metadata["phase"] == "find_entrance"));
+
+        assert_eq!(find_duplicate_with_steps(&[3, 1, 3, 4, 2]).unwrap().0, 3);
+        assert_eq!(find_duplicate_with_steps(&[1, 1]).unwrap().0, 1);
+    }
+
+    #[test]
+    fn test_find_duplicate_past_step_limit() {
+        use crate::recorder::set_max_steps;
+
+        let arr = [2, 3, 4, 5, 6, 7, 8, 9, 1, 5];
+        set_max_steps(Some(2));
+        let (duplicate, steps) = find_duplicate_with_steps(&arr).unwrap();
+        set_max_steps(None);
+
+        assert_eq!(duplicate, 5);
+        assert_eq!(steps.last().unwrap().metadata["truncated"], true);
+    }
+
+    #[test]
+    fn test_find_duplicate_rejects_out_of_range() {
+        assert!(matches!(find_duplicate_with_steps(&[1, 5, 2]), Err(DsavError::InvalidState { .. })));
+        assert!(matches!(find_duplicate_with_steps(&[0, 1, 1]), Err(DsavError::InvalidState { .. })));
+        assert!(matches!(find_duplicate_with_steps(&[1]), Err(DsavError::InvalidState { .. })));
+    }
+}
@@ -0,0 +1,243 @@
+//! Infix to postfix conversion with the shunting-yard algorithm, keeping
+//! pending operators on a [`VisualizableStack`].
+
+use crate::error::{DsavError, Result};
+use crate::recorder::StepRecorder;
+use crate::structures::VisualizableStack;
+use crate::traits::Step;
+
+/// Binding strength of a supported operator; higher binds tighter.
+fn precedence(op: char) -> Option<u8> {
+    match op {
+        '+' | '-' => Some(1),
+        '*' | '/' => Some(2),
+        _ => None,
+    }
+}
+
+/// The single character of a one-character token.
+fn single_char(token: &str) -> Option<char> {
+    let mut chars = token.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => None,
+    }
+}
+
+/// Operators on the stack, bottom first, as strings for display.
+fn stack_state(stack: &VisualizableStack) -> Vec<String> {
+    stack
+        .to_vec()
+        .into_iter()
+        .filter_map(|code| char::from_u32(code as u32))
+        .map(String::from)
+        .collect()
+}
+
+fn mismatched(reason: &str) -> DsavError {
+    DsavError::InvalidState {
+        reason: format!("Mismatched parentheses: {}", reason),
+    }
+}
+
+/// Convert infix `tokens` to postfix order, e.g. `3 + 4 * 2` to `3 4 2 * +`.
+///
+/// Supports `+ - * /` (left-associative) and parentheses; any other token
+/// made of letters and digits is an operand. Operators wait on the stack until
+/// one of lower precedence, a `)` or the end of input pops them to the output.
+/// Every step's metadata holds the `output` so far and the operator
+/// `stack_state`. Mismatched parentheses return `DsavError::InvalidState`.
+pub fn infix_to_postfix_with_steps(tokens: &[&str]) -> Result<(Vec<String>, Vec<Step>)> {
+    let mut steps = StepRecorder::new();
+    let mut stack = VisualizableStack::with_capacity(tokens.len().max(1));
+    let mut output: Vec<String> = Vec::new();
+
+    steps.push(Step {
+        description: format!("Converting {} to postfix", tokens.join(" ")),
+        highlight_indices: vec![],
+        active_indices: vec![],
+        metadata: serde_json::json!({
+            "operation": "infix_to_postfix",
+            "tokens": tokens,
+            "output": output,
+            "stack_state": stack_state(&stack)
+        }),
+        detail: None,
+    });
+
+    for &token in tokens {
+        match single_char(token) {
+            Some('(') => {
+                stack.push('(' as i32)?;
+                steps.push(Step {
+                    description: "Push ( to hold operators until the matching )".to_string(),
+                    highlight_indices: vec![],
+                    active_indices: vec![stack.len() - 1],
+                    metadata: serde_json::json!({
+                        "operation": "push",
+                        "token": token,
+                        "output": output,
+                        "stack_state": stack_state(&stack)
+                    }),
+                    detail: None,
+                });
+            }
+
+            Some(')') => {
+                loop {
+                    let top = stack.pop().map_err(|_| mismatched("')' has no matching '('"))?;
+                    if top == '(' as i32 {
+                        steps.push(Step {
+                            description: "Pop ( and discard the pair of parentheses".to_string(),
+                            highlight_indices: vec![],
+                            active_indices: vec![],
+                            metadata: serde_json::json!({
+                                "operation": "discard",
+                                "token": token,
+                                "output": output,
+                                "stack_state": stack_state(&stack)
+                            }),
+                            detail: None,
+                        });
+                        break;
+                    }
+
+                    output.push(String::from(char::from_u32(top as u32).unwrap_or('?')));
+                    steps.push(pop_step(
+                        &format!("Pop {} to output while looking for (", output[output.len() - 1]),
+                        &output,
+                        &stack,
+                    ));
+                }
+            }
+
+            Some(op) if precedence(op).is_some() => {
+                let prec = precedence(op);
+
+                while let Ok(top) = stack.peek() {
+                    // A '(' has no precedence, and None sorts below any operator's
+                    let top = char::from_u32(top as u32).unwrap_or('(');
+                    if precedence(top) < prec {
+                        break;
+                    }
+
+                    stack.pop()?;
+                    output.push(top.to_string());
+                    steps.push(pop_step(
+                        &format!("{} binds at least as tightly as {}, pop it to output", top, op),
+                        &output,
+                        &stack,
+                    ));
+                }
+
+                stack.push(op as i32)?;
+                steps.push(Step {
+                    description: format!("Push operator {}", op),
+                    highlight_indices: vec![],
+                    active_indices: vec![stack.len() - 1],
+                    metadata: serde_json::json!({
+                        "operation": "push",
+                        "token": token,
+                        "output": output,
+                        "stack_state": stack_state(&stack)
+                    }),
+                    detail: None,
+                });
+            }
+
+            _ if !token.is_empty() && token.chars().all(char::is_alphanumeric) => {
+                output.push(token.to_string());
+                steps.push(Step {
+                    description: format!("Operand {} goes straight to output", token),
+                    highlight_indices: vec![],
+                    active_indices: vec![],
+                    metadata: serde_json::json!({
+                        "operation": "output",
+                        "token": token,
+                        "output": output,
+                        "stack_state": stack_state(&stack)
+                    }),
+                    detail: None,
+                });
+            }
+
+            _ => {
+                return Err(DsavError::InvalidState {
+                    reason: format!("Unexpected token '{}'", token),
+                })
+            }
+        }
+    }
+
+    while let Ok(top) = stack.pop() {
+        if top == '(' as i32 {
+            return Err(mismatched("'(' is never closed"));
+        }
+
+        output.push(String::from(char::from_u32(top as u32).unwrap_or('?')));
+        steps.push(pop_step(
+            &format!("End of input, pop {} to output", output[output.len() - 1]),
+            &output,
+            &stack,
+        ));
+    }
+
+    steps.push(Step {
+        description: format!("Postfix: {}", output.join(" ")),
+        highlight_indices: vec![],
+        active_indices: vec![],
+        metadata: serde_json::json!({
+            "output": output,
+            "stack_state": stack_state(&stack)
+        }),
+        detail: None,
+    });
+
+    Ok((output, steps.finish(&stack.to_vec())))
+}
+
+fn pop_step(description: &str, output: &[String], stack: &VisualizableStack) -> Step {
+    Step {
+        description: description.to_string(),
+        highlight_indices: vec![],
+        active_indices: vec![],
+        metadata: serde_json::json!({
+            "operation": "pop",
+            "output": output,
+            "stack_state": stack_state(stack)
+        }),
+        detail: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn convert(expression: &str) -> Result<Vec<String>> {
+        let tokens: Vec<&str> = expression.split_whitespace().collect();
+        infix_to_postfix_with_steps(&tokens).map(|(output, _)| output)
+    }
+
+    #[test]
+    fn test_postfix_respects_precedence() {
+        assert_eq!(convert("3 + 4 * 2").unwrap(), vec!["3", "4", "2", "*", "+"]);
+        assert_eq!(convert("8 - 3 - 2").unwrap(), vec!["8", "3", "-", "2", "-"]);
+    }
+
+    #[test]
+    fn test_postfix_with_parentheses() {
+        let tokens = ["(", "3", "+", "4", ")", "*", "2"];
+        let (output, steps) = infix_to_postfix_with_steps(&tokens).unwrap();
+
+        assert_eq!(output, vec!["3", "4", "+", "2", "*"]);
+        assert!(steps.iter().any(|s| s.metadata["operation"] == "discard"));
+        assert_eq!(steps.last().unwrap().metadata["stack_state"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn test_postfix_mismatched_parentheses() {
+        assert!(matches!(convert("( 3 + 4"), Err(DsavError::InvalidState { .. })));
+        assert!(matches!(convert("3 + 4 )"), Err(DsavError::InvalidState { .. })));
+    }
+}
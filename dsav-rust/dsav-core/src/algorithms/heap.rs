@@ -0,0 +1,161 @@
+//! Bottom-up max-heap construction with step-by-step visualization.
+
+use crate::error::Result;
+use crate::recorder::StepRecorder;
+use crate::traits::Step;
+
+/// The array read as a complete binary tree, one vector per level, so
+/// `[9, 5, 7, 1, 3]` becomes `[[9], [5, 7], [1, 3]]`.
+fn tree_levels(arr: &[i32]) -> Vec<Vec<i32>> {
+    let mut levels = Vec::new();
+    let mut start = 0;
+    let mut width = 1;
+    while start < arr.len() {
+        let end = (start + width).min(arr.len());
+        levels.push(arr[start..end].to_vec());
+        start = end;
+        width *= 2;
+    }
+    levels
+}
+
+/// Rearrange `arr` into a max-heap in place, so `arr[i] >= arr[2i+1]` and
+/// `arr[i] >= arr[2i+2]` wherever those children exist.
+///
+/// Leaves are already one-element heaps, so the build sifts down each
+/// internal node from the last one (`n / 2 - 1`) back to the root. A sift-down
+/// compares a node with its children and swaps it with the larger child until
+/// neither child is bigger. Each step's metadata holds the array read as a
+/// tree in `tree_levels`. The total work is O(n), since most nodes sit near
+/// the bottom and sift only a short distance.
+///
+/// Steps stop at the step limit, but the heap is always finished.
+pub fn heapify_with_steps(arr: &mut [i32]) -> Result<Vec<Step>> {
+    let mut steps = StepRecorder::new();
+    let n = arr.len();
+
+    steps.push(Step {
+        description: format!("Building a max-heap from {} element(s)", n),
+        highlight_indices: vec![],
+        active_indices: vec![],
+        metadata: serde_json::json!({
+            "operation": "heapify",
+            "tree_levels": tree_levels(arr),
+            "array_state": arr.to_vec()
+        }),
+        detail: None,
+    });
+
+    for start in (0..n / 2).rev() {
+        steps.push(Step {
+            description: format!("Sift down {} at index {}", arr[start], start),
+            highlight_indices: vec![start],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": "sift_down",
+                "index": start,
+                "tree_levels": tree_levels(arr),
+                "array_state": arr.to_vec()
+            }),
+            detail: None,
+        });
+
+        let mut node = start;
+        loop {
+            let children: Vec<usize> = [2 * node + 1, 2 * node + 2].into_iter().filter(|&c| c < n).collect();
+            let Some(&largest) = children.iter().max_by_key(|&&c| arr[c]) else {
+                break;
+            };
+
+            steps.push(Step {
+                description: format!(
+                    "Compare {} with its children {}",
+                    arr[node],
+                    children.iter().map(|&c| arr[c].to_string()).collect::<Vec<_>>().join(" and ")
+                ),
+                highlight_indices: vec![node],
+                active_indices: children.clone(),
+                metadata: serde_json::json!({
+                    "operation": "compare",
+                    "index": node,
+                    "children": children,
+                    "tree_levels": tree_levels(arr),
+                    "array_state": arr.to_vec()
+                }),
+                detail: None,
+            });
+
+            if arr[largest] <= arr[node] {
+                steps.push(Step {
+                    description: format!("{} is not smaller than its children, sift-down done", arr[node]),
+                    highlight_indices: vec![],
+                    active_indices: vec![node],
+                    metadata: serde_json::json!({
+                        "operation": "settled",
+                        "index": node,
+                        "tree_levels": tree_levels(arr),
+                        "array_state": arr.to_vec()
+                    }),
+                    detail: None,
+                });
+                break;
+            }
+
+            arr.swap(node, largest);
+            steps.push(Step {
+                description: format!("Swap {} down with larger child {}", arr[largest], arr[node]),
+                highlight_indices: vec![],
+                active_indices: vec![node, largest],
+                metadata: serde_json::json!({
+                    "operation": "swap",
+                    "indices": [node, largest],
+                    "tree_levels": tree_levels(arr),
+                    "array_state": arr.to_vec()
+                }),
+                detail: None,
+            });
+            node = largest;
+        }
+    }
+
+    steps.push(Step {
+        description: match arr.first() {
+            Some(max) => format!("Max-heap built, the root {} is the largest value", max),
+            None => "Array is empty, nothing to heapify".to_string(),
+        },
+        highlight_indices: vec![],
+        active_indices: if n > 0 { vec![0] } else { vec![] },
+        metadata: serde_json::json!({
+            "operation": "heapify_done",
+            "tree_levels": tree_levels(arr),
+            "array_state": arr.to_vec()
+        }),
+        detail: None,
+    });
+
+    Ok(steps.finish(arr))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heapify_satisfies_max_heap_property() {
+        let mut arr = vec![3, 9, 2, 1, 4, 5, 8, 7, 6, 0];
+        let steps = heapify_with_steps(&mut arr).unwrap();
+
+        for i in 0..arr.len() {
+            for child in [2 * i + 1, 2 * i + 2] {
+                if child < arr.len() {
+                    assert!(arr[i] >= arr[child], "arr[{}] = {} < arr[{}] = {}", i, arr[i], child, arr[child]);
+                }
+            }
+        }
+
+        let mut sorted = arr.clone();
+        sorted.sort();
+        assert_eq!(sorted, (0..10).collect::<Vec<_>>());
+        assert_eq!(steps.last().unwrap().metadata["tree_levels"][0], serde_json::json!([9]));
+    }
+}
@@ -0,0 +1,187 @@
+//! Inversion counting with a modified merge sort.
+//!
+//! An inversion is a pair `i < j` with `arr[i] > arr[j]`. While merging, each
+//! right-half element placed before the remaining left-half elements forms an
+//! inversion with every one of them, so all of them are counted at once.
+
+use crate::recorder::StepRecorder;
+use crate::traits::Step;
+
+/// Count the inversions in `arr` in O(n log n), recording the merges.
+///
+/// Sorting happens on a copy; `arr` is untouched and the final step shows it
+/// again. Cross-pair steps carry the running `"inversions"` count and the
+/// `"pairs"` of values just counted.
+pub fn count_inversions_with_steps(arr: &[i32]) -> (usize, Vec<Step>) {
+    let mut steps = StepRecorder::new();
+    let mut work = arr.to_vec();
+    let mut inversions = 0;
+
+    steps.push(Step {
+        description: "Counting inversions with merge sort".to_string(),
+        highlight_indices: vec![],
+        active_indices: vec![],
+        metadata: serde_json::json!({
+            "operation": "count_inversions",
+            "inversions": 0,
+            "array_state": arr.to_vec()
+        }),
+        detail: None,
+    });
+
+    if work.len() > 1 {
+        let last = work.len() - 1;
+        sort_and_count(&mut work, 0, last, &mut inversions, &mut steps);
+    }
+
+    steps.push(Step {
+        description: format!("The array has {} inversion(s)", inversions),
+        highlight_indices: vec![],
+        active_indices: vec![],
+        metadata: serde_json::json!({
+            "inversions": inversions,
+            "array_state": arr.to_vec()
+        }),
+        detail: None,
+    });
+
+    (inversions, steps.finish(arr))
+}
+
+fn sort_and_count(arr: &mut [i32], left: usize, right: usize, inversions: &mut usize, steps: &mut StepRecorder) {
+    if left >= right {
+        return;
+    }
+
+    let mid = left + (right - left) / 2;
+    sort_and_count(arr, left, mid, inversions, steps);
+    sort_and_count(arr, mid + 1, right, inversions, steps);
+    merge_and_count(arr, left, mid, right, inversions, steps);
+}
+
+fn merge_and_count(
+    arr: &mut [i32],
+    left: usize,
+    mid: usize,
+    right: usize,
+    inversions: &mut usize,
+    steps: &mut StepRecorder,
+) {
+    let left_half = arr[left..=mid].to_vec();
+    let right_half = arr[mid + 1..=right].to_vec();
+
+    steps.push(Step {
+        description: format!("Merging [{}..{}] and [{}..{}]", left, mid, mid + 1, right),
+        highlight_indices: (left..=right).collect(),
+        active_indices: vec![],
+        metadata: serde_json::json!({
+            "operation": "merge_start",
+            "inversions": *inversions,
+            "array_state": arr.to_vec()
+        }),
+        detail: None,
+    });
+
+    let (mut i, mut j, mut k) = (0, 0, left);
+
+    while i < left_half.len() && j < right_half.len() {
+        if left_half[i] <= right_half[j] {
+            arr[k] = left_half[i];
+            i += 1;
+        } else {
+            // Every left element still waiting is larger than right_half[j]
+            let remaining = left_half.len() - i;
+            *inversions += remaining;
+
+            let pairs: Vec<[i32; 2]> = left_half[i..].iter().map(|&value| [value, right_half[j]]).collect();
+            let mut cross: Vec<usize> = (left + i..=mid).collect();
+            cross.push(mid + 1 + j);
+
+            steps.push(Step {
+                description: format!(
+                    "{} comes before {} remaining left element(s) {:?}: +{} inversion(s), {} so far",
+                    right_half[j],
+                    remaining,
+                    &left_half[i..],
+                    remaining,
+                    inversions
+                ),
+                highlight_indices: cross,
+                active_indices: vec![],
+                metadata: serde_json::json!({
+                    "operation": "count",
+                    "pairs": pairs,
+                    "inversions": *inversions,
+                    "array_state": arr.to_vec()
+                }),
+                detail: None,
+            });
+
+            arr[k] = right_half[j];
+            j += 1;
+        }
+        k += 1;
+    }
+
+    for &value in left_half[i..].iter().chain(&right_half[j..]) {
+        arr[k] = value;
+        k += 1;
+    }
+
+    steps.push(Step {
+        description: format!("Merged [{}..{}], {} inversion(s) so far", left, right, inversions),
+        highlight_indices: vec![],
+        active_indices: (left..=right).collect(),
+        metadata: serde_json::json!({
+            "operation": "merge_end",
+            "inversions": *inversions,
+            "array_state": arr.to_vec()
+        }),
+        detail: None,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brute_force(arr: &[i32]) -> usize {
+        (0..arr.len())
+            .flat_map(|i| (i + 1..arr.len()).map(move |j| (i, j)))
+            .filter(|&(i, j)| arr[i] > arr[j])
+            .count()
+    }
+
+    #[test]
+    fn test_sorted_has_no_inversions() {
+        let (count, steps) = count_inversions_with_steps(&[1, 2, 3, 4, 5]);
+        assert_eq!(count, 0);
+        assert!(steps.iter().all(|s| s.metadata["operation"] != "count"));
+    }
+
+    #[test]
+    fn test_reversed_has_all_inversions() {
+        for n in [2usize, 5, 10] {
+            let arr: Vec<i32> = (0..n as i32).rev().collect();
+            let (count, steps) = count_inversions_with_steps(&arr);
+
+            assert_eq!(count, n * (n - 1) / 2);
+            assert_eq!(steps.last().unwrap().metadata["inversions"], count);
+            assert_eq!(steps.last().unwrap().metadata["array_state"], serde_json::json!(arr));
+        }
+    }
+
+    #[test]
+    fn test_inversions_match_brute_force() {
+        let arr = [3, 1, 2, 5, 4, 4, 0];
+        let (count, steps) = count_inversions_with_steps(&arr);
+        assert_eq!(count, brute_force(&arr));
+
+        let counted: usize = steps
+            .iter()
+            .filter(|s| s.metadata["operation"] == "count")
+            .map(|s| s.metadata["pairs"].as_array().unwrap().len())
+            .sum();
+        assert_eq!(counted, count);
+    }
+}
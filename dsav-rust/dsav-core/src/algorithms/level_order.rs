@@ -0,0 +1,148 @@
+//! Checking whether a level-order array encodes a binary search tree.
+
+use crate::recorder::StepRecorder;
+use crate::traits::Step;
+
+fn bound_text(bound: Option<i32>, infinity: &str) -> String {
+    bound.map_or_else(|| infinity.to_string(), |bound| bound.to_string())
+}
+
+/// Whether `levels` is the level-order form of a valid BST, where `None`
+/// marks a missing node. The same arrays `VisualizableBST::from_level_order`
+/// accepts.
+pub fn is_valid_bst_array(levels: &[Option<i32>]) -> bool {
+    is_valid_bst_array_with_steps(levels).0
+}
+
+/// Check `levels` as a level-order tree, with the children of index `i` at
+/// `i * 2 + 1` and `i * 2 + 2`.
+///
+/// Every present node but the root needs a present parent. Nodes are then
+/// visited root first, each carrying the open `(min, max)` range its
+/// ancestors allow: a left child inherits its parent as the new max, a right
+/// child as the new min. The check stops at the first node outside its range,
+/// and runs to the end even after the step limit stops recording. Step
+/// indices are array indices, which are also the nodes' render indices.
+pub fn is_valid_bst_array_with_steps(levels: &[Option<i32>]) -> (bool, Vec<Step>) {
+    let mut steps = StepRecorder::new();
+
+    steps.push(Step {
+        description: format!("Checking {} slot(s) as a level-order BST", levels.len()),
+        highlight_indices: vec![],
+        active_indices: vec![],
+        metadata: serde_json::json!({
+            "operation": "validate_bst_array",
+            "levels": levels
+        }),
+        detail: None,
+    });
+
+    let orphan = (1..levels.len()).find(|&idx| levels[idx].is_some() && levels[(idx - 1) / 2].is_none());
+    if let Some(idx) = orphan {
+        steps.push(Step {
+            description: format!("Index {} holds a value but its parent at {} is empty", idx, (idx - 1) / 2),
+            highlight_indices: vec![idx],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": "orphan",
+                "index": idx,
+                "valid": false
+            }),
+            detail: None,
+        });
+        return (false, steps.finish(&[]));
+    }
+
+    let mut checked = Vec::new();
+    // (index, exclusive min, exclusive max)
+    let mut pending = vec![(0, None, None)];
+
+    while let Some((idx, min, max)) = pending.pop() {
+        let Some(value) = levels.get(idx).copied().flatten() else {
+            continue;
+        };
+
+        let in_range = min.is_none_or(|min| value > min) && max.is_none_or(|max| value < max);
+        let range = format!("({}, {})", bound_text(min, "-∞"), bound_text(max, "∞"));
+
+        steps.push(Step {
+            description: if in_range {
+                format!("Index {}: {} is within {}", idx, value, range)
+            } else {
+                format!("Index {}: {} is outside {}; not a BST", idx, value, range)
+            },
+            highlight_indices: vec![idx],
+            active_indices: checked.clone(),
+            metadata: serde_json::json!({
+                "operation": "compare",
+                "index": idx,
+                "value": value,
+                "min": min,
+                "max": max,
+                "valid": in_range
+            }),
+            detail: None,
+        });
+
+        if !in_range {
+            return (false, steps.finish(&[]));
+        }
+        checked.push(idx);
+
+        // Right is pushed first so the left subtree is checked first
+        pending.push((idx * 2 + 2, Some(value), max));
+        pending.push((idx * 2 + 1, min, Some(value)));
+    }
+
+    steps.push(Step {
+        description: format!("All {} node(s) respect their bounds: a valid BST", checked.len()),
+        highlight_indices: vec![],
+        active_indices: checked,
+        metadata: serde_json::json!({
+            "valid": true
+        }),
+        detail: None,
+    });
+
+    (true, steps.finish(&[]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structures::VisualizableBST;
+
+    #[test]
+    fn test_is_valid_bst_array() {
+        let valid = [Some(50), Some(30), Some(70), Some(20), Some(40), None, Some(80)];
+        assert!(is_valid_bst_array(&valid));
+        assert!(VisualizableBST::from_level_order(&valid).is_ok());
+
+        // 60 is the left child of 30: below 30's parent 50 but above 30
+        let invalid = [Some(50), Some(30), Some(70), Some(60), Some(40)];
+        let (is_valid, steps) = is_valid_bst_array_with_steps(&invalid);
+        assert!(!is_valid);
+        let last = steps.last().unwrap();
+        assert_eq!(last.highlight_indices, vec![3]);
+        assert_eq!(last.metadata["max"], 30);
+
+        // 55 is fine for its parent 30 but lies in the left subtree of 50
+        assert!(!is_valid_bst_array(&[Some(50), Some(30), Some(70), None, Some(55)]));
+        assert!(!is_valid_bst_array(&[Some(50), None, Some(70), Some(20)]));
+        assert!(is_valid_bst_array(&[]));
+    }
+
+    #[test]
+    fn test_is_valid_bst_array_past_step_limit() {
+        use crate::recorder::set_max_steps;
+
+        // 10 is the right child of 70, found long after a cap of 3 steps
+        let invalid = [Some(50), Some(30), Some(70), Some(20), Some(40), Some(60), Some(10)];
+        set_max_steps(Some(3));
+        let (is_valid, steps) = is_valid_bst_array_with_steps(&invalid);
+        set_max_steps(None);
+
+        assert!(!is_valid);
+        assert_eq!(steps.last().unwrap().metadata["truncated"], true);
+    }
+}
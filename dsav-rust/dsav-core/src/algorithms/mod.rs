@@ -1,5 +1,43 @@
 //! Algorithm implementations with step-by-step visualization.
 
 pub mod sorting;
+pub mod aggregate;
+pub mod brackets;
+pub mod compare;
+pub mod dp;
+pub mod duplicate;
+pub mod expression;
+pub mod heap;
+pub mod inversions;
+pub mod level_order;
+pub mod partition;
+pub mod shuffle;
+pub mod recursion;
+pub mod rle;
+pub mod scale;
+pub mod selection;
+pub mod stability;
+pub mod two_pointer;
 
-pub use sorting::{bubble_sort_with_steps, insertion_sort_with_steps, quick_sort_with_steps};
+pub use sorting::{
+    bubble_sort_with_progress, bubble_sort_with_steps, bucket_sort_with_progress, bucket_sort_with_steps,
+    insertion_sort_with_progress, insertion_sort_with_steps, quick_sort_with_progress, quick_sort_with_steps,
+    search_range_with_steps,
+};
+pub use aggregate::{average_with_steps, count_with_steps, sum_with_steps};
+pub use brackets::check_balanced_with_steps;
+pub use compare::compare_arrays_with_steps;
+pub use dp::{kadane_with_steps, lis_with_steps};
+pub use duplicate::find_duplicate_with_steps;
+pub use expression::infix_to_postfix_with_steps;
+pub use heap::heapify_with_steps;
+pub use inversions::count_inversions_with_steps;
+pub use level_order::{is_valid_bst_array, is_valid_bst_array_with_steps};
+pub use partition::dutch_flag_with_steps;
+pub use recursion::{simulate_recursion_with_steps, RecursionKind};
+pub use rle::rle_encode_with_steps;
+pub use scale::scale_with_steps;
+pub use selection::median_of_medians_with_steps;
+pub use shuffle::{shuffle_with_steps, shuffle_with_steps_seeded};
+pub use stability::{insertion_sort_tagged_with_steps, selection_sort_tagged_with_steps, tag_duplicates};
+pub use two_pointer::two_sum_with_steps;
@@ -0,0 +1,124 @@
+//! Three-way (Dutch National Flag) partitioning with step-by-step visualization.
+
+use crate::error::Result;
+use crate::recorder::StepRecorder;
+use crate::traits::Step;
+
+/// Half-open ranges of the four regions, for coloring each one.
+fn regions(low: usize, mid: usize, high: usize, n: usize) -> serde_json::Value {
+    serde_json::json!({
+        "less": [0, low],
+        "equal": [low, mid],
+        "unknown": [mid, high],
+        "greater": [high, n]
+    })
+}
+
+/// Rearrange `arr` into `< pivot | == pivot | > pivot` in a single pass.
+///
+/// Three pointers split the array into four regions: `[0, low)` holds values
+/// below the pivot, `[low, mid)` values equal to it, `[mid, high)` values not
+/// yet examined and `[high, n)` values above it. Each step examines
+/// `arr[mid]` and grows one region; every step's metadata holds the
+/// `regions` as half-open `[start, end)` pairs. This is the partition step of
+/// three-way quicksort, which handles many duplicates well.
+pub fn dutch_flag_with_steps(arr: &mut [i32], pivot: i32) -> Result<Vec<Step>> {
+    let mut steps = StepRecorder::new();
+    let n = arr.len();
+    let (mut low, mut mid, mut high) = (0, 0, n);
+
+    steps.push(Step {
+        description: format!("Three-way partition around {}: less | equal | unknown | greater", pivot),
+        highlight_indices: vec![],
+        active_indices: vec![],
+        metadata: serde_json::json!({
+            "operation": "dutch_flag",
+            "pivot": pivot,
+            "regions": regions(low, mid, high, n),
+            "array_state": arr.to_vec()
+        }),
+        detail: None,
+    });
+
+    while mid < high && !steps.is_truncated() {
+        let value = arr[mid];
+
+        let (description, operation) = if value < pivot {
+            arr.swap(low, mid);
+            let description = format!("{} < {}: swap into the less region at {}", value, pivot, low);
+            low += 1;
+            mid += 1;
+            (description, "swap")
+        } else if value > pivot {
+            high -= 1;
+            arr.swap(mid, high);
+            (format!("{} > {}: swap into the greater region at {}", value, pivot, high), "swap")
+        } else {
+            mid += 1;
+            (format!("{} == {}: extend the equal region", value, pivot), "extend")
+        };
+
+        steps.push(Step {
+            description,
+            highlight_indices: [low, mid, high].into_iter().filter(|&i| i < n).collect(),
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": operation,
+                "low": low,
+                "mid": mid,
+                "high": high,
+                "regions": regions(low, mid, high, n),
+                "array_state": arr.to_vec()
+            }),
+            detail: None,
+        });
+    }
+
+    steps.push(Step {
+        description: format!(
+            "Partitioned: {} less, {} equal, {} greater than {}",
+            low,
+            mid - low,
+            n - high,
+            pivot
+        ),
+        highlight_indices: vec![],
+        active_indices: vec![],
+        metadata: serde_json::json!({
+            "regions": regions(low, mid, high, n),
+            "array_state": arr.to_vec()
+        }),
+        detail: None,
+    });
+
+    Ok(steps.finish(arr))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dutch_flag_orders_regions() {
+        let mut arr = vec![2, 0, 1, 2, 1, 0];
+        let steps = dutch_flag_with_steps(&mut arr, 1).unwrap();
+
+        assert_eq!(arr, vec![0, 0, 1, 1, 2, 2]);
+        let last = steps.last().unwrap();
+        assert_eq!(last.metadata["regions"]["less"], serde_json::json!([0, 2]));
+        assert_eq!(last.metadata["regions"]["equal"], serde_json::json!([2, 4]));
+        assert_eq!(last.metadata["regions"]["greater"], serde_json::json!([4, 6]));
+    }
+
+    #[test]
+    fn test_dutch_flag_general_pivot() {
+        let mut arr = vec![9, 5, 1, 5, 7, 3, 5];
+        dutch_flag_with_steps(&mut arr, 5).unwrap();
+
+        let first_equal = arr.iter().position(|&v| v == 5).unwrap();
+        let last_equal = arr.iter().rposition(|&v| v == 5).unwrap();
+        assert!(arr[..first_equal].iter().all(|&v| v < 5));
+        assert!(arr[first_equal..=last_equal].iter().all(|&v| v == 5));
+        assert!(arr[last_equal + 1..].iter().all(|&v| v > 5));
+    }
+}
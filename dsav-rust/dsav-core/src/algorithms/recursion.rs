@@ -0,0 +1,165 @@
+//! Recursion shown through the call stack: each call pushes a frame holding
+//! its argument onto a [`VisualizableStack`], and each return pops it.
+
+use crate::error::{DsavError, Result};
+use crate::recorder::StepRecorder;
+use crate::structures::VisualizableStack;
+use crate::traits::Step;
+
+/// Largest `n` accepted; factorial overflows past 20 and naive Fibonacci
+/// makes too many calls to animate.
+pub const MAX_RECURSION_N: u32 = 20;
+
+/// Which recursive function to simulate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecursionKind {
+    /// `n! = n * (n - 1)!`, with `0! = 1! = 1`.
+    Factorial,
+    /// `fib(n) = fib(n - 1) + fib(n - 2)`, with `fib(0) = 0` and `fib(1) = 1`.
+    Fibonacci,
+}
+
+impl RecursionKind {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Factorial => "factorial",
+            Self::Fibonacci => "fib",
+        }
+    }
+}
+
+/// Evaluate `kind(n)` recursively, recording a step whenever a frame is
+/// pushed (call) or popped (return). Returns the result and the steps.
+///
+/// The deepest stack holds `n` frames for `n >= 1`: the chain of calls from
+/// `n` down to the base case at 1.
+pub fn simulate_recursion_with_steps(n: u32, kind: RecursionKind) -> Result<(u64, Vec<Step>)> {
+    if n > MAX_RECURSION_N {
+        return Err(DsavError::InvalidState {
+            reason: format!("n must be at most {} for {} simulation", MAX_RECURSION_N, kind.name()),
+        });
+    }
+
+    let mut steps = StepRecorder::new();
+    let mut stack = VisualizableStack::with_capacity(n.max(1) as usize);
+    let mut max_depth = 0;
+
+    steps.push(Step {
+        description: format!("Calling {}({})", kind.name(), n),
+        highlight_indices: vec![],
+        active_indices: vec![],
+        metadata: serde_json::json!({
+            "operation": "simulate_recursion",
+            "kind": kind.name(),
+            "n": n
+        }),
+        detail: None,
+    });
+
+    let result = call(n, kind, &mut stack, &mut steps, &mut max_depth)?;
+
+    steps.push(Step {
+        description: format!("{}({}) = {}, call stack is empty again", kind.name(), n, result),
+        highlight_indices: vec![],
+        active_indices: vec![],
+        metadata: serde_json::json!({
+            "result": result,
+            "max_depth": max_depth,
+            "stack_state": stack.to_vec()
+        }),
+        detail: None,
+    });
+
+    Ok((result, steps.finish(&stack.to_vec())))
+}
+
+fn call(
+    n: u32,
+    kind: RecursionKind,
+    stack: &mut VisualizableStack,
+    steps: &mut StepRecorder,
+    max_depth: &mut usize,
+) -> Result<u64> {
+    stack.push(n as i32)?;
+    *max_depth = (*max_depth).max(stack.len());
+    let top = stack.len() - 1;
+
+    steps.push(Step {
+        description: format!("Push frame {}({}), depth {}", kind.name(), n, stack.len()),
+        highlight_indices: vec![],
+        active_indices: vec![top],
+        metadata: serde_json::json!({
+            "operation": "call",
+            "n": n,
+            "depth": stack.len(),
+            "stack_state": stack.to_vec()
+        }),
+        detail: None,
+    });
+
+    let (result, how) = match (kind, n) {
+        (RecursionKind::Factorial, 0 | 1) => (1, "base case".to_string()),
+        (RecursionKind::Fibonacci, 0 | 1) => (n as u64, "base case".to_string()),
+        (RecursionKind::Factorial, _) => {
+            let rest = call(n - 1, kind, stack, steps, max_depth)?;
+            (n as u64 * rest, format!("{} * {}", n, rest))
+        }
+        (RecursionKind::Fibonacci, _) => {
+            let first = call(n - 1, kind, stack, steps, max_depth)?;
+            let second = call(n - 2, kind, stack, steps, max_depth)?;
+            (first + second, format!("{} + {}", first, second))
+        }
+    };
+
+    steps.push(Step {
+        description: format!("Pop frame {}({}), returning {} ({})", kind.name(), n, result, how),
+        highlight_indices: vec![top],
+        active_indices: vec![],
+        metadata: serde_json::json!({
+            "operation": "return",
+            "n": n,
+            "result": result,
+            "depth": stack.len(),
+            "stack_state": stack.to_vec()
+        }),
+        detail: None,
+    });
+    stack.pop()?;
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_factorial_simulation() {
+        let (result, steps) = simulate_recursion_with_steps(4, RecursionKind::Factorial).unwrap();
+
+        assert_eq!(result, 24);
+        let last = steps.last().unwrap();
+        assert_eq!(last.metadata["max_depth"], 4);
+        assert_eq!(last.metadata["stack_state"], serde_json::json!([]));
+
+        let calls = steps.iter().filter(|s| s.metadata["operation"] == "call").count();
+        let returns = steps.iter().filter(|s| s.metadata["operation"] == "return").count();
+        assert_eq!((calls, returns), (4, 4));
+    }
+
+    #[test]
+    fn test_fibonacci_simulation() {
+        let (result, steps) = simulate_recursion_with_steps(5, RecursionKind::Fibonacci).unwrap();
+
+        assert_eq!(result, 5);
+        assert_eq!(steps.last().unwrap().metadata["max_depth"], 5);
+
+        let deepest = steps.iter().filter_map(|s| s.metadata["depth"].as_u64()).max();
+        assert_eq!(deepest, Some(5));
+    }
+
+    #[test]
+    fn test_recursion_rejects_large_n() {
+        assert!(simulate_recursion_with_steps(MAX_RECURSION_N + 1, RecursionKind::Factorial).is_err());
+    }
+}
@@ -0,0 +1,125 @@
+//! Run-length encoding with step-by-step visualization.
+
+use crate::recorder::StepRecorder;
+use crate::traits::Step;
+
+/// Compress `arr` into `(value, count)` pairs, one per run of equal
+/// consecutive values, so `[1, 1, 1, 2, 3, 3]` becomes
+/// `[(1, 3), (2, 1), (3, 2)]`.
+///
+/// A single left-to-right scan keeps the start of the current run. Each step
+/// highlights the element being read and marks the run so far as active; a
+/// run ends when the value changes or the array does, emitting its pair.
+/// The whole input is always encoded, even once the step limit stops
+/// recording.
+pub fn rle_encode_with_steps(arr: &[i32]) -> (Vec<(i32, usize)>, Vec<Step>) {
+    let mut steps = StepRecorder::new();
+    let mut encoded = Vec::new();
+
+    steps.push(Step {
+        description: format!("Run-length encoding {} element(s)", arr.len()),
+        highlight_indices: vec![],
+        active_indices: vec![],
+        metadata: serde_json::json!({
+            "operation": "rle_encode",
+            "array_state": arr.to_vec()
+        }),
+        detail: None,
+    });
+
+    let mut start = 0;
+    for i in 0..arr.len() {
+        // Past the limit steps would be dropped; skip building them
+        let recording = !steps.is_truncated();
+
+        if recording && i > start {
+            steps.push(Step {
+                description: format!("{} continues the run of {}", arr[i], arr[start]),
+                highlight_indices: vec![i],
+                active_indices: (start..i).collect(),
+                metadata: serde_json::json!({
+                    "operation": "compare",
+                    "run_start": start,
+                    "run_length": i - start + 1,
+                    "array_state": arr.to_vec()
+                }),
+                detail: None,
+            });
+        }
+
+        let run_ends = i + 1 == arr.len() || arr[i + 1] != arr[i];
+        if run_ends {
+            let count = i - start + 1;
+            encoded.push((arr[i], count));
+            if recording {
+                steps.push(Step {
+                    description: format!("Run ends: ({}, {})", arr[i], count),
+                    highlight_indices: vec![],
+                    active_indices: (start..=i).collect(),
+                    metadata: serde_json::json!({
+                        "operation": "emit",
+                        "value": arr[i],
+                        "count": count,
+                        "encoded": encoded,
+                        "array_state": arr.to_vec()
+                    }),
+                    detail: None,
+                });
+            }
+            start = i + 1;
+        }
+    }
+
+    steps.push(Step {
+        description: format!(
+            "Encoded {} element(s) as {} run(s): {}",
+            arr.len(),
+            encoded.len(),
+            encoded
+                .iter()
+                .map(|(value, count)| format!("({}, {})", value, count))
+                .collect::<Vec<_>>()
+                .join(" ")
+        ),
+        highlight_indices: vec![],
+        active_indices: vec![],
+        metadata: serde_json::json!({
+            "encoded": encoded,
+            "array_state": arr.to_vec()
+        }),
+        detail: None,
+    });
+
+    (encoded, steps.finish(arr))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rle_encode() {
+        let (encoded, steps) = rle_encode_with_steps(&[1, 1, 1, 2, 3, 3]);
+        assert_eq!(encoded, vec![(1, 3), (2, 1), (3, 2)]);
+
+        let emitted: Vec<&Step> = steps.iter().filter(|s| s.metadata["operation"] == "emit").collect();
+        assert_eq!(emitted.len(), 3);
+        assert_eq!(emitted[0].active_indices, vec![0, 1, 2]);
+        assert_eq!(steps.last().unwrap().metadata["encoded"], serde_json::json!([[1, 3], [2, 1], [3, 2]]));
+
+        assert!(rle_encode_with_steps(&[]).0.is_empty());
+    }
+
+    #[test]
+    fn test_rle_encode_past_step_limit() {
+        use crate::recorder::set_max_steps;
+
+        let arr: Vec<i32> = (1..=20).collect();
+        set_max_steps(Some(3));
+        let (encoded, steps) = rle_encode_with_steps(&arr);
+        set_max_steps(None);
+
+        assert_eq!(encoded, arr.iter().map(|&value| (value, 1)).collect::<Vec<_>>());
+        assert_eq!(steps.last().unwrap().metadata["truncated"], true);
+    }
+}
@@ -0,0 +1,87 @@
+//! Multiplying every element by a constant, with step-by-step visualization.
+
+use crate::error::{DsavError, Result};
+use crate::recorder::StepRecorder;
+use crate::traits::Step;
+
+/// Multiply each element of `arr` by `factor` in place.
+///
+/// Every product is checked before anything changes, so an `i32` overflow
+/// returns `DsavError::InvalidState` and leaves `arr` untouched. Each step
+/// then rewrites one element, with its `before` and `after` values in the
+/// metadata; the final step holds the whole array before and after.
+pub fn scale_with_steps(arr: &mut [i32], factor: i32) -> Result<Vec<Step>> {
+    if let Some((index, &value)) = arr.iter().enumerate().find(|(_, value)| value.checked_mul(factor).is_none()) {
+        return Err(DsavError::InvalidState {
+            reason: format!("{} × {} at index {} overflows i32", value, factor, index),
+        });
+    }
+
+    let original = arr.to_vec();
+    let mut steps = StepRecorder::new();
+
+    steps.push(Step {
+        description: format!("Multiplying every element by {}", factor),
+        highlight_indices: vec![],
+        active_indices: vec![],
+        metadata: serde_json::json!({
+            "operation": "scale",
+            "factor": factor,
+            "array_state": arr.to_vec()
+        }),
+        detail: None,
+    });
+
+    for i in 0..arr.len() {
+        let before = arr[i];
+        arr[i] = before * factor;
+
+        steps.push(Step {
+            description: format!("Index {}: {} × {} = {}", i, before, factor, arr[i]),
+            highlight_indices: vec![i],
+            active_indices: (0..i).collect(),
+            metadata: serde_json::json!({
+                "operation": "multiply",
+                "index": i,
+                "before": before,
+                "after": arr[i],
+                "array_state": arr.to_vec()
+            }),
+            detail: None,
+        });
+    }
+
+    steps.push(Step {
+        description: format!("Scaled {} element(s) by {}", arr.len(), factor),
+        highlight_indices: vec![],
+        active_indices: (0..arr.len()).collect(),
+        metadata: serde_json::json!({
+            "factor": factor,
+            "before": original,
+            "after": arr.to_vec(),
+            "array_state": arr.to_vec()
+        }),
+        detail: None,
+    });
+
+    Ok(steps.finish(arr))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scale() {
+        let mut arr = vec![1, 2, 3];
+        let steps = scale_with_steps(&mut arr, 3).unwrap();
+        assert_eq!(arr, vec![3, 6, 9]);
+        assert_eq!(steps[2].metadata["before"], 2);
+        assert_eq!(steps[2].metadata["after"], 6);
+        assert_eq!(steps.last().unwrap().metadata["before"], serde_json::json!([1, 2, 3]));
+
+        let mut arr = vec![1, i32::MAX / 2 + 1, 3];
+        assert!(matches!(scale_with_steps(&mut arr, 2), Err(DsavError::InvalidState { .. })));
+        assert_eq!(arr, vec![1, i32::MAX / 2 + 1, 3], "an overflow leaves the array unchanged");
+    }
+}
@@ -0,0 +1,203 @@
+//! Deterministic selection (median of medians) with step-by-step visualization.
+
+use crate::error::{DsavError, Result};
+use crate::recorder::StepRecorder;
+use crate::traits::Step;
+
+const GROUP_SIZE: usize = 5;
+
+/// Find the median with the median-of-medians pivot rule, which guarantees
+/// linear time unlike randomized quickselect.
+///
+/// For even lengths this returns the lower median (index `(n - 1) / 2` of the
+/// sorted array). `arr` is left partially reordered by the partitioning.
+pub fn median_of_medians_with_steps(arr: &mut [i32]) -> Result<(i32, Vec<Step>)> {
+    if arr.is_empty() {
+        return Err(DsavError::EmptyStructure);
+    }
+
+    let mut steps = StepRecorder::new();
+    let k = (arr.len() - 1) / 2;
+
+    steps.push(Step {
+        description: format!("Finding the median (element {} in sorted order) with median of medians", k),
+        highlight_indices: vec![],
+        active_indices: vec![],
+        metadata: serde_json::json!({
+            "operation": "median_of_medians",
+            "k": k,
+            "array_state": arr.to_vec()
+        }),
+        detail: None,
+    });
+
+    let median = select(arr, k, &mut steps);
+
+    steps.push(Step {
+        description: format!("Median is {}", median),
+        highlight_indices: vec![],
+        active_indices: vec![k],
+        metadata: serde_json::json!({
+            "median": median,
+            "array_state": arr.to_vec()
+        }),
+        detail: None,
+    });
+
+    Ok((median, steps.finish(arr)))
+}
+
+/// Return the `k`th smallest value of `arr` (0-based), recording steps.
+fn select(arr: &mut [i32], k: usize, steps: &mut StepRecorder) -> i32 {
+    let mut low = 0;
+    let mut high = arr.len() - 1;
+
+    loop {
+        if high - low < GROUP_SIZE {
+            arr[low..=high].sort_unstable();
+            steps.push(Step {
+                description: format!("Range {}..={} is small, sorting it directly", low, high),
+                highlight_indices: (low..=high).collect(),
+                active_indices: vec![k],
+                metadata: serde_json::json!({
+                    "operation": "base_case",
+                    "array_state": arr.to_vec()
+                }),
+                detail: None,
+            });
+            return arr[k];
+        }
+
+        // Sort each group of five and take its middle element
+        let mut medians = Vec::new();
+        for group_low in (low..=high).step_by(GROUP_SIZE) {
+            let group_high = (group_low + GROUP_SIZE - 1).min(high);
+            arr[group_low..=group_high].sort_unstable();
+            let median_idx = group_low + (group_high - group_low) / 2;
+            medians.push(arr[median_idx]);
+
+            steps.push(Step {
+                description: format!(
+                    "Group {}..={} sorted, its median is {}",
+                    group_low, group_high, arr[median_idx]
+                ),
+                highlight_indices: vec![median_idx],
+                active_indices: (group_low..=group_high).collect(),
+                metadata: serde_json::json!({
+                    "operation": "group_median",
+                    "group": [group_low, group_high],
+                    "array_state": arr.to_vec()
+                }),
+                detail: None,
+            });
+        }
+
+        // Only the chosen pivot is animated, not the nested selection
+        let mid = (medians.len() - 1) / 2;
+        let pivot = select(&mut medians.clone(), mid, &mut StepRecorder::with_limit(None));
+
+        steps.push(Step {
+            description: format!("Median of the group medians {:?} is {}, using it as the pivot", medians, pivot),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": "pivot",
+                "medians": medians,
+                "pivot": pivot,
+                "array_state": arr.to_vec()
+            }),
+            detail: None,
+        });
+
+        let pivot_idx = partition(arr, low, high, pivot);
+
+        steps.push(Step {
+            description: format!("Partitioned around {}, which lands at index {}", pivot, pivot_idx),
+            highlight_indices: (low..=high).collect(),
+            active_indices: vec![pivot_idx],
+            metadata: serde_json::json!({
+                "operation": "partition",
+                "pivot": pivot,
+                "pivot_index": pivot_idx,
+                "array_state": arr.to_vec()
+            }),
+            detail: None,
+        });
+
+        match k.cmp(&pivot_idx) {
+            std::cmp::Ordering::Equal => return arr[k],
+            std::cmp::Ordering::Less => high = pivot_idx - 1,
+            std::cmp::Ordering::Greater => low = pivot_idx + 1,
+        }
+    }
+}
+
+/// Lomuto partition of `arr[low..=high]` around `pivot`, which must be present.
+/// Returns the pivot's final index.
+fn partition(arr: &mut [i32], low: usize, high: usize, pivot: i32) -> usize {
+    let pivot_pos = (low..=high).find(|&i| arr[i] == pivot).unwrap_or(high);
+    arr.swap(pivot_pos, high);
+
+    let mut store = low;
+    for i in low..high {
+        if arr[i] < pivot {
+            arr.swap(i, store);
+            store += 1;
+        }
+    }
+    arr.swap(store, high);
+    store
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lower_median(values: &[i32]) -> i32 {
+        let mut sorted = values.to_vec();
+        sorted.sort();
+        sorted[(sorted.len() - 1) / 2]
+    }
+
+    #[test]
+    fn test_median_of_medians_correctness() {
+        let inputs: Vec<Vec<i32>> = vec![
+            vec![7],
+            vec![3, 1],
+            vec![5, 2, 9, 1, 7],
+            vec![12, 3, 5, 7, 4, 19, 26, 1, 8, 15, 2, 11, 6],
+            vec![4, 4, 4, 1, 4, 4, 9, 4, 4, 4, 4, 4],
+            (0..40).rev().collect(),
+        ];
+
+        for input in inputs {
+            let expected = lower_median(&input);
+            let mut arr = input.clone();
+            let (median, steps) = median_of_medians_with_steps(&mut arr).unwrap();
+            assert_eq!(median, expected, "input {:?}", input);
+            assert_eq!(steps.last().unwrap().metadata["median"], expected);
+        }
+    }
+
+    #[test]
+    fn test_median_even_length_is_lower() {
+        let mut arr = vec![10, 40, 20, 30];
+        let (median, _) = median_of_medians_with_steps(&mut arr).unwrap();
+        assert_eq!(median, 20);
+    }
+
+    #[test]
+    fn test_median_records_group_medians() {
+        let mut arr: Vec<i32> = (1..=15).rev().collect();
+        let (_, steps) = median_of_medians_with_steps(&mut arr).unwrap();
+
+        let groups = steps.iter().filter(|s| s.metadata["operation"] == "group_median").count();
+        assert!(groups >= 3);
+        assert!(steps.iter().any(|s| s.metadata["operation"] == "pivot"));
+    }
+
+    #[test]
+    fn test_median_empty() {
+        assert!(median_of_medians_with_steps(&mut []).is_err());
+    }
+}
@@ -0,0 +1,140 @@
+//! Shuffling algorithm implementations with step-by-step visualization.
+
+use crate::dataset::{RandomSource, SeededRandom, ThreadRandom};
+use crate::error::Result;
+use crate::recorder::StepRecorder;
+use crate::traits::Step;
+
+/// Fisher-Yates shuffle using a freshly drawn seed.
+///
+/// The seed is recorded in the step metadata so a shuffle can be replayed
+/// with [`shuffle_with_steps_seeded`].
+pub fn shuffle_with_steps(arr: &mut [i32]) -> Result<Vec<Step>> {
+    let seed = ThreadRandom.next_seed();
+    shuffle_with_steps_seeded(arr, seed)
+}
+
+/// Fisher-Yates shuffle driven by a seeded RNG, for reproducible results.
+pub fn shuffle_with_steps_seeded(arr: &mut [i32], seed: u64) -> Result<Vec<Step>> {
+    let mut steps = StepRecorder::new();
+    let n = arr.len();
+
+    if n <= 1 {
+        return Ok(steps.finish(arr));
+    }
+
+    let mut rng = SeededRandom::new(seed);
+
+    steps.push(Step {
+        description: format!("Starting Fisher-Yates shuffle (seed {})", seed),
+        highlight_indices: vec![],
+        active_indices: vec![],
+        metadata: serde_json::json!({
+            "seed": seed,
+            "array_state": arr.to_vec()
+        }),
+        detail: None,
+    });
+
+    for i in (1..n).rev() {
+        if steps.is_truncated() {
+            break;
+        }
+
+        let j = rng.next_in_range(0, i as i32) as usize;
+
+        steps.push(Step {
+            description: format!("Picked random index {} from 0..={}", j, i),
+            highlight_indices: vec![i, j],
+            active_indices: (i + 1..n).collect(),
+            metadata: serde_json::json!({
+                "operation": "pick",
+                "i": i,
+                "j": j,
+                "seed": seed,
+                "array_state": arr.to_vec()
+            }),
+            detail: None,
+        });
+
+        if i != j {
+            arr.swap(i, j);
+
+            steps.push(Step {
+                description: format!("Swapping {} and {}", arr[j], arr[i]),
+                highlight_indices: vec![],
+                active_indices: vec![i, j],
+                metadata: serde_json::json!({
+                    "operation": "swap",
+                    "i": i,
+                    "j": j,
+                    "array_state": arr.to_vec()
+                }),
+                detail: None,
+            });
+        } else {
+            steps.push(Step {
+                description: format!("{} stays in place at index {}", arr[i], i),
+                highlight_indices: vec![],
+                active_indices: vec![i],
+                metadata: serde_json::json!({
+                    "operation": "keep",
+                    "i": i,
+                    "array_state": arr.to_vec()
+                }),
+                detail: None,
+            });
+        }
+    }
+
+    steps.push(Step {
+        description: "Shuffle complete".to_string(),
+        highlight_indices: vec![],
+        active_indices: vec![],
+        metadata: serde_json::json!({
+            "seed": seed,
+            "array_state": arr.to_vec()
+        }),
+        detail: None,
+    });
+
+    Ok(steps.finish(arr))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shuffle_seeded_is_deterministic() {
+        let mut first = vec![1, 2, 3, 4, 5, 6, 7, 8];
+        let mut second = first.clone();
+
+        shuffle_with_steps_seeded(&mut first, 42).unwrap();
+        shuffle_with_steps_seeded(&mut second, 42).unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(first, vec![7, 3, 6, 1, 5, 8, 4, 2]);
+    }
+
+    #[test]
+    fn test_shuffle_preserves_elements() {
+        let original = vec![5, 3, 3, 9, 1, 7];
+        let mut arr = original.clone();
+        let steps = shuffle_with_steps(&mut arr).unwrap();
+
+        let mut sorted = arr.clone();
+        sorted.sort();
+        let mut expected = original;
+        expected.sort();
+        assert_eq!(sorted, expected);
+
+        assert_eq!(steps.last().unwrap().metadata["array_state"], serde_json::json!(arr));
+    }
+
+    #[test]
+    fn test_shuffle_trivial() {
+        let mut arr = vec![1];
+        assert!(shuffle_with_steps_seeded(&mut arr, 7).unwrap().is_empty());
+    }
+}
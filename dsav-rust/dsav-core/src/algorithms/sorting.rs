@@ -1,14 +1,20 @@
 //! Sorting and searching algorithm implementations with step-by-step visualization.
 
 use crate::error::Result;
+use crate::recorder::StepRecorder;
 use crate::traits::Step;
 
 pub fn bubble_sort_with_steps(arr: &mut [i32]) -> Result<Vec<Step>> {
-    let mut steps = Vec::new();
+    bubble_sort_with_progress(arr, &mut |_| {})
+}
+
+/// Like [`bubble_sort_with_steps`], calling `progress` with the step count as steps are recorded.
+pub fn bubble_sort_with_progress(arr: &mut [i32], progress: &mut dyn FnMut(usize)) -> Result<Vec<Step>> {
+    let mut steps = StepRecorder::with_progress(progress);
     let n = arr.len();
 
     if n <= 1 {
-        return Ok(steps);
+        return Ok(steps.finish(arr));
     }
 
     // Store initial state
@@ -19,12 +25,17 @@ pub fn bubble_sort_with_steps(arr: &mut [i32]) -> Result<Vec<Step>> {
         metadata: serde_json::json!({
             "array_state": arr.to_vec()
         }),
+        detail: None,
     });
 
     for i in 0..n {
         let mut swapped = false;
 
         for j in 0..n - i - 1 {
+            if steps.is_truncated() {
+                break;
+            }
+
             steps.push(Step {
                 description: format!("Comparing {} and {}", arr[j], arr[j + 1]),
                 highlight_indices: vec![j, j + 1],
@@ -34,6 +45,7 @@ pub fn bubble_sort_with_steps(arr: &mut [i32]) -> Result<Vec<Step>> {
                     "values": [arr[j], arr[j + 1]],
                     "array_state": arr.to_vec()
                 }),
+                detail: None,
             });
 
             if arr[j] > arr[j + 1] {
@@ -48,6 +60,7 @@ pub fn bubble_sort_with_steps(arr: &mut [i32]) -> Result<Vec<Step>> {
                         "values": [arr[j], arr[j + 1]],
                         "array_state": arr.to_vec()
                     }),
+                    detail: None,
                 });
 
                 swapped = true;
@@ -66,8 +79,13 @@ pub fn bubble_sort_with_steps(arr: &mut [i32]) -> Result<Vec<Step>> {
                 "index": n - i - 1,
                 "array_state": arr.to_vec()
             }),
+            detail: None,
         });
 
+        if steps.is_truncated() {
+            break;
+        }
+
         if !swapped {
             steps.push(Step {
                 description: "Array is sorted, no more swaps needed".to_string(),
@@ -76,6 +94,7 @@ pub fn bubble_sort_with_steps(arr: &mut [i32]) -> Result<Vec<Step>> {
                 metadata: serde_json::json!({
                     "array_state": arr.to_vec()
                 }),
+                detail: None,
             });
             break;
         }
@@ -88,17 +107,23 @@ pub fn bubble_sort_with_steps(arr: &mut [i32]) -> Result<Vec<Step>> {
         metadata: serde_json::json!({
             "array_state": arr.to_vec()
         }),
+        detail: None,
     });
 
-    Ok(steps)
+    Ok(steps.finish(arr))
 }
 
 pub fn insertion_sort_with_steps(arr: &mut [i32]) -> Result<Vec<Step>> {
-    let mut steps = Vec::new();
+    insertion_sort_with_progress(arr, &mut |_| {})
+}
+
+/// Like [`insertion_sort_with_steps`], calling `progress` with the step count as steps are recorded.
+pub fn insertion_sort_with_progress(arr: &mut [i32], progress: &mut dyn FnMut(usize)) -> Result<Vec<Step>> {
+    let mut steps = StepRecorder::with_progress(progress);
     let n = arr.len();
 
     if n <= 1 {
-        return Ok(steps);
+        return Ok(steps.finish(arr));
     }
 
     // Store initial state
@@ -109,9 +134,14 @@ pub fn insertion_sort_with_steps(arr: &mut [i32]) -> Result<Vec<Step>> {
         metadata: serde_json::json!({
             "array_state": arr.to_vec()
         }),
+        detail: None,
     });
 
     for i in 1..n {
+        if steps.is_truncated() {
+            break;
+        }
+
         let key = arr[i];
         let mut j = i;
 
@@ -125,6 +155,7 @@ pub fn insertion_sort_with_steps(arr: &mut [i32]) -> Result<Vec<Step>> {
                 "index": i,
                 "array_state": arr.to_vec()
             }),
+            detail: None,
         });
 
         while j > 0 && arr[j - 1] > key {
@@ -137,19 +168,21 @@ pub fn insertion_sort_with_steps(arr: &mut [i32]) -> Result<Vec<Step>> {
                     "values": [arr[j - 1], key],
                     "array_state": arr.to_vec()
                 }),
+                detail: None,
             });
 
             arr[j] = arr[j - 1];
             j -= 1;
 
             steps.push(Step {
-                description: format!("Shifting element to the right"),
+                description: "Shifting element to the right".to_string(),
                 highlight_indices: vec![],
                 active_indices: vec![j, j + 1],
                 metadata: serde_json::json!({
                     "operation": "shift",
                     "array_state": arr.to_vec()
                 }),
+                detail: None,
             });
         }
 
@@ -165,6 +198,7 @@ pub fn insertion_sort_with_steps(arr: &mut [i32]) -> Result<Vec<Step>> {
                 "index": j,
                 "array_state": arr.to_vec()
             }),
+            detail: None,
         });
 
         steps.push(Step {
@@ -174,6 +208,7 @@ pub fn insertion_sort_with_steps(arr: &mut [i32]) -> Result<Vec<Step>> {
             metadata: serde_json::json!({
                 "array_state": arr.to_vec()
             }),
+            detail: None,
         });
     }
 
@@ -184,17 +219,23 @@ pub fn insertion_sort_with_steps(arr: &mut [i32]) -> Result<Vec<Step>> {
         metadata: serde_json::json!({
             "array_state": arr.to_vec()
         }),
+        detail: None,
     });
 
-    Ok(steps)
+    Ok(steps.finish(arr))
 }
 
 pub fn quick_sort_with_steps(arr: &mut [i32]) -> Result<Vec<Step>> {
-    let mut steps = Vec::new();
+    quick_sort_with_progress(arr, &mut |_| {})
+}
+
+/// Like [`quick_sort_with_steps`], calling `progress` with the step count as steps are recorded.
+pub fn quick_sort_with_progress(arr: &mut [i32], progress: &mut dyn FnMut(usize)) -> Result<Vec<Step>> {
+    let mut steps = StepRecorder::with_progress(progress);
     let n = arr.len();
 
     if n <= 1 {
-        return Ok(steps);
+        return Ok(steps.finish(arr));
     }
 
     steps.push(Step {
@@ -204,9 +245,10 @@ pub fn quick_sort_with_steps(arr: &mut [i32]) -> Result<Vec<Step>> {
         metadata: serde_json::json!({
             "array_state": arr.to_vec()
         }),
+        detail: None,
     });
 
-    quick_sort_helper(arr, 0, n - 1, &mut steps)?;
+    quick_sort_helper(arr, 0, n - 1, 0, &mut steps)?;
 
     steps.push(Step {
         description: "Quick sort complete".to_string(),
@@ -215,33 +257,41 @@ pub fn quick_sort_with_steps(arr: &mut [i32]) -> Result<Vec<Step>> {
         metadata: serde_json::json!({
             "array_state": arr.to_vec()
         }),
+        detail: None,
     });
 
-    Ok(steps)
+    Ok(steps.finish(arr))
 }
 
 fn quick_sort_helper(
     arr: &mut [i32],
     low: usize,
     high: usize,
-    steps: &mut Vec<Step>,
+    depth: usize,
+    steps: &mut StepRecorder,
 ) -> Result<()> {
+    if steps.is_truncated() {
+        return Ok(());
+    }
+
     if low < high {
+        let start = steps.len();
         let pivot_index = partition(arr, low, high, steps)?;
+        steps.annotate_since(start, &recursion_frame(depth, "partition", low, high));
 
         if pivot_index > 0 {
-            quick_sort_helper(arr, low, pivot_index - 1, steps)?;
+            quick_sort_helper(arr, low, pivot_index - 1, depth + 1, steps)?;
         }
 
         if pivot_index < high {
-            quick_sort_helper(arr, pivot_index + 1, high, steps)?;
+            quick_sort_helper(arr, pivot_index + 1, high, depth + 1, steps)?;
         }
     }
 
     Ok(())
 }
 
-fn partition(arr: &mut [i32], low: usize, high: usize, steps: &mut Vec<Step>) -> Result<usize> {
+fn partition(arr: &mut [i32], low: usize, high: usize, steps: &mut StepRecorder) -> Result<usize> {
     let pivot = arr[high];
 
     steps.push(Step {
@@ -252,8 +302,10 @@ fn partition(arr: &mut [i32], low: usize, high: usize, steps: &mut Vec<Step>) ->
             "operation": "pivot",
             "value": pivot,
             "index": high,
+            "pivot_index": high,
             "array_state": arr.to_vec()
         }),
+        detail: None,
     });
 
     let mut i = low;
@@ -266,8 +318,10 @@ fn partition(arr: &mut [i32], low: usize, high: usize, steps: &mut Vec<Step>) ->
             metadata: serde_json::json!({
                 "operation": "compare",
                 "values": [arr[j], pivot],
+                "pivot_index": high,
                 "array_state": arr.to_vec()
             }),
+            detail: None,
         });
 
         if arr[j] < pivot {
@@ -281,8 +335,10 @@ fn partition(arr: &mut [i32], low: usize, high: usize, steps: &mut Vec<Step>) ->
                     metadata: serde_json::json!({
                         "operation": "swap",
                         "values": [arr[i], arr[j]],
+                        "pivot_index": high,
                         "array_state": arr.to_vec()
                     }),
+                    detail: None,
                 });
             }
 
@@ -299,8 +355,10 @@ fn partition(arr: &mut [i32], low: usize, high: usize, steps: &mut Vec<Step>) ->
         metadata: serde_json::json!({
             "operation": "swap",
             "values": [arr[i], arr[high]],
+            "pivot_index": i,
             "array_state": arr.to_vec()
         }),
+        detail: None,
     });
 
     steps.push(Step {
@@ -310,19 +368,35 @@ fn partition(arr: &mut [i32], low: usize, high: usize, steps: &mut Vec<Step>) ->
         metadata: serde_json::json!({
             "operation": "sorted",
             "index": i,
+            "pivot_index": i,
             "array_state": arr.to_vec()
         }),
+        detail: None,
     });
 
     Ok(i)
 }
 
+/// Metadata identifying the recursive call (depth and inclusive range) a step belongs to.
+fn recursion_frame(depth: usize, phase: &str, low: usize, high: usize) -> serde_json::Value {
+    serde_json::json!({
+        "recursion_depth": depth,
+        "phase": phase,
+        "range": [low, high]
+    })
+}
+
 pub fn selection_sort_with_steps(arr: &mut [i32]) -> Result<Vec<Step>> {
-    let mut steps = Vec::new();
+    selection_sort_with_progress(arr, &mut |_| {})
+}
+
+/// Like [`selection_sort_with_steps`], calling `progress` with the step count as steps are recorded.
+pub fn selection_sort_with_progress(arr: &mut [i32], progress: &mut dyn FnMut(usize)) -> Result<Vec<Step>> {
+    let mut steps = StepRecorder::with_progress(progress);
     let n = arr.len();
 
     if n <= 1 {
-        return Ok(steps);
+        return Ok(steps.finish(arr));
     }
 
     steps.push(Step {
@@ -332,9 +406,14 @@ pub fn selection_sort_with_steps(arr: &mut [i32]) -> Result<Vec<Step>> {
         metadata: serde_json::json!({
             "array_state": arr.to_vec()
         }),
+        detail: None,
     });
 
     for i in 0..n - 1 {
+        if steps.is_truncated() {
+            break;
+        }
+
         let mut min_idx = i;
 
         steps.push(Step {
@@ -346,6 +425,7 @@ pub fn selection_sort_with_steps(arr: &mut [i32]) -> Result<Vec<Step>> {
                 "index": i,
                 "array_state": arr.to_vec()
             }),
+            detail: None,
         });
 
         for j in i + 1..n {
@@ -358,6 +438,7 @@ pub fn selection_sort_with_steps(arr: &mut [i32]) -> Result<Vec<Step>> {
                     "values": [arr[j], arr[min_idx]],
                     "array_state": arr.to_vec()
                 }),
+                detail: None,
             });
 
             if arr[j] < arr[min_idx] {
@@ -372,6 +453,7 @@ pub fn selection_sort_with_steps(arr: &mut [i32]) -> Result<Vec<Step>> {
                         "min_index": min_idx,
                         "array_state": arr.to_vec()
                     }),
+                    detail: None,
                 });
             }
         }
@@ -388,6 +470,7 @@ pub fn selection_sort_with_steps(arr: &mut [i32]) -> Result<Vec<Step>> {
                     "values": [arr[i], arr[min_idx]],
                     "array_state": arr.to_vec()
                 }),
+                detail: None,
             });
         }
 
@@ -400,6 +483,7 @@ pub fn selection_sort_with_steps(arr: &mut [i32]) -> Result<Vec<Step>> {
                 "index": i,
                 "array_state": arr.to_vec()
             }),
+            detail: None,
         });
     }
 
@@ -410,17 +494,23 @@ pub fn selection_sort_with_steps(arr: &mut [i32]) -> Result<Vec<Step>> {
         metadata: serde_json::json!({
             "array_state": arr.to_vec()
         }),
+        detail: None,
     });
 
-    Ok(steps)
+    Ok(steps.finish(arr))
 }
 
 pub fn merge_sort_with_steps(arr: &mut [i32]) -> Result<Vec<Step>> {
-    let mut steps = Vec::new();
+    merge_sort_with_progress(arr, &mut |_| {})
+}
+
+/// Like [`merge_sort_with_steps`], calling `progress` with the step count as steps are recorded.
+pub fn merge_sort_with_progress(arr: &mut [i32], progress: &mut dyn FnMut(usize)) -> Result<Vec<Step>> {
+    let mut steps = StepRecorder::with_progress(progress);
     let n = arr.len();
 
     if n <= 1 {
-        return Ok(steps);
+        return Ok(steps.finish(arr));
     }
 
     steps.push(Step {
@@ -430,9 +520,10 @@ pub fn merge_sort_with_steps(arr: &mut [i32]) -> Result<Vec<Step>> {
         metadata: serde_json::json!({
             "array_state": arr.to_vec()
         }),
+        detail: None,
     });
 
-    merge_sort_helper(arr, 0, n - 1, &mut steps)?;
+    merge_sort_helper(arr, 0, n - 1, 0, &mut steps)?;
 
     steps.push(Step {
         description: "Merge sort complete".to_string(),
@@ -441,12 +532,23 @@ pub fn merge_sort_with_steps(arr: &mut [i32]) -> Result<Vec<Step>> {
         metadata: serde_json::json!({
             "array_state": arr.to_vec()
         }),
+        detail: None,
     });
 
-    Ok(steps)
+    Ok(steps.finish(arr))
 }
 
-fn merge_sort_helper(arr: &mut [i32], left: usize, right: usize, steps: &mut Vec<Step>) -> Result<()> {
+fn merge_sort_helper(
+    arr: &mut [i32],
+    left: usize,
+    right: usize,
+    depth: usize,
+    steps: &mut StepRecorder,
+) -> Result<()> {
+    if steps.is_truncated() {
+        return Ok(());
+    }
+
     if left < right {
         let mid = left + (right - left) / 2;
 
@@ -459,19 +561,26 @@ fn merge_sort_helper(arr: &mut [i32], left: usize, right: usize, steps: &mut Vec
                 "left": left,
                 "mid": mid,
                 "right": right,
+                "recursion_depth": depth,
+                "phase": "divide",
+                "range": [left, right],
                 "array_state": arr.to_vec()
             }),
+            detail: None,
         });
 
-        merge_sort_helper(arr, left, mid, steps)?;
-        merge_sort_helper(arr, mid + 1, right, steps)?;
+        merge_sort_helper(arr, left, mid, depth + 1, steps)?;
+        merge_sort_helper(arr, mid + 1, right, depth + 1, steps)?;
+
+        let start = steps.len();
         merge(arr, left, mid, right, steps)?;
+        steps.annotate_since(start, &recursion_frame(depth, "merge", left, right));
     }
 
     Ok(())
 }
 
-fn merge(arr: &mut [i32], left: usize, mid: usize, right: usize, steps: &mut Vec<Step>) -> Result<()> {
+fn merge(arr: &mut [i32], left: usize, mid: usize, right: usize, steps: &mut StepRecorder) -> Result<()> {
     let left_half = arr[left..=mid].to_vec();
     let right_half = arr[mid + 1..=right].to_vec();
 
@@ -486,6 +595,7 @@ fn merge(arr: &mut [i32], left: usize, mid: usize, right: usize, steps: &mut Vec
             "right": right,
             "array_state": arr.to_vec()
         }),
+        detail: None,
     });
 
     let mut i = 0;
@@ -502,6 +612,7 @@ fn merge(arr: &mut [i32], left: usize, mid: usize, right: usize, steps: &mut Vec
                 "values": [left_half[i], right_half[j]],
                 "array_state": arr.to_vec()
             }),
+            detail: None,
         });
 
         if left_half[i] <= right_half[j] {
@@ -516,6 +627,7 @@ fn merge(arr: &mut [i32], left: usize, mid: usize, right: usize, steps: &mut Vec
                     "index": k,
                     "array_state": arr.to_vec()
                 }),
+                detail: None,
             });
             i += 1;
         } else {
@@ -530,6 +642,7 @@ fn merge(arr: &mut [i32], left: usize, mid: usize, right: usize, steps: &mut Vec
                     "index": k,
                     "array_state": arr.to_vec()
                 }),
+                detail: None,
             });
             j += 1;
         }
@@ -548,6 +661,7 @@ fn merge(arr: &mut [i32], left: usize, mid: usize, right: usize, steps: &mut Vec
                 "index": k,
                 "array_state": arr.to_vec()
             }),
+            detail: None,
         });
         i += 1;
         k += 1;
@@ -565,6 +679,7 @@ fn merge(arr: &mut [i32], left: usize, mid: usize, right: usize, steps: &mut Vec
                 "index": k,
                 "array_state": arr.to_vec()
             }),
+            detail: None,
         });
         j += 1;
         k += 1;
@@ -578,11 +693,147 @@ fn merge(arr: &mut [i32], left: usize, mid: usize, right: usize, steps: &mut Vec
             "operation": "merge_complete",
             "array_state": arr.to_vec()
         }),
+        detail: None,
     });
 
     Ok(())
 }
 
+/// Bucket sort: distribute values into range-based buckets, insertion sort
+/// each bucket, then concatenate.
+///
+/// Uses `ceil(sqrt(n))` buckets spread evenly over `min..=max`.
+pub fn bucket_sort_with_steps(arr: &mut [i32]) -> Result<Vec<Step>> {
+    bucket_sort_with_progress(arr, &mut |_| {})
+}
+
+/// Like [`bucket_sort_with_steps`], calling `progress` with the step count as steps are recorded.
+pub fn bucket_sort_with_progress(arr: &mut [i32], progress: &mut dyn FnMut(usize)) -> Result<Vec<Step>> {
+    let mut steps = StepRecorder::with_progress(progress);
+    let n = arr.len();
+
+    if n <= 1 {
+        return Ok(steps.finish(arr));
+    }
+
+    let min = *arr.iter().min().unwrap();
+    let max = *arr.iter().max().unwrap();
+    let bucket_count = (n as f64).sqrt().ceil() as usize;
+    // Widen to i64 so the span of extreme i32 values can't overflow
+    let range = max as i64 - min as i64 + 1;
+
+    steps.push(Step {
+        description: format!(
+            "Starting Bucket Sort: {} buckets covering {}..={}",
+            bucket_count, min, max
+        ),
+        highlight_indices: vec![],
+        active_indices: vec![],
+        metadata: serde_json::json!({
+            "bucket_count": bucket_count,
+            "min": min,
+            "max": max,
+            "array_state": arr.to_vec()
+        }),
+        detail: None,
+    });
+
+    let mut buckets: Vec<Vec<i32>> = vec![Vec::new(); bucket_count];
+
+    for (i, &value) in arr.iter().enumerate() {
+        if steps.is_truncated() {
+            return Ok(steps.finish(arr));
+        }
+
+        let bucket = ((value as i64 - min as i64) * bucket_count as i64 / range) as usize;
+        buckets[bucket].push(value);
+
+        steps.push(Step {
+            description: format!("Placing {} into bucket {}", value, bucket),
+            highlight_indices: vec![i],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": "distribute",
+                "value": value,
+                "bucket": bucket,
+                "buckets": buckets,
+                "array_state": arr.to_vec()
+            }),
+            detail: None,
+        });
+    }
+
+    for (dst, value) in arr.iter_mut().zip(buckets.iter().flatten()) {
+        *dst = *value;
+    }
+
+    let mut bounds = Vec::with_capacity(bucket_count);
+    let mut start = 0;
+    for bucket in &buckets {
+        bounds.push((start, start + bucket.len()));
+        start += bucket.len();
+    }
+
+    steps.push(Step {
+        description: "Gathering buckets back into the array".to_string(),
+        highlight_indices: vec![],
+        active_indices: vec![],
+        metadata: serde_json::json!({
+            "operation": "gather",
+            "buckets": buckets,
+            "array_state": arr.to_vec()
+        }),
+        detail: None,
+    });
+
+    for (bucket, &(start, end)) in bounds.iter().enumerate() {
+        if steps.is_truncated() {
+            break;
+        }
+
+        if end - start <= 1 {
+            continue;
+        }
+
+        let bucket_steps = insertion_sort_with_steps(&mut arr[start..end])?;
+
+        // Re-anchor the bucket's insertion sort steps onto the full array
+        for mut step in bucket_steps {
+            if step.metadata.get("truncated").is_some() {
+                continue;
+            }
+
+            step.description = format!("Bucket {}: {}", bucket, step.description);
+            step.highlight_indices.iter_mut().for_each(|idx| *idx += start);
+            step.active_indices.iter_mut().for_each(|idx| *idx += start);
+
+            if let Some(state) = step.metadata.get_mut("array_state") {
+                let mut full = arr.to_vec();
+                if let Some(values) = state.as_array() {
+                    for (offset, value) in values.iter().enumerate() {
+                        full[start + offset] = value.as_i64().unwrap_or_default() as i32;
+                    }
+                }
+                *state = serde_json::json!(full);
+            }
+
+            steps.push(step);
+        }
+    }
+
+    steps.push(Step {
+        description: "Bucket sort complete".to_string(),
+        highlight_indices: vec![],
+        active_indices: (0..n).collect(),
+        metadata: serde_json::json!({
+            "array_state": arr.to_vec()
+        }),
+        detail: None,
+    });
+
+    Ok(steps.finish(arr))
+}
+
 pub fn binary_search_with_steps(arr: &[i32], target: i32) -> Result<Vec<Step>> {
     let mut steps = Vec::new();
     let n = arr.len();
@@ -595,6 +846,7 @@ pub fn binary_search_with_steps(arr: &[i32], target: i32) -> Result<Vec<Step>> {
             metadata: serde_json::json!({
                 "found": false
             }),
+            detail: None,
         });
         return Ok(steps);
     }
@@ -608,6 +860,7 @@ pub fn binary_search_with_steps(arr: &[i32], target: i32) -> Result<Vec<Step>> {
             "target": target,
             "array_state": arr.to_vec()
         }),
+        detail: None,
     });
 
     let mut left = 0;
@@ -627,6 +880,7 @@ pub fn binary_search_with_steps(arr: &[i32], target: i32) -> Result<Vec<Step>> {
                 "mid_value": arr[mid],
                 "array_state": arr.to_vec()
             }),
+            detail: None,
         });
 
         if arr[mid] == target {
@@ -639,6 +893,7 @@ pub fn binary_search_with_steps(arr: &[i32], target: i32) -> Result<Vec<Step>> {
                     "index": mid,
                     "array_state": arr.to_vec()
                 }),
+                detail: None,
             });
             return Ok(steps);
         }
@@ -651,6 +906,7 @@ pub fn binary_search_with_steps(arr: &[i32], target: i32) -> Result<Vec<Step>> {
                 metadata: serde_json::json!({
                     "array_state": arr.to_vec()
                 }),
+                detail: None,
             });
             left = mid + 1;
         } else {
@@ -664,6 +920,7 @@ pub fn binary_search_with_steps(arr: &[i32], target: i32) -> Result<Vec<Step>> {
                 metadata: serde_json::json!({
                     "array_state": arr.to_vec()
                 }),
+                detail: None,
             });
             right = mid - 1;
         }
@@ -677,11 +934,120 @@ pub fn binary_search_with_steps(arr: &[i32], target: i32) -> Result<Vec<Step>> {
             "found": false,
             "array_state": arr.to_vec()
         }),
+        detail: None,
     });
 
     Ok(steps)
 }
 
+/// First and last index of a value, if present.
+type IndexRange = Option<(usize, usize)>;
+
+/// Find the first and last index of `target` with two binary searches.
+///
+/// Assumes `arr` is sorted ascending; an unsorted input gets a warning step and
+/// the bounds may be wrong. The final step's metadata holds
+/// `"range": [first, last]` or `"range": null`.
+pub fn search_range_with_steps(arr: &[i32], target: i32) -> Result<(IndexRange, Vec<Step>)> {
+    let mut steps = StepRecorder::new();
+
+    steps.push(Step {
+        description: format!("Searching for the first and last index of {}", target),
+        highlight_indices: vec![],
+        active_indices: vec![],
+        metadata: serde_json::json!({
+            "operation": "search_range",
+            "target": target,
+            "array_state": arr.to_vec()
+        }),
+        detail: None,
+    });
+
+    if !arr.windows(2).all(|pair| pair[0] <= pair[1]) {
+        steps.push(Step {
+            description: "Warning: array is not sorted, the bounds may be wrong".to_string(),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "warning": "unsorted",
+                "array_state": arr.to_vec()
+            }),
+            detail: None,
+        });
+    }
+
+    let range = bound_search(arr, target, true, &mut steps)
+        .and_then(|first| bound_search(arr, target, false, &mut steps).map(|last| (first, last)));
+
+    let (description, active) = match range {
+        Some((first, last)) => (
+            format!("{} spans indices {} to {}", target, first, last),
+            (first..=last).collect(),
+        ),
+        None => (format!("Value {} not found in array", target), vec![]),
+    };
+
+    steps.push(Step {
+        description,
+        highlight_indices: vec![],
+        active_indices: active,
+        metadata: serde_json::json!({
+            "range": range.map(|(first, last)| [first, last]),
+            "array_state": arr.to_vec()
+        }),
+        detail: None,
+    });
+
+    Ok((range, steps.finish(arr)))
+}
+
+/// One binary search for the leftmost (or rightmost) index holding `target`.
+/// On a match it keeps narrowing toward that side instead of stopping.
+fn bound_search(arr: &[i32], target: i32, leftmost: bool, steps: &mut StepRecorder) -> Option<usize> {
+    let side = if leftmost { "first" } else { "last" };
+    let mut low = 0;
+    let mut high = arr.len();
+    let mut found = None;
+
+    // Runs to the end past the step limit; each search is only O(log n)
+    while low < high {
+        let mid = low + (high - low) / 2;
+
+        let verdict = if arr[mid] == target {
+            found = Some(mid);
+            if leftmost {
+                high = mid;
+                "match, looking further left"
+            } else {
+                low = mid + 1;
+                "match, looking further right"
+            }
+        } else if arr[mid] < target {
+            low = mid + 1;
+            "too small, searching right half"
+        } else {
+            high = mid;
+            "too large, searching left half"
+        };
+
+        steps.push(Step {
+            description: format!("Searching for {} {}: index {} holds {}, {}", side, target, mid, arr[mid], verdict),
+            highlight_indices: vec![mid],
+            active_indices: found.into_iter().collect(),
+            metadata: serde_json::json!({
+                "operation": "compare",
+                "bound": side,
+                "mid": mid,
+                "mid_value": arr[mid],
+                "array_state": arr.to_vec()
+            }),
+            detail: None,
+        });
+    }
+
+    found
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -737,4 +1103,154 @@ mod tests {
         let _ = quick_sort_with_steps(&mut arr).unwrap();
         assert_eq!(arr, vec![1, 2, 2, 5, 5]);
     }
+
+    #[test]
+    fn test_step_limit_truncates_sort() {
+        use crate::recorder::set_max_steps;
+
+        let mut arr = vec![9, 8, 7, 6, 5, 4, 3, 2, 1];
+        set_max_steps(Some(10));
+        let steps = bubble_sort_with_steps(&mut arr).unwrap();
+        set_max_steps(None);
+
+        assert_eq!(steps.len(), 11);
+        let marker = steps.last().unwrap();
+        assert_eq!(marker.metadata["truncated"], true);
+        assert_eq!(marker.metadata["array_state"], serde_json::json!(arr));
+
+        // Partially sorted, but still a permutation of the input
+        assert_ne!(arr, vec![1, 2, 3, 4, 5, 6, 7, 8, 9]);
+        let mut sorted = arr.clone();
+        sorted.sort();
+        assert_eq!(sorted, vec![1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn test_step_limit_keeps_merge_sort_consistent() {
+        use crate::recorder::set_max_steps;
+
+        let mut arr = vec![5, 2, 8, 1, 9, 3, 7, 4, 6];
+        set_max_steps(Some(10));
+        let steps = merge_sort_with_steps(&mut arr).unwrap();
+        set_max_steps(None);
+
+        assert_eq!(steps.len(), 11);
+        assert_eq!(steps[10].metadata["array_state"], serde_json::json!(arr));
+
+        let mut sorted = arr.clone();
+        sorted.sort();
+        assert_eq!(sorted, vec![1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    fn test_progress_reaches_final_step_count() {
+        let mut arr: Vec<i32> = (0..30).rev().collect();
+        let mut calls = 0;
+        let mut last = 0;
+        let mut progress = |count| {
+            calls += 1;
+            last = count;
+        };
+
+        let steps = bubble_sort_with_progress(&mut arr, &mut progress).unwrap();
+
+        assert!(calls > 1);
+        assert_eq!(last, steps.len());
+        assert_eq!(arr, (0..30).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_bucket_sort_clustered() {
+        let mut arr = vec![101, 3, 100, 2, 102, 1, 99, 3, -5];
+        let steps = bucket_sort_with_steps(&mut arr).unwrap();
+        assert_eq!(arr, vec![-5, 1, 2, 3, 3, 99, 100, 101, 102]);
+        assert_eq!(steps.last().unwrap().metadata["array_state"], serde_json::json!(arr));
+    }
+
+    #[test]
+    fn test_bucket_sort_uniform() {
+        let mut arr: Vec<i32> = (0..25).map(|i| (i * 37) % 50).collect();
+        let mut expected = arr.clone();
+        expected.sort();
+
+        let steps = bucket_sort_with_steps(&mut arr).unwrap();
+        assert_eq!(arr, expected);
+
+        // Every element is placed into exactly one bucket
+        let placed = steps
+            .iter()
+            .filter(|s| s.metadata["operation"] == "distribute")
+            .count();
+        assert_eq!(placed, 25);
+    }
+
+    #[test]
+    fn test_bucket_sort_extreme_values() {
+        let mut arr = vec![i32::MAX, 0, i32::MIN, -1, 1];
+        bucket_sort_with_steps(&mut arr).unwrap();
+        assert_eq!(arr, vec![i32::MIN, -1, 0, 1, i32::MAX]);
+    }
+
+    #[test]
+    fn test_recursive_sorts_record_depth() {
+        let mut arr = vec![5, 2, 8, 1, 9, 3, 7, 4];
+        let steps = merge_sort_with_steps(&mut arr).unwrap();
+
+        let divide = steps.iter().find(|s| s.metadata["operation"] == "divide").unwrap();
+        assert_eq!(divide.metadata["recursion_depth"], 0);
+        assert_eq!(divide.metadata["range"], serde_json::json!([0, 7]));
+
+        let deepest = steps
+            .iter()
+            .filter_map(|s| s.metadata["recursion_depth"].as_u64())
+            .max()
+            .unwrap();
+        assert_eq!(deepest, 2);
+        assert!(steps
+            .iter()
+            .filter(|s| s.metadata["operation"] == "place")
+            .all(|s| s.metadata["phase"] == "merge"));
+
+        let mut arr = vec![3, 1, 2];
+        let steps = quick_sort_with_steps(&mut arr).unwrap();
+        let compare = steps.iter().find(|s| s.metadata["operation"] == "compare").unwrap();
+        assert_eq!(compare.metadata["phase"], "partition");
+        assert_eq!(compare.metadata["range"], serde_json::json!([0, 2]));
+    }
+
+    #[test]
+    fn test_search_range_finds_duplicate_span() {
+        let (range, steps) = search_range_with_steps(&[1, 2, 2, 2, 3], 2).unwrap();
+        assert_eq!(range, Some((1, 3)));
+        assert_eq!(steps.last().unwrap().metadata["range"], serde_json::json!([1, 3]));
+        assert!(steps.iter().any(|s| s.metadata["bound"] == "first"));
+        assert!(steps.iter().any(|s| s.metadata["bound"] == "last"));
+        assert!(steps.iter().all(|s| s.metadata.get("warning").is_none()));
+    }
+
+    #[test]
+    fn test_search_range_missing_value() {
+        let (range, steps) = search_range_with_steps(&[1, 2, 2, 2, 3], 4).unwrap();
+        assert_eq!(range, None);
+        assert!(steps.last().unwrap().metadata["range"].is_null());
+        assert!(steps.iter().all(|s| s.metadata["bound"] != "last"));
+    }
+
+    #[test]
+    fn test_search_range_warns_when_unsorted() {
+        let (_, steps) = search_range_with_steps(&[3, 1, 2], 1).unwrap();
+        assert_eq!(steps[1].metadata["warning"], "unsorted");
+    }
+
+    #[test]
+    fn test_search_range_past_step_limit() {
+        use crate::recorder::set_max_steps;
+
+        set_max_steps(Some(3));
+        let (range, steps) = search_range_with_steps(&[2; 64], 2).unwrap();
+        set_max_steps(None);
+
+        assert_eq!(range, Some((0, 63)));
+        assert_eq!(steps.last().unwrap().metadata["truncated"], true);
+    }
 }
@@ -0,0 +1,197 @@
+//! Sorting on tagged `(value, tag)` pairs to demonstrate sort stability.
+//!
+//! Equal values get distinct tags (3a, 3b, ...) so the animation shows whether
+//! a sort keeps them in their original relative order.
+
+use crate::error::Result;
+use crate::recorder::StepRecorder;
+use crate::traits::Step;
+
+/// A value paired with a tag that tells equal values apart.
+pub type Tagged = (i32, char);
+
+/// Tag each value by occurrence: the first 3 becomes `(3, 'a')`, the second `(3, 'b')`, and so on.
+pub fn tag_duplicates(values: &[i32]) -> Vec<Tagged> {
+    let mut counts = std::collections::HashMap::new();
+
+    values
+        .iter()
+        .map(|&value| {
+            let count = counts.entry(value).or_insert(0usize);
+            let tag = (b'a' + (*count % 26) as u8) as char;
+            *count += 1;
+            (value, tag)
+        })
+        .collect()
+}
+
+fn values(arr: &[Tagged]) -> Vec<i32> {
+    arr.iter().map(|&(value, _)| value).collect()
+}
+
+fn tags(arr: &[Tagged]) -> Vec<String> {
+    arr.iter().map(|&(_, tag)| tag.to_string()).collect()
+}
+
+fn label(item: Tagged) -> String {
+    format!("{}{}", item.0, item.1)
+}
+
+/// Insertion sort on tagged pairs, comparing values only. Stable: equal values
+/// are never moved past each other.
+pub fn insertion_sort_tagged_with_steps(arr: &mut [Tagged]) -> Result<Vec<Step>> {
+    let mut steps = StepRecorder::new();
+    let n = arr.len();
+
+    steps.push(Step {
+        description: "Starting Insertion Sort on tagged values (stable)".to_string(),
+        highlight_indices: vec![],
+        active_indices: vec![],
+        metadata: serde_json::json!({
+            "array_state": values(arr),
+            "tags": tags(arr)
+        }),
+        detail: None,
+    });
+
+    for i in 1..n {
+        if steps.is_truncated() {
+            break;
+        }
+
+        let key = arr[i];
+        let mut j = i;
+
+        // Strict comparison keeps equal values in their original order
+        while j > 0 && arr[j - 1].0 > key.0 {
+            arr[j] = arr[j - 1];
+            j -= 1;
+        }
+
+        arr[j] = key;
+
+        steps.push(Step {
+            description: format!("Inserted {} at position {}", label(key), j),
+            highlight_indices: vec![j],
+            active_indices: (0..=i).collect(),
+            metadata: serde_json::json!({
+                "operation": "insert",
+                "array_state": values(arr),
+                "tags": tags(arr)
+            }),
+            detail: None,
+        });
+    }
+
+    steps.push(Step {
+        description: "Insertion sort complete: equal values kept their tag order".to_string(),
+        highlight_indices: vec![],
+        active_indices: (0..n).collect(),
+        metadata: serde_json::json!({
+            "stable": true,
+            "array_state": values(arr),
+            "tags": tags(arr)
+        }),
+        detail: None,
+    });
+
+    Ok(steps.finish(&values(arr)))
+}
+
+/// Selection sort on tagged pairs, comparing values only. Unstable: the long
+/// swap can carry an element past an equal one.
+pub fn selection_sort_tagged_with_steps(arr: &mut [Tagged]) -> Result<Vec<Step>> {
+    let mut steps = StepRecorder::new();
+    let n = arr.len();
+
+    steps.push(Step {
+        description: "Starting Selection Sort on tagged values (unstable)".to_string(),
+        highlight_indices: vec![],
+        active_indices: vec![],
+        metadata: serde_json::json!({
+            "array_state": values(arr),
+            "tags": tags(arr)
+        }),
+        detail: None,
+    });
+
+    for i in 0..n.saturating_sub(1) {
+        if steps.is_truncated() {
+            break;
+        }
+
+        let min_idx = (i..n).fold(i, |min, j| if arr[j].0 < arr[min].0 { j } else { min });
+
+        if min_idx != i {
+            arr.swap(i, min_idx);
+
+            steps.push(Step {
+                description: format!("Swapped {} into index {}, moving {} to index {}", label(arr[i]), i, label(arr[min_idx]), min_idx),
+                highlight_indices: vec![min_idx],
+                active_indices: vec![i],
+                metadata: serde_json::json!({
+                    "operation": "swap",
+                    "array_state": values(arr),
+                    "tags": tags(arr)
+                }),
+                detail: None,
+            });
+        }
+    }
+
+    steps.push(Step {
+        description: "Selection sort complete: check whether equal values kept their tag order".to_string(),
+        highlight_indices: vec![],
+        active_indices: (0..n).collect(),
+        metadata: serde_json::json!({
+            "stable": is_stable_order(arr),
+            "array_state": values(arr),
+            "tags": tags(arr)
+        }),
+        detail: None,
+    });
+
+    Ok(steps.finish(&values(arr)))
+}
+
+/// Whether every run of equal values is still in tag order.
+pub fn is_stable_order(arr: &[Tagged]) -> bool {
+    arr.windows(2).all(|pair| pair[0].0 != pair[1].0 || pair[0].1 < pair[1].1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tag_duplicates() {
+        assert_eq!(tag_duplicates(&[3, 1, 3]), vec![(3, 'a'), (1, 'a'), (3, 'b')]);
+
+        // Tags wrap every 26 and keep counting past 255 equal values
+        let tagged = tag_duplicates(&[7; 300]);
+        assert_eq!(tagged[26].1, 'a');
+        assert_eq!(tagged[299].1, (b'a' + (299 % 26) as u8) as char);
+    }
+
+    #[test]
+    fn test_insertion_sort_is_stable() {
+        let mut arr = tag_duplicates(&[3, 3, 1]);
+        let steps = insertion_sort_tagged_with_steps(&mut arr).unwrap();
+
+        assert_eq!(arr, vec![(1, 'a'), (3, 'a'), (3, 'b')]);
+        assert!(is_stable_order(&arr));
+        assert_eq!(steps.last().unwrap().metadata["stable"], true);
+    }
+
+    #[test]
+    fn test_selection_sort_is_unstable() {
+        // Swapping 1 to the front carries 3a past 3b
+        let mut arr = tag_duplicates(&[3, 3, 1]);
+        let steps = selection_sort_tagged_with_steps(&mut arr).unwrap();
+
+        assert_eq!(arr, vec![(1, 'a'), (3, 'b'), (3, 'a')]);
+        assert!(!is_stable_order(&arr));
+        assert_eq!(steps.last().unwrap().metadata["stable"], false);
+        assert_eq!(steps.last().unwrap().metadata["tags"], serde_json::json!(["a", "b", "a"]));
+    }
+}
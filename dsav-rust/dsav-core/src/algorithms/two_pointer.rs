@@ -0,0 +1,130 @@
+//! Two-pointer technique on sorted arrays.
+
+use crate::error::Result;
+use crate::recorder::StepRecorder;
+use crate::traits::Step;
+
+/// Find two elements summing to `target` by moving a left and right pointer inward.
+///
+/// Assumes `arr` is sorted ascending; an unsorted input gets a warning step and
+/// the search may miss a valid pair. The final step's metadata holds
+/// `"pair": [left, right]` or `"pair": null`.
+pub fn two_sum_with_steps(arr: &[i32], target: i32) -> Result<Vec<Step>> {
+    let mut steps = StepRecorder::new();
+
+    steps.push(Step {
+        description: format!("Looking for two elements that sum to {}", target),
+        highlight_indices: vec![],
+        active_indices: vec![],
+        metadata: serde_json::json!({
+            "operation": "two_sum",
+            "target": target,
+            "array_state": arr.to_vec()
+        }),
+        detail: None,
+    });
+
+    if !arr.windows(2).all(|pair| pair[0] <= pair[1]) {
+        steps.push(Step {
+            description: "Warning: array is not sorted, the two-pointer search may miss a pair".to_string(),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "warning": "unsorted",
+                "array_state": arr.to_vec()
+            }),
+            detail: None,
+        });
+    }
+
+    let mut pair = None;
+    if arr.len() >= 2 {
+        let mut left = 0;
+        let mut right = arr.len() - 1;
+
+        while left < right && !steps.is_truncated() {
+            // i64 so extreme values can't overflow
+            let sum = arr[left] as i64 + arr[right] as i64;
+
+            let verdict = match sum.cmp(&(target as i64)) {
+                std::cmp::Ordering::Equal => "found",
+                std::cmp::Ordering::Less => "too small, moving left pointer right",
+                std::cmp::Ordering::Greater => "too large, moving right pointer left",
+            };
+
+            steps.push(Step {
+                description: format!("{} + {} = {}: {}", arr[left], arr[right], sum, verdict),
+                highlight_indices: vec![left, right],
+                active_indices: vec![],
+                metadata: serde_json::json!({
+                    "operation": "compare",
+                    "left": left,
+                    "right": right,
+                    "sum": sum,
+                    "array_state": arr.to_vec()
+                }),
+                detail: None,
+            });
+
+            match sum.cmp(&(target as i64)) {
+                std::cmp::Ordering::Equal => {
+                    pair = Some((left, right));
+                    break;
+                }
+                std::cmp::Ordering::Less => left += 1,
+                std::cmp::Ordering::Greater => right -= 1,
+            }
+        }
+    }
+
+    let (description, active) = match pair {
+        Some((left, right)) => (
+            format!("Found pair at indices {} and {}: {} + {} = {}", left, right, arr[left], arr[right], target),
+            vec![left, right],
+        ),
+        None => (format!("No pair sums to {}", target), vec![]),
+    };
+
+    steps.push(Step {
+        description,
+        highlight_indices: vec![],
+        active_indices: active,
+        metadata: serde_json::json!({
+            "pair": pair.map(|(left, right)| [left, right]),
+            "array_state": arr.to_vec()
+        }),
+        detail: None,
+    });
+
+    Ok(steps.finish(arr))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_two_sum_finds_pair() {
+        let steps = two_sum_with_steps(&[1, 2, 4, 7], 9).unwrap();
+        let last = steps.last().unwrap();
+
+        assert_eq!(last.metadata["pair"], serde_json::json!([1, 3]));
+        assert_eq!(last.active_indices, vec![1, 3]);
+        assert!(steps.iter().all(|s| s.metadata.get("warning").is_none()));
+    }
+
+    #[test]
+    fn test_two_sum_no_pair() {
+        let steps = two_sum_with_steps(&[1, 2, 4, 7], 100).unwrap();
+        let last = steps.last().unwrap();
+
+        assert!(last.metadata["pair"].is_null());
+        assert!(last.description.contains("No pair"));
+    }
+
+    #[test]
+    fn test_two_sum_warns_when_unsorted() {
+        let steps = two_sum_with_steps(&[7, 1, 4], 5).unwrap();
+        assert_eq!(steps[1].metadata["warning"], "unsorted");
+    }
+}
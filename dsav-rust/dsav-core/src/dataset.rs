@@ -0,0 +1,106 @@
+//! Random dataset generation shared by every structure.
+//!
+//! Passing a seed makes the generated values reproducible, so the same
+//! example can be shown across sessions. All randomness is drawn through a
+//! [`RandomSource`], so tests can supply a seeded one.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Smallest value produced by [`random_values`].
+pub const MIN_VALUE: i32 = 1;
+/// Largest value produced by [`random_values`].
+pub const MAX_VALUE: i32 = 100;
+
+/// A supply of random integers.
+pub trait RandomSource {
+    /// A value in `lo..=hi`.
+    fn next_in_range(&mut self, lo: i32, hi: i32) -> i32;
+
+    /// A seed for a [`SeededRandom`], so randomness drawn from this source can
+    /// be recorded and replayed.
+    fn next_seed(&mut self) -> u64;
+}
+
+/// Deterministic source: the same seed always yields the same sequence.
+#[derive(Debug, Clone)]
+pub struct SeededRandom {
+    rng: StdRng,
+}
+
+impl SeededRandom {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl RandomSource for SeededRandom {
+    fn next_in_range(&mut self, lo: i32, hi: i32) -> i32 {
+        self.rng.gen_range(lo..=hi)
+    }
+
+    fn next_seed(&mut self) -> u64 {
+        self.rng.gen()
+    }
+}
+
+/// Source backed by the thread-local RNG.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ThreadRandom;
+
+impl RandomSource for ThreadRandom {
+    fn next_in_range(&mut self, lo: i32, hi: i32) -> i32 {
+        rand::thread_rng().gen_range(lo..=hi)
+    }
+
+    fn next_seed(&mut self) -> u64 {
+        rand::thread_rng().gen()
+    }
+}
+
+/// Generate `count` values in `MIN_VALUE..=MAX_VALUE`.
+///
+/// With `Some(seed)` the same seed always yields the same values; with `None`
+/// the values come from the thread-local RNG.
+pub fn random_values(count: usize, seed: Option<u64>) -> Vec<i32> {
+    match seed {
+        Some(seed) => random_values_from(&mut SeededRandom::new(seed), count),
+        None => random_values_from(&mut ThreadRandom, count),
+    }
+}
+
+/// Generate `count` values in `MIN_VALUE..=MAX_VALUE` from `source`.
+pub fn random_values_from(source: &mut dyn RandomSource, count: usize) -> Vec<i32> {
+    (0..count).map(|_| source.next_in_range(MIN_VALUE, MAX_VALUE)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_values() {
+        assert_eq!(random_values(16, Some(1234)), random_values(16, Some(1234)));
+        assert_ne!(random_values(16, Some(1234)), random_values(16, Some(4321)));
+    }
+
+    #[test]
+    fn test_values_in_range() {
+        let values = random_values(200, None);
+        assert_eq!(values.len(), 200);
+        assert!(values.iter().all(|v| (MIN_VALUE..=MAX_VALUE).contains(v)));
+    }
+
+    #[test]
+    fn test_seeded_sources_match() {
+        let mut first = SeededRandom::new(99);
+        let mut second = SeededRandom::new(99);
+
+        let a: Vec<i32> = (0..32).map(|_| first.next_in_range(-50, 50)).collect();
+        let b: Vec<i32> = (0..32).map(|_| second.next_in_range(-50, 50)).collect();
+        assert_eq!(a, b);
+        assert!(a.iter().all(|v| (-50..=50).contains(v)));
+    }
+}
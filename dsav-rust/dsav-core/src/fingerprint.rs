@@ -0,0 +1,94 @@
+//! Stable fingerprints of step sequences for snapshot-style regression tests.
+//!
+//! The hash is FNV-1a, computed here rather than with `std`'s hasher, whose
+//! output may change between Rust releases.
+
+use crate::traits::Step;
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+struct Fnv(u64);
+
+impl Fnv {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    /// Length-prefixed so adjacent fields can't run together.
+    fn write_field(&mut self, bytes: &[u8]) {
+        self.write(&(bytes.len() as u64).to_le_bytes());
+        self.write(bytes);
+    }
+
+    fn write_indices(&mut self, indices: &[usize]) {
+        self.write(&(indices.len() as u64).to_le_bytes());
+        for &idx in indices {
+            self.write(&(idx as u64).to_le_bytes());
+        }
+    }
+}
+
+/// Hash the ordered descriptions, index vectors and metadata of `steps` into
+/// a 16-digit hex string that stays the same from run to run.
+///
+/// Metadata is hashed in its serialized form, whose object keys are sorted,
+/// so the fingerprint doesn't depend on insertion order. Steps carrying a
+/// `"seed"` (random shuffles) are only stable for a fixed seed.
+pub fn step_fingerprint(steps: &[Step]) -> String {
+    let mut hash = Fnv(FNV_OFFSET);
+
+    hash.write(&(steps.len() as u64).to_le_bytes());
+    for step in steps {
+        hash.write_field(step.description.as_bytes());
+        hash.write_indices(&step.highlight_indices);
+        hash.write_indices(&step.active_indices);
+        hash.write_field(step.metadata.to_string().as_bytes());
+        hash.write_field(step.detail.as_deref().unwrap_or_default().as_bytes());
+    }
+
+    format!("{:016x}", hash.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::sorting::bubble_sort_with_steps;
+
+    fn bubble_sort_fingerprint(input: &[i32]) -> String {
+        let mut arr = input.to_vec();
+        step_fingerprint(&bubble_sort_with_steps(&mut arr).unwrap())
+    }
+
+    #[test]
+    fn test_fingerprint_stable_across_runs() {
+        let first = bubble_sort_fingerprint(&[5, 2, 8, 1]);
+        assert_eq!(first, bubble_sort_fingerprint(&[5, 2, 8, 1]));
+        assert_eq!(first.len(), 16);
+        assert_ne!(first, bubble_sort_fingerprint(&[5, 2, 1, 8]));
+
+        // Pinned: any change to bubble sort's steps must update this on purpose
+        assert_eq!(first, "02a986f71e89a6c1");
+    }
+
+    #[test]
+    fn test_fingerprint_snapshot() {
+        let step = Step {
+            description: "Compare".to_string(),
+            highlight_indices: vec![0, 1],
+            active_indices: vec![],
+            metadata: serde_json::json!({ "b": 2, "a": 1 }),
+            detail: None,
+        };
+
+        assert_eq!(step_fingerprint(&[]), "a8c7f832281a39c5");
+        assert_eq!(step_fingerprint(std::slice::from_ref(&step)), "735d1a8a531be388");
+
+        let mut moved = step.clone();
+        moved.highlight_indices = vec![0, 2];
+        assert_ne!(step_fingerprint(&[step]), step_fingerprint(&[moved]));
+    }
+}
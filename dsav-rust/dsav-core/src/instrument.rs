@@ -0,0 +1,116 @@
+//! Optional structured logging of executed operations.
+//!
+//! With the `tracing` feature enabled, every `execute_with_steps` call emits
+//! a `tracing` event carrying the structure, the operation, the number of
+//! steps recorded and how long it took. Without the feature this compiles to
+//! a plain call and the crate has no `tracing` dependency.
+
+use crate::error::Result;
+use crate::traits::{Operation, Step};
+
+/// Run `execute`, reporting the outcome as a `DEBUG` event when the
+/// `tracing` feature is on.
+pub(crate) fn traced(
+    structure: &'static str,
+    operation: Operation,
+    execute: impl FnOnce() -> Result<Vec<Step>>,
+) -> Result<Vec<Step>> {
+    #[cfg(feature = "tracing")]
+    {
+        let start = std::time::Instant::now();
+        let result = execute();
+        let duration_us = start.elapsed().as_micros() as u64;
+
+        match &result {
+            Ok(steps) => tracing::debug!(
+                structure,
+                operation = ?operation,
+                steps = steps.len(),
+                duration_us,
+                "operation executed"
+            ),
+            Err(error) => tracing::debug!(
+                structure,
+                operation = ?operation,
+                error = %error,
+                duration_us,
+                "operation failed"
+            ),
+        }
+
+        result
+    }
+
+    #[cfg(not(feature = "tracing"))]
+    {
+        let _ = (structure, operation);
+        execute()
+    }
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use super::*;
+    use crate::structures::VisualizableArray;
+    use crate::traits::Visualizable;
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    /// Subscriber that keeps the fields of every event as `name=value` text.
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Vec<String>>>);
+
+    struct Fields(Vec<String>);
+
+    impl Visit for Fields {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            self.0.push(format!("{}={:?}", field.name(), value));
+        }
+    }
+
+    impl Subscriber for Capture {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, _: &Attributes<'_>) -> Id {
+            Id::from_u64(1)
+        }
+
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut fields = Fields(Vec::new());
+            event.record(&mut fields);
+            self.0.lock().unwrap().push(fields.0.join(" "));
+        }
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[test]
+    fn test_bubble_sort_emits_event() {
+        let capture = Capture::default();
+        let mut arr = VisualizableArray::new(8);
+        for value in [5, 2, 8, 1] {
+            arr.push(value).unwrap();
+        }
+
+        let steps = tracing::subscriber::with_default(capture.clone(), || {
+            arr.execute_with_steps(Operation::BubbleSort).unwrap()
+        });
+
+        let events = capture.0.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        assert!(events[0].contains("structure=\"array\""));
+        assert!(events[0].contains("operation=BubbleSort"));
+        assert!(events[0].contains(&format!("steps={}", steps.len())));
+        assert!(events[0].contains("duration_us="));
+    }
+}
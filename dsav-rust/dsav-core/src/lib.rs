@@ -7,9 +7,17 @@
 pub mod error;
 pub mod traits;
 pub mod state;
+pub mod recorder;
 pub mod structures;
 pub mod algorithms;
+pub mod dataset;
+pub mod fingerprint;
+pub mod profile;
+mod instrument;
 
 pub use error::{DsavError, Result};
 pub use traits::{Visualizable, Step, Operation};
-pub use state::{RenderState, RenderElement, ElementState};
+pub use state::{RenderState, RenderElement, ElementState, RenderDiff};
+pub use recorder::{StepRecorder, set_max_steps};
+pub use fingerprint::step_fingerprint;
+pub use profile::time_operation;
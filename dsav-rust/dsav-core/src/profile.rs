@@ -0,0 +1,40 @@
+//! Measuring how long step generation takes.
+
+use std::time::{Duration, Instant};
+
+use crate::error::Result;
+use crate::traits::{Operation, Step, Visualizable};
+
+/// Run `operation` through `execute_with_steps`, also returning the
+/// wall-clock time it took to produce the steps.
+///
+/// Only generation is timed, not playback; large sorts that take a noticeable
+/// time here are the ones worth generating off the UI thread.
+pub fn time_operation<V: Visualizable>(structure: &mut V, operation: Operation) -> Result<(Vec<Step>, Duration)> {
+    let start = Instant::now();
+    let steps = structure.execute_with_steps(operation)?;
+    Ok((steps, start.elapsed()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fingerprint::step_fingerprint;
+    use crate::structures::VisualizableArray;
+
+    #[test]
+    fn test_time_operation_matches_normal_path() {
+        let mut timed = VisualizableArray::new(100);
+        for value in (0..100).rev() {
+            timed.push(value).unwrap();
+        }
+        let mut plain = timed.clone();
+
+        let (steps, elapsed) = time_operation(&mut timed, Operation::BubbleSort).unwrap();
+        assert!(elapsed > Duration::ZERO);
+
+        let expected = plain.execute_with_steps(Operation::BubbleSort).unwrap();
+        assert_eq!(steps.len(), expected.len());
+        assert_eq!(step_fingerprint(&steps), step_fingerprint(&expected));
+    }
+}
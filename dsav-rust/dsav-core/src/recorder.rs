@@ -0,0 +1,212 @@
+//! Step recording with an optional cap on the number of generated steps.
+//!
+//! Large inputs can make sorting algorithms produce hundreds of thousands of
+//! steps. The cap is stored per thread so callers (like the GUI) can set it
+//! once instead of threading it through every algorithm signature.
+
+use crate::traits::Step;
+use std::cell::Cell;
+
+thread_local! {
+    static MAX_STEPS: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Set the maximum number of steps recorded per operation on the current thread.
+///
+/// `None` removes the limit.
+pub fn set_max_steps(limit: Option<usize>) {
+    MAX_STEPS.with(|max| max.set(limit));
+}
+
+/// The step limit currently configured for this thread.
+pub fn max_steps() -> Option<usize> {
+    MAX_STEPS.with(|max| max.get())
+}
+
+/// How many recorded steps pass between progress callbacks.
+pub const PROGRESS_INTERVAL: usize = 100;
+
+/// Collects steps for an algorithm, stopping once the step limit is reached.
+#[derive(Default)]
+pub struct StepRecorder<'a> {
+    steps: Vec<Step>,
+    limit: Option<usize>,
+    truncated: bool,
+    progress: Option<&'a mut dyn FnMut(usize)>,
+}
+
+impl std::fmt::Debug for StepRecorder<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StepRecorder")
+            .field("steps", &self.steps)
+            .field("limit", &self.limit)
+            .field("truncated", &self.truncated)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> StepRecorder<'a> {
+    /// Create a recorder using the limit configured via [`set_max_steps`].
+    pub fn new() -> Self {
+        Self::with_limit(max_steps())
+    }
+
+    pub fn with_limit(limit: Option<usize>) -> Self {
+        Self {
+            steps: Vec::new(),
+            limit,
+            truncated: false,
+            progress: None,
+        }
+    }
+
+    /// Create a recorder that reports the step count to `progress` every
+    /// [`PROGRESS_INTERVAL`] steps and once more from [`Self::finish`].
+    pub fn with_progress(progress: &'a mut dyn FnMut(usize)) -> Self {
+        Self {
+            progress: Some(progress),
+            ..Self::new()
+        }
+    }
+
+    /// Record a step. Steps past the limit are dropped and mark the recording as truncated.
+    pub fn push(&mut self, step: Step) {
+        if self.truncated {
+            return;
+        }
+
+        if let Some(limit) = self.limit {
+            if self.steps.len() >= limit {
+                self.truncated = true;
+                return;
+            }
+        }
+
+        self.steps.push(step);
+
+        if self.steps.len() % PROGRESS_INTERVAL == 0 {
+            if let Some(progress) = self.progress.as_mut() {
+                progress(self.steps.len());
+            }
+        }
+    }
+
+    /// Whether generation has hit the limit. Algorithms check this to stop early.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Merge `fields` into the metadata of every step recorded since `start`.
+    pub(crate) fn annotate_since(&mut self, start: usize, fields: &serde_json::Value) {
+        let Some(fields) = fields.as_object() else {
+            return;
+        };
+
+        for step in self.steps.iter_mut().skip(start) {
+            if let Some(metadata) = step.metadata.as_object_mut() {
+                for (key, value) in fields {
+                    metadata.insert(key.clone(), value.clone());
+                }
+            }
+        }
+    }
+
+    /// Finish recording, appending a truncation marker if the limit was hit.
+    ///
+    /// `array_state` is the array as the algorithm left it, so the marker shows
+    /// the partially processed data.
+    pub fn finish(mut self, array_state: &[i32]) -> Vec<Step> {
+        if self.truncated {
+            let limit = self.limit.unwrap_or(self.steps.len());
+            self.steps.push(Step {
+                description: format!("Truncated: reached step limit of {}", limit),
+                highlight_indices: vec![],
+                active_indices: vec![],
+                metadata: serde_json::json!({
+                    "truncated": true,
+                    "limit": limit,
+                    "array_state": array_state.to_vec()
+                }),
+                detail: None,
+            });
+        }
+
+        if let Some(progress) = self.progress.as_mut() {
+            progress(self.steps.len());
+        }
+
+        self.steps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(n: usize) -> Step {
+        Step {
+            description: format!("Step {}", n),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({}),
+            detail: None,
+        }
+    }
+
+    #[test]
+    fn test_recorder_without_limit() {
+        let mut recorder = StepRecorder::with_limit(None);
+        for i in 0..100 {
+            recorder.push(step(i));
+        }
+
+        assert!(!recorder.is_truncated());
+        assert_eq!(recorder.finish(&[]).len(), 100);
+    }
+
+    #[test]
+    fn test_recorder_truncates_at_limit() {
+        let mut recorder = StepRecorder::with_limit(Some(3));
+        for i in 0..5 {
+            recorder.push(step(i));
+        }
+
+        assert!(recorder.is_truncated());
+        let steps = recorder.finish(&[1, 2]);
+        assert_eq!(steps.len(), 4);
+        assert_eq!(steps[3].metadata["truncated"], true);
+        assert_eq!(steps[3].metadata["array_state"], serde_json::json!([1, 2]));
+    }
+
+    #[test]
+    fn test_recorder_reports_progress() {
+        let mut reported = Vec::new();
+        let mut progress = |count| reported.push(count);
+        let mut recorder = StepRecorder::with_progress(&mut progress);
+        for i in 0..250 {
+            recorder.push(step(i));
+        }
+
+        assert_eq!(recorder.finish(&[]).len(), 250);
+        assert_eq!(reported, vec![100, 200, 250]);
+    }
+
+    #[test]
+    fn test_exactly_limit_is_not_truncated() {
+        let mut recorder = StepRecorder::with_limit(Some(3));
+        for i in 0..3 {
+            recorder.push(step(i));
+        }
+
+        assert!(!recorder.is_truncated());
+        assert_eq!(recorder.finish(&[]).len(), 3);
+    }
+}
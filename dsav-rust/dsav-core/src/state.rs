@@ -1,9 +1,85 @@
 //! Rendering state types for visualization.
 
+use crate::error::{DsavError, Result};
+
 #[derive(Debug, Clone)]
 pub struct RenderState {
     pub elements: Vec<RenderElement>,
     pub connections: Vec<(usize, usize)>,
+    /// Row width for grid-shaped structures such as matrices, whose elements
+    /// are listed row by row. `None` for everything else.
+    pub cols: Option<usize>,
+}
+
+/// Element positions that differ between two render states.
+///
+/// `added` and `changed` index into the newer state, `removed` into the older.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RenderDiff {
+    pub added: Vec<usize>,
+    pub removed: Vec<usize>,
+    pub changed: Vec<usize>,
+}
+
+impl RenderDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl RenderState {
+    /// State for an empty structure: a single placeholder element whose label
+    /// says what is empty and whose sublabel hints how to fill it.
+    pub fn empty_placeholder(label: &str, hint: &str) -> Self {
+        Self {
+            elements: vec![RenderElement::new(0)
+                .with_label(label.to_string())
+                .with_sublabel(hint.to_string())
+                .with_state(ElementState::Placeholder)],
+            connections: Vec::new(),
+            cols: None,
+        }
+    }
+
+    /// Whether this state stands in for an empty structure.
+    pub fn is_empty_placeholder(&self) -> bool {
+        matches!(self.elements.as_slice(), [elem] if elem.state == ElementState::Placeholder)
+    }
+
+    /// Compare with `newer` position by position. Placeholders and the
+    /// blank padding in tree layouts count as no element.
+    pub fn diff(&self, newer: &RenderState) -> RenderDiff {
+        fn present(state: &RenderState, idx: usize) -> Option<&str> {
+            state
+                .elements
+                .get(idx)
+                .filter(|elem| elem.state != ElementState::Placeholder && !elem.label.is_empty())
+                .map(|elem| elem.label.as_str())
+        }
+
+        let mut diff = RenderDiff::default();
+        for idx in 0..self.elements.len().max(newer.elements.len()) {
+            match (present(self, idx), present(newer, idx)) {
+                (Some(old), Some(new)) if old != new => diff.changed.push(idx),
+                (None, Some(_)) => diff.added.push(idx),
+                (Some(_), None) => diff.removed.push(idx),
+                _ => {}
+            }
+        }
+        diff
+    }
+
+    /// Check that every connection endpoint refers to an existing element.
+    pub fn validate(&self) -> Result<()> {
+        let len = self.elements.len();
+
+        match self.connections.iter().find(|&&(from, to)| from >= len || to >= len) {
+            Some(&(from, to)) => Err(DsavError::InvalidState {
+                reason: format!("Connection ({}, {}) references a missing element ({} elements)", from, to, len),
+            }),
+            None => Ok(()),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -12,6 +88,14 @@ pub struct RenderElement {
     pub state: ElementState,
     pub label: String,
     pub sublabel: String,
+    /// Distinguishes equal values (3a, 3b) in stability demonstrations.
+    pub tag: Option<char>,
+    /// Identity of the logical element, stable across `render_state` calls
+    /// so views can follow an element as it moves.
+    pub id: Option<u64>,
+    /// Short role marker such as "root" or "pivot", drawn as a chip at the
+    /// element's corner.
+    pub badge: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -22,6 +106,10 @@ pub enum ElementState {
     Sorted,
     Comparing,
     Swapping,
+    /// Stand-in shown when a structure has no elements.
+    Placeholder,
+    /// Unused slot inside a structure's storage, like a gap buffer's gap.
+    Gap,
 }
 
 impl RenderElement {
@@ -31,6 +119,9 @@ impl RenderElement {
             state: ElementState::Normal,
             label: value.to_string(),
             sublabel: String::new(),
+            tag: None,
+            id: None,
+            badge: None,
         }
     }
 
@@ -44,8 +135,87 @@ impl RenderElement {
         self
     }
 
+    /// Attach a tag and show it after the value in the label.
+    pub fn with_tag(mut self, tag: char) -> Self {
+        self.tag = Some(tag);
+        self.label = format!("{}{}", self.value, tag);
+        self
+    }
+
+    pub fn with_badge(mut self, badge: &str) -> Self {
+        self.badge = Some(badge.to_string());
+        self
+    }
+
+    pub fn with_id(mut self, id: u64) -> Self {
+        self.id = Some(id);
+        self
+    }
+
     pub fn with_state(mut self, state: ElementState) -> Self {
         self.state = state;
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_accepts_valid_state() {
+        let state = RenderState {
+            elements: vec![RenderElement::new(1), RenderElement::new(2)],
+            connections: vec![(0, 1)],
+            cols: None,
+        };
+        assert!(state.validate().is_ok());
+        assert!(RenderState::empty_placeholder("Empty", "Insert").validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_dangling_connection() {
+        let state = RenderState {
+            elements: vec![RenderElement::new(1), RenderElement::new(2)],
+            connections: vec![(0, 1), (1, 2)],
+            cols: None,
+        };
+        assert!(matches!(state.validate(), Err(DsavError::InvalidState { .. })));
+    }
+
+    #[test]
+    fn test_diff_reports_added_removed_changed() {
+        let old = RenderState {
+            elements: vec![RenderElement::new(1), RenderElement::new(2), RenderElement::new(3)],
+            connections: vec![],
+            cols: None,
+        };
+        let new = RenderState {
+            elements: vec![RenderElement::new(1), RenderElement::new(5)],
+            connections: vec![],
+            cols: None,
+        };
+
+        let diff = old.diff(&new);
+        assert_eq!(diff.changed, vec![1]);
+        assert_eq!(diff.removed, vec![2]);
+        assert!(diff.added.is_empty());
+
+        let diff = new.diff(&old);
+        assert_eq!(diff.added, vec![2]);
+        assert!(old.diff(&old).is_empty());
+    }
+
+    #[test]
+    fn test_diff_treats_placeholder_as_empty() {
+        let empty = RenderState::empty_placeholder("Empty", "Insert");
+        let state = RenderState {
+            elements: vec![RenderElement::new(7), RenderElement::new(8)],
+            connections: vec![],
+            cols: None,
+        };
+
+        assert_eq!(empty.diff(&state).added, vec![0, 1]);
+        assert_eq!(state.diff(&empty).removed, vec![0, 1]);
+    }
+}
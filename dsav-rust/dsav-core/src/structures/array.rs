@@ -5,11 +5,16 @@
 
 use crate::error::{DsavError, Result};
 use crate::state::{RenderElement, RenderState};
+use crate::instrument::traced;
 use crate::traits::{Operation, Step, Visualizable};
+use std::collections::{HashMap, HashSet, VecDeque};
 
 #[derive(Debug, Clone)]
 pub struct VisualizableArray {
     elements: Vec<i32>,
+    /// Identity of each element, parallel to `elements`.
+    ids: Vec<u64>,
+    next_id: u64,
     capacity: usize,
 }
 
@@ -17,6 +22,8 @@ impl VisualizableArray {
     pub fn new(capacity: usize) -> Self {
         Self {
             elements: Vec::with_capacity(capacity),
+            ids: Vec::with_capacity(capacity),
+            next_id: 0,
             capacity,
         }
     }
@@ -36,9 +43,16 @@ impl VisualizableArray {
         }
 
         self.elements.insert(index, value);
+        self.ids.insert(index, self.next_id);
+        self.next_id += 1;
         Ok(())
     }
 
+    /// Append a value after the last element.
+    pub fn push(&mut self, value: i32) -> Result<()> {
+        self.insert(self.elements.len(), value)
+    }
+
     pub fn delete(&mut self, index: usize) -> Result<i32> {
         if index >= self.elements.len() {
             return Err(DsavError::IndexOutOfBounds {
@@ -47,6 +61,7 @@ impl VisualizableArray {
             });
         }
 
+        self.ids.remove(index);
         Ok(self.elements.remove(index))
     }
 
@@ -72,6 +87,248 @@ impl VisualizableArray {
         })
     }
 
+    /// Replace every occurrence of `old` with `new`, returning how many were replaced.
+    pub fn replace_all(&mut self, old: i32, new: i32) -> usize {
+        let mut count = 0;
+        for element in self.elements.iter_mut().filter(|e| **e == old) {
+            *element = new;
+            count += 1;
+        }
+        count
+    }
+
+    /// Limit every element to `min..=max`, returning how many were changed.
+    pub fn clamp(&mut self, min: i32, max: i32) -> Result<usize> {
+        Self::check_clamp_bounds(min, max)?;
+
+        let mut count = 0;
+        for element in self.elements.iter_mut().filter(|e| !(min..=max).contains(*e)) {
+            *element = (*element).clamp(min, max);
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    fn check_clamp_bounds(min: i32, max: i32) -> Result<()> {
+        if min > max {
+            return Err(DsavError::InvalidState {
+                reason: format!("Clamp minimum {} is greater than maximum {}", min, max),
+            });
+        }
+        Ok(())
+    }
+
+    /// Remove repeated values, keeping the first occurrence of each in order.
+    /// Returns how many elements were removed.
+    pub fn remove_duplicates(&mut self) -> usize {
+        let mut seen = HashSet::new();
+        let before = self.elements.len();
+        let keep: Vec<bool> = self.elements.iter().map(|value| seen.insert(*value)).collect();
+        let mut flags = keep.iter();
+        self.elements.retain(|_| *flags.next().unwrap());
+        let mut flags = keep.iter();
+        self.ids.retain(|_| *flags.next().unwrap());
+        before - self.elements.len()
+    }
+
+    /// Remove every occurrence of `value`, keeping the rest in order.
+    /// Returns how many elements were removed.
+    pub fn remove_value(&mut self, value: i32) -> usize {
+        let before = self.elements.len();
+        let keep: Vec<bool> = self.elements.iter().map(|&v| v != value).collect();
+        let mut flags = keep.iter();
+        self.elements.retain(|_| *flags.next().unwrap());
+        let mut flags = keep.iter();
+        self.ids.retain(|_| *flags.next().unwrap());
+        before - self.elements.len()
+    }
+
+    /// Reverse the elements in place.
+    pub fn reverse(&mut self) {
+        self.elements.reverse();
+        self.ids.reverse();
+    }
+
+    /// Run an algorithm that reorders the elements in place, then carry each
+    /// element's id over to wherever it ended up.
+    fn permute_with<T>(&mut self, f: impl FnOnce(&mut Vec<i32>) -> Result<T>) -> Result<T> {
+        let before = self.elements.clone();
+        let result = f(&mut self.elements);
+        self.remap_ids(&before);
+        result
+    }
+
+    /// Reassign ids after a reordering. Equal values are matched in their
+    /// original order; any value not present before gets a fresh id.
+    fn remap_ids(&mut self, before: &[i32]) {
+        let mut pool: HashMap<i32, VecDeque<u64>> = HashMap::new();
+        for (&value, &id) in before.iter().zip(&self.ids) {
+            pool.entry(value).or_default().push_back(id);
+        }
+
+        let mut ids = Vec::with_capacity(self.elements.len());
+        for value in &self.elements {
+            let id = match pool.get_mut(value).and_then(VecDeque::pop_front) {
+                Some(id) => id,
+                None => {
+                    self.next_id += 1;
+                    self.next_id - 1
+                }
+            };
+            ids.push(id);
+        }
+        self.ids = ids;
+    }
+
+    /// Linear search for `target`, returning its first index and the steps.
+    fn search_steps(&self, target: i32) -> (Option<usize>, Vec<Step>) {
+        self.search_steps_limited(target, None)
+    }
+
+    /// Linear search that gives up after `budget` comparisons, if one is set.
+    fn search_steps_limited(&self, target: i32, budget: Option<usize>) -> (Option<usize>, Vec<Step>) {
+        let mut steps = Vec::new();
+
+        for (i, &value) in self.elements.iter().enumerate() {
+            if budget == Some(i) {
+                steps.push(Step {
+                    description: format!(
+                        "Budget exhausted after {} comparison(s); {} element(s) left unchecked",
+                        i,
+                        self.elements.len() - i
+                    ),
+                    highlight_indices: vec![],
+                    active_indices: vec![],
+                    metadata: serde_json::json!({
+                        "budget_exhausted": true,
+                        "comparisons": i,
+                        "budget": i,
+                        "target": target
+                    }),
+                    detail: None,
+                });
+                return (None, steps);
+            }
+
+            steps.push(Step {
+                description: format!("Checking index {}: {}", i, value),
+                highlight_indices: vec![i],
+                active_indices: vec![],
+                metadata: serde_json::json!({
+                    "checking": value,
+                    "target": target
+                }),
+                detail: None,
+            });
+
+            if value == target {
+                steps.push(Step {
+                    description: format!("Found {} at index {}", target, i),
+                    highlight_indices: vec![],
+                    active_indices: vec![i],
+                    metadata: serde_json::json!({}),
+                    detail: None,
+                });
+                return (Some(i), steps);
+            }
+        }
+
+        steps.push(Step {
+            description: format!("Value {} not found", target),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({}),
+            detail: None,
+        });
+
+        (None, steps)
+    }
+
+    /// Linear search for `target` that stops after at most `budget`
+    /// comparisons, ending with a "budget exhausted" step if it gave up
+    /// before reaching the target or the end of the array.
+    pub fn search_with_budget_steps(&self, target: i32, budget: usize) -> Vec<Step> {
+        self.search_steps_limited(target, Some(budget)).1
+    }
+
+    /// Search for each target in turn, then summarize which were found.
+    ///
+    /// The final step's `results` metadata maps each target to its first
+    /// index, or `null` when it isn't present.
+    pub fn contains_all_with_steps(&self, targets: &[i32]) -> Vec<Step> {
+        let mut steps = Vec::new();
+        let mut results = serde_json::Map::new();
+
+        for (n, &target) in targets.iter().enumerate() {
+            steps.push(Step {
+                description: format!("Query {} of {}: searching for {}", n + 1, targets.len(), target),
+                highlight_indices: vec![],
+                active_indices: vec![],
+                metadata: serde_json::json!({
+                    "operation": "query",
+                    "target": target
+                }),
+                detail: None,
+            });
+
+            let (index, search) = self.search_steps(target);
+            steps.extend(search);
+            results.insert(target.to_string(), serde_json::json!(index));
+        }
+
+        let found: Vec<usize> = results.values().filter_map(|v| v.as_u64()).map(|i| i as usize).collect();
+        let summary: Vec<String> = targets
+            .iter()
+            .map(|target| match results[&target.to_string()].as_u64() {
+                Some(i) => format!("{}→{}", target, i),
+                None => format!("{}→not found", target),
+            })
+            .collect();
+
+        steps.push(Step {
+            description: format!("Results: {}", summary.join(", ")),
+            highlight_indices: vec![],
+            active_indices: found,
+            metadata: serde_json::json!({
+                "results": results
+            }),
+            detail: None,
+        });
+
+        steps
+    }
+
+    /// Copy of the elements in `[start, end)`.
+    pub fn get_range(&self, start: usize, end: usize) -> Result<Vec<i32>> {
+        self.check_range(start, end)?;
+        Ok(self.elements[start..end].to_vec())
+    }
+
+    /// Write `value` to every index in `[start, end)`.
+    pub fn fill_range(&mut self, start: usize, end: usize, value: i32) -> Result<()> {
+        self.check_range(start, end)?;
+        self.elements[start..end].fill(value);
+        Ok(())
+    }
+
+    fn check_range(&self, start: usize, end: usize) -> Result<()> {
+        if end > self.elements.len() {
+            return Err(DsavError::IndexOutOfBounds {
+                index: end,
+                size: self.elements.len(),
+            });
+        }
+
+        if start > end {
+            return Err(DsavError::IndexOutOfBounds {
+                index: start,
+                size: self.elements.len(),
+            });
+        }
+
+        Ok(())
+    }
+
     pub fn search(&self, value: i32) -> Option<usize> {
         self.elements.iter().position(|&x| x == value)
     }
@@ -87,11 +344,16 @@ impl VisualizableArray {
     pub fn capacity(&self) -> usize {
         self.capacity
     }
+
+    /// Format the array as `[1, 2, 3]`.
+    pub fn pretty_print(&self) -> String {
+        format!("{:?}", self.elements)
+    }
 }
 
 impl Visualizable for VisualizableArray {
     fn execute_with_steps(&mut self, operation: Operation) -> Result<Vec<Step>> {
-        match operation {
+        traced("array", operation, || match operation {
             Operation::Insert(index, value) => {
                 let mut steps = Vec::new();
 
@@ -104,6 +366,7 @@ impl Visualizable for VisualizableArray {
                         "value": value,
                         "index": index
                     }),
+                    detail: None,
                 });
 
                 if index < self.elements.len() {
@@ -112,6 +375,7 @@ impl Visualizable for VisualizableArray {
                         highlight_indices: (index..self.elements.len()).collect(),
                         active_indices: vec![],
                         metadata: serde_json::json!({}),
+                        detail: None,
                     });
                 }
 
@@ -122,6 +386,36 @@ impl Visualizable for VisualizableArray {
                     highlight_indices: vec![],
                     active_indices: vec![index],
                     metadata: serde_json::json!({}),
+                    detail: None,
+                });
+
+                Ok(steps)
+            }
+
+            Operation::Append(value) => {
+                let mut steps = Vec::new();
+                let index = self.elements.len();
+
+                steps.push(Step {
+                    description: format!("Appending {} at end", value),
+                    highlight_indices: vec![],
+                    active_indices: vec![],
+                    metadata: serde_json::json!({
+                        "operation": "append",
+                        "value": value,
+                        "index": index
+                    }),
+                    detail: None,
+                });
+
+                self.push(value)?;
+
+                steps.push(Step {
+                    description: format!("Appended {} at index {}", value, index),
+                    highlight_indices: vec![],
+                    active_indices: vec![index],
+                    metadata: serde_json::json!({}),
+                    detail: None,
                 });
 
                 Ok(steps)
@@ -137,6 +431,7 @@ impl Visualizable for VisualizableArray {
                     highlight_indices: vec![index],
                     active_indices: vec![],
                     metadata: serde_json::json!({}),
+                    detail: None,
                 });
 
                 self.delete(index)?;
@@ -147,6 +442,7 @@ impl Visualizable for VisualizableArray {
                         highlight_indices: (index..self.elements.len()).collect(),
                         active_indices: vec![],
                         metadata: serde_json::json!({}),
+                        detail: None,
                     });
                 }
 
@@ -155,41 +451,502 @@ impl Visualizable for VisualizableArray {
                     highlight_indices: vec![],
                     active_indices: vec![],
                     metadata: serde_json::json!({}),
+                    detail: None,
                 });
 
                 Ok(steps)
             }
 
-            Operation::Search(target) => {
+            Operation::Search(target) => Ok(self.search_steps(target).1),
+
+            Operation::SearchWithBudget(target, budget) => Ok(self.search_with_budget_steps(target, budget)),
+
+            Operation::Replace(old, new) => {
                 let mut steps = Vec::new();
+                let mut replaced = Vec::new();
+
+                steps.push(Step {
+                    description: format!("Replacing all {} with {}", old, new),
+                    highlight_indices: vec![],
+                    active_indices: vec![],
+                    metadata: serde_json::json!({
+                        "operation": "replace",
+                        "old_value": old,
+                        "new_value": new,
+                        "array_state": self.elements.clone()
+                    }),
+                    detail: None,
+                });
 
-                for (i, &value) in self.elements.iter().enumerate() {
+                for i in 0..self.elements.len() {
                     steps.push(Step {
-                        description: format!("Checking index {}: {}", i, value),
+                        description: format!("Checking index {}: {}", i, self.elements[i]),
                         highlight_indices: vec![i],
-                        active_indices: vec![],
+                        active_indices: replaced.clone(),
+                        metadata: serde_json::json!({
+                            "checking": self.elements[i],
+                            "target": old,
+                            "array_state": self.elements.clone()
+                        }),
+                        detail: None,
+                    });
+
+                    if self.elements[i] == old {
+                        self.elements[i] = new;
+                        replaced.push(i);
+
+                        steps.push(Step {
+                            description: format!("Replaced {} with {} at index {}", old, new, i),
+                            highlight_indices: vec![],
+                            active_indices: replaced.clone(),
+                            metadata: serde_json::json!({
+                                "index": i,
+                                "array_state": self.elements.clone()
+                            }),
+                            detail: None,
+                        });
+                    }
+                }
+
+                steps.push(Step {
+                    description: format!("Replaced {} occurrence(s) of {} with {}", replaced.len(), old, new),
+                    highlight_indices: vec![],
+                    active_indices: replaced.clone(),
+                    metadata: serde_json::json!({
+                        "replacements": replaced.len(),
+                        "array_state": self.elements.clone()
+                    }),
+                    detail: None,
+                });
+
+                Ok(steps)
+            }
+
+            Operation::GetRange(start, end) => {
+                let mut steps = Vec::new();
+
+                let range = self.get_range(start, end)?;
+
+                steps.push(Step {
+                    description: format!("Getting elements in range [{}, {})", start, end),
+                    highlight_indices: vec![],
+                    active_indices: vec![],
+                    metadata: serde_json::json!({
+                        "operation": "get_range",
+                        "start": start,
+                        "end": end
+                    }),
+                    detail: None,
+                });
+
+                steps.push(Step {
+                    description: format!("Range [{}, {}) contains {:?}", start, end, range),
+                    highlight_indices: vec![],
+                    active_indices: (start..end).collect(),
+                    metadata: serde_json::json!({
+                        "range": range
+                    }),
+                    detail: None,
+                });
+
+                Ok(steps)
+            }
+
+            Operation::FillRange(start, end, value) => {
+                let mut steps = Vec::new();
+
+                self.check_range(start, end)?;
+
+                steps.push(Step {
+                    description: format!("Filling range [{}, {}) with {}", start, end, value),
+                    highlight_indices: (start..end).collect(),
+                    active_indices: vec![],
+                    metadata: serde_json::json!({
+                        "operation": "fill_range",
+                        "start": start,
+                        "end": end,
+                        "value": value,
+                        "array_state": self.elements.clone()
+                    }),
+                    detail: None,
+                });
+
+                for index in start..end {
+                    self.elements[index] = value;
+
+                    steps.push(Step {
+                        description: format!("Wrote {} to index {}", value, index),
+                        highlight_indices: vec![],
+                        active_indices: vec![index],
                         metadata: serde_json::json!({
-                            "checking": value,
-                            "target": target
+                            "operation": "write",
+                            "index": index,
+                            "array_state": self.elements.clone()
                         }),
+                        detail: None,
                     });
+                }
+
+                Ok(steps)
+            }
+
+            Operation::Clamp(min, max) => {
+                let mut steps = Vec::new();
+
+                Self::check_clamp_bounds(min, max)?;
 
-                    if value == target {
+                steps.push(Step {
+                    description: format!("Clamping every element to [{}, {}]", min, max),
+                    highlight_indices: vec![],
+                    active_indices: vec![],
+                    metadata: serde_json::json!({
+                        "operation": "clamp",
+                        "min": min,
+                        "max": max,
+                        "array_state": self.elements.clone()
+                    }),
+                    detail: None,
+                });
+
+                let mut changed = Vec::new();
+                for index in 0..self.elements.len() {
+                    let before = self.elements[index];
+                    let after = before.clamp(min, max);
+
+                    if before == after {
                         steps.push(Step {
-                            description: format!("Found {} at index {}", target, i),
+                            description: format!("{} at index {} is within range", before, index),
                             highlight_indices: vec![],
-                            active_indices: vec![i],
-                            metadata: serde_json::json!({}),
+                            active_indices: vec![index],
+                            metadata: serde_json::json!({
+                                "operation": "check",
+                                "index": index,
+                                "array_state": self.elements.clone()
+                            }),
+                            detail: None,
                         });
-                        return Ok(steps);
+                        continue;
                     }
+
+                    self.elements[index] = after;
+                    changed.push(index);
+
+                    steps.push(Step {
+                        description: format!(
+                            "{} at index {} is {} the range, clamped to {}",
+                            before,
+                            index,
+                            if before < min { "below" } else { "above" },
+                            after
+                        ),
+                        highlight_indices: vec![index],
+                        active_indices: vec![],
+                        metadata: serde_json::json!({
+                            "operation": "clamp_element",
+                            "index": index,
+                            "before": before,
+                            "after": after,
+                            "array_state": self.elements.clone()
+                        }),
+                        detail: None,
+                    });
                 }
 
                 steps.push(Step {
-                    description: format!("Value {} not found", target),
+                    description: format!("Clamped {} element(s)", changed.len()),
+                    highlight_indices: vec![],
+                    active_indices: changed.clone(),
+                    metadata: serde_json::json!({
+                        "clamped": changed.len(),
+                        "array_state": self.elements.clone()
+                    }),
+                    detail: None,
+                });
+
+                Ok(steps)
+            }
+
+            Operation::RemoveDuplicates => {
+                let mut steps = Vec::new();
+                let mut seen = HashSet::new();
+                // Insertion-ordered copy of the seen set for display
+                let mut seen_order = Vec::new();
+                let mut write = 0;
+                let len = self.elements.len();
+
+                steps.push(Step {
+                    description: "Removing duplicates while preserving order".to_string(),
                     highlight_indices: vec![],
                     active_indices: vec![],
-                    metadata: serde_json::json!({}),
+                    metadata: serde_json::json!({
+                        "operation": "remove_duplicates",
+                        "seen": seen_order,
+                        "array_state": self.elements.clone()
+                    }),
+                    detail: None,
+                });
+
+                for read in 0..len {
+                    let value = self.elements[read];
+
+                    if seen.insert(value) {
+                        seen_order.push(value);
+                        self.elements[write] = value;
+                        self.ids[write] = self.ids[read];
+
+                        steps.push(Step {
+                            description: format!("{} not seen yet, keeping it at index {}", value, write),
+                            highlight_indices: vec![read],
+                            active_indices: (0..=write).collect(),
+                            metadata: serde_json::json!({
+                                "operation": "keep",
+                                "value": value,
+                                "seen": seen_order,
+                                "array_state": self.elements.clone()
+                            }),
+                            detail: None,
+                        });
+
+                        write += 1;
+                    } else {
+                        steps.push(Step {
+                            description: format!("{} already seen, skipping it", value),
+                            highlight_indices: vec![read],
+                            active_indices: (0..write).collect(),
+                            metadata: serde_json::json!({
+                                "operation": "skip",
+                                "value": value,
+                                "seen": seen_order,
+                                "array_state": self.elements.clone()
+                            }),
+                            detail: None,
+                        });
+                    }
+                }
+
+                self.elements.truncate(write);
+                self.ids.truncate(write);
+
+                steps.push(Step {
+                    description: format!("Removed {} duplicate(s), {} element(s) remain", len - write, write),
+                    highlight_indices: vec![],
+                    active_indices: (0..write).collect(),
+                    metadata: serde_json::json!({
+                        "removed": len - write,
+                        "seen": seen_order,
+                        "array_state": self.elements.clone()
+                    }),
+                    detail: None,
+                });
+
+                Ok(steps)
+            }
+
+            Operation::RemoveValue(target) => {
+                let mut steps = Vec::new();
+                let mut write = 0;
+                let len = self.elements.len();
+
+                steps.push(Step {
+                    description: format!("Compacting: removing every {} with a read and a write pointer", target),
+                    highlight_indices: vec![],
+                    active_indices: vec![],
+                    metadata: serde_json::json!({
+                        "operation": "remove_value",
+                        "target": target,
+                        "array_state": self.elements.clone()
+                    }),
+                    detail: None,
+                });
+
+                for read in 0..len {
+                    let value = self.elements[read];
+
+                    if value != target {
+                        self.elements[write] = value;
+                        self.ids[write] = self.ids[read];
+
+                        steps.push(Step {
+                            description: format!("Keeping {}: copy from read {} to write {}", value, read, write),
+                            highlight_indices: vec![read, write],
+                            active_indices: (0..=write).collect(),
+                            metadata: serde_json::json!({
+                                "operation": "keep",
+                                "read": read,
+                                "write": write,
+                                "value": value,
+                                "array_state": self.elements.clone()
+                            }),
+                            detail: None,
+                        });
+
+                        write += 1;
+                    } else {
+                        steps.push(Step {
+                            description: format!("Skipping {} at read {}, write stays at {}", value, read, write),
+                            highlight_indices: vec![read, write],
+                            active_indices: (0..write).collect(),
+                            metadata: serde_json::json!({
+                                "operation": "skip",
+                                "read": read,
+                                "write": write,
+                                "value": value,
+                                "array_state": self.elements.clone()
+                            }),
+                            detail: None,
+                        });
+                    }
+                }
+
+                self.elements.truncate(write);
+                self.ids.truncate(write);
+
+                steps.push(Step {
+                    description: format!("Removed {} occurrence(s) of {}, {} element(s) remain", len - write, target, write),
+                    highlight_indices: vec![],
+                    active_indices: (0..write).collect(),
+                    metadata: serde_json::json!({
+                        "removed": len - write,
+                        "array_state": self.elements.clone()
+                    }),
+                    detail: None,
+                });
+
+                Ok(steps)
+            }
+
+            Operation::ReverseArray => {
+                let mut steps = Vec::new();
+                let len = self.elements.len();
+
+                steps.push(Step {
+                    description: "Reversing in place by swapping from both ends".to_string(),
+                    highlight_indices: vec![],
+                    active_indices: vec![],
+                    metadata: serde_json::json!({
+                        "operation": "reverse",
+                        "array_state": self.elements.clone()
+                    }),
+                    detail: None,
+                });
+
+                let (mut left, mut right) = (0, len.saturating_sub(1));
+                while left < right {
+                    self.elements.swap(left, right);
+                    self.ids.swap(left, right);
+
+                    steps.push(Step {
+                        description: format!(
+                            "Swapping index {} and {}, then moving both pointers inward",
+                            left, right
+                        ),
+                        highlight_indices: vec![left, right],
+                        active_indices: (0..=left).chain(right..len).collect(),
+                        metadata: serde_json::json!({
+                            "operation": "swap",
+                            "left": left,
+                            "right": right,
+                            "array_state": self.elements.clone()
+                        }),
+                        detail: None,
+                    });
+
+                    left += 1;
+                    right -= 1;
+                }
+
+                if len % 2 == 1 {
+                    steps.push(Step {
+                        description: format!("Middle element {} at index {} stays in place", self.elements[left], left),
+                        highlight_indices: vec![left],
+                        active_indices: vec![],
+                        metadata: serde_json::json!({
+                            "operation": "middle",
+                            "index": left,
+                            "array_state": self.elements.clone()
+                        }),
+                        detail: None,
+                    });
+                }
+
+                steps.push(Step {
+                    description: format!("Reversed {} element(s) with {} swap(s)", len, len / 2),
+                    highlight_indices: vec![],
+                    active_indices: (0..len).collect(),
+                    metadata: serde_json::json!({
+                        "swaps": len / 2,
+                        "array_state": self.elements.clone()
+                    }),
+                    detail: None,
+                });
+
+                Ok(steps)
+            }
+
+            // Evens first, odds last; order within each group is not preserved
+            Operation::PartitionByParity => {
+                let mut steps = Vec::new();
+                let is_even = |value: i32| value % 2 == 0;
+                // Everything before `left` is even, everything from `right` on is odd
+                let (mut left, mut right) = (0, self.elements.len());
+
+                steps.push(Step {
+                    description: "Partitioning: evens to the front, odds to the back".to_string(),
+                    highlight_indices: vec![],
+                    active_indices: vec![],
+                    metadata: serde_json::json!({
+                        "operation": "partition_by_parity",
+                        "partition_index": left,
+                        "array_state": self.elements.clone()
+                    }),
+                    detail: None,
+                });
+
+                while left < right {
+                    let (description, operation, touched) = if is_even(self.elements[left]) {
+                        left += 1;
+                        let index = left - 1;
+                        (format!("{} at index {} is even, advancing left", self.elements[index], index), "advance", vec![index])
+                    } else if !is_even(self.elements[right - 1]) {
+                        right -= 1;
+                        (format!("{} at index {} is odd, retreating right", self.elements[right], right), "retreat", vec![right])
+                    } else {
+                        let (i, j) = (left, right - 1);
+                        self.elements.swap(i, j);
+                        self.ids.swap(i, j);
+                        left += 1;
+                        right -= 1;
+                        (
+                            format!("Swapping odd {} at {} with even {} at {}", self.elements[j], i, self.elements[i], j),
+                            "swap",
+                            vec![i, j],
+                        )
+                    };
+
+                    steps.push(Step {
+                        description,
+                        highlight_indices: touched,
+                        active_indices: (0..left).collect(),
+                        metadata: serde_json::json!({
+                            "operation": operation,
+                            "left": left,
+                            "right": right,
+                            "partition_index": left,
+                            "array_state": self.elements.clone()
+                        }),
+                        detail: None,
+                    });
+                }
+
+                steps.push(Step {
+                    description: format!("{} even value(s) now precede the odd ones", left),
+                    highlight_indices: vec![],
+                    active_indices: (0..left).collect(),
+                    metadata: serde_json::json!({
+                        "partition_index": left,
+                        "array_state": self.elements.clone()
+                    }),
+                    detail: None,
                 });
 
                 Ok(steps)
@@ -197,17 +954,35 @@ impl Visualizable for VisualizableArray {
 
             Operation::BubbleSort => {
                 use crate::algorithms::sorting::bubble_sort_with_steps;
-                bubble_sort_with_steps(&mut self.elements)
+                self.permute_with(|elements| bubble_sort_with_steps(elements))
             }
 
             Operation::InsertionSort => {
                 use crate::algorithms::sorting::insertion_sort_with_steps;
-                insertion_sort_with_steps(&mut self.elements)
+                self.permute_with(|elements| insertion_sort_with_steps(elements))
+            }
+
+            Operation::StableInsertionSort | Operation::UnstableSelectionSort => {
+                use crate::algorithms::stability::{
+                    insertion_sort_tagged_with_steps, selection_sort_tagged_with_steps, tag_duplicates,
+                };
+
+                self.permute_with(|elements| {
+                    let mut tagged = tag_duplicates(elements);
+                    let steps = if matches!(operation, Operation::StableInsertionSort) {
+                        insertion_sort_tagged_with_steps(&mut tagged)?
+                    } else {
+                        selection_sort_tagged_with_steps(&mut tagged)?
+                    };
+
+                    *elements = tagged.into_iter().map(|(value, _)| value).collect();
+                    Ok(steps)
+                })
             }
 
             Operation::QuickSort => {
                 use crate::algorithms::sorting::quick_sort_with_steps;
-                quick_sort_with_steps(&mut self.elements)
+                self.permute_with(|elements| quick_sort_with_steps(elements))
             }
 
             Operation::BinarySearch(target) => {
@@ -215,6 +990,17 @@ impl Visualizable for VisualizableArray {
                 binary_search_with_steps(&self.elements, target)
             }
 
+            Operation::SearchRange(target) => {
+                use crate::algorithms::sorting::search_range_with_steps;
+                let (_, steps) = search_range_with_steps(&self.elements, target)?;
+                Ok(steps)
+            }
+
+            Operation::TwoSum(target) => {
+                use crate::algorithms::two_pointer::two_sum_with_steps;
+                two_sum_with_steps(&self.elements, target)
+            }
+
             Operation::Update(index, value) => {
                 let mut steps = Vec::new();
 
@@ -230,6 +1016,7 @@ impl Visualizable for VisualizableArray {
                         "old_value": old_value,
                         "new_value": value
                     }),
+                    detail: None,
                 });
 
                 self.update(index, value)?;
@@ -239,6 +1026,7 @@ impl Visualizable for VisualizableArray {
                     highlight_indices: vec![],
                     active_indices: vec![index],
                     metadata: serde_json::json!({}),
+                    detail: None,
                 });
 
                 Ok(steps)
@@ -246,21 +1034,128 @@ impl Visualizable for VisualizableArray {
 
             Operation::SelectionSort => {
                 use crate::algorithms::sorting::selection_sort_with_steps;
-                selection_sort_with_steps(&mut self.elements)
+                self.permute_with(|elements| selection_sort_with_steps(elements))
             }
 
             Operation::MergeSort => {
                 use crate::algorithms::sorting::merge_sort_with_steps;
-                merge_sort_with_steps(&mut self.elements)
+                self.permute_with(|elements| merge_sort_with_steps(elements))
+            }
+
+            Operation::BucketSort => {
+                use crate::algorithms::sorting::bucket_sort_with_steps;
+                self.permute_with(|elements| bucket_sort_with_steps(elements))
+            }
+
+            Operation::Heapify => {
+                use crate::algorithms::heap::heapify_with_steps;
+                self.permute_with(|elements| heapify_with_steps(elements))
+            }
+
+            Operation::LongestIncreasingSubsequence => {
+                use crate::algorithms::dp::lis_with_steps;
+                let (_, steps) = lis_with_steps(&self.elements);
+                Ok(steps)
+            }
+
+            Operation::MaxSubarray => {
+                use crate::algorithms::dp::kadane_with_steps;
+                let (_, steps) = kadane_with_steps(&self.elements)?;
+                Ok(steps)
+            }
+
+            Operation::Sum => {
+                use crate::algorithms::aggregate::sum_with_steps;
+                let (_, steps) = sum_with_steps(&self.elements);
+                Ok(steps)
+            }
+
+            Operation::Average => {
+                use crate::algorithms::aggregate::average_with_steps;
+                let (_, steps) = average_with_steps(&self.elements)?;
+                Ok(steps)
+            }
+
+            Operation::Count => {
+                use crate::algorithms::aggregate::count_with_steps;
+                let (_, steps) = count_with_steps(&self.elements);
+                Ok(steps)
+            }
+
+            Operation::FindDuplicate => {
+                use crate::algorithms::duplicate::find_duplicate_with_steps;
+                let (_, steps) = find_duplicate_with_steps(&self.elements)?;
+                Ok(steps)
+            }
+
+            Operation::CountInversions => {
+                use crate::algorithms::inversions::count_inversions_with_steps;
+                let (_, steps) = count_inversions_with_steps(&self.elements);
+                Ok(steps)
+            }
+
+            Operation::Scale(factor) => {
+                use crate::algorithms::scale::scale_with_steps;
+                // Values change in place, so every element keeps its id
+                scale_with_steps(&mut self.elements, factor)
+            }
+
+            Operation::Compress => {
+                use crate::algorithms::rle::rle_encode_with_steps;
+                let (_, steps) = rle_encode_with_steps(&self.elements);
+                Ok(steps)
+            }
+
+            Operation::IsValidBstArray => {
+                use crate::algorithms::level_order::is_valid_bst_array_with_steps;
+                let levels: Vec<Option<i32>> = self.elements.iter().copied().map(Some).collect();
+                let (_, steps) = is_valid_bst_array_with_steps(&levels);
+                Ok(steps)
+            }
+
+            Operation::DutchFlag(pivot) => {
+                use crate::algorithms::partition::dutch_flag_with_steps;
+                self.permute_with(|elements| dutch_flag_with_steps(elements, pivot))
+            }
+
+            Operation::Median => {
+                use crate::algorithms::selection::median_of_medians_with_steps;
+                let (_, steps) = self.permute_with(|elements| median_of_medians_with_steps(elements))?;
+                Ok(steps)
+            }
+
+            Operation::Shuffle => {
+                use crate::algorithms::shuffle::shuffle_with_steps;
+                self.permute_with(|elements| shuffle_with_steps(elements))
             }
 
             _ => Err(DsavError::InvalidState {
                 reason: "Operation not supported for arrays".to_string(),
             }),
+        })
+    }
+
+    fn execute(&mut self, operation: Operation) -> Result<()> {
+        match operation {
+            Operation::Insert(index, value) => self.insert(index, value),
+            Operation::Delete(index) => self.delete(index).map(|_| ()),
+            Operation::Update(index, value) => self.update(index, value).map(|_| ()),
+            Operation::Append(value) => self.push(value),
+            Operation::FillRange(start, end, value) => self.fill_range(start, end, value),
+            Operation::Clamp(min, max) => self.clamp(min, max).map(|_| ()),
+            Operation::Replace(old, new) => {
+                self.replace_all(old, new);
+                Ok(())
+            }
+            _ => self.execute_with_steps(operation).map(|_| ()),
         }
     }
 
     fn render_state(&self) -> RenderState {
+        if self.elements.is_empty() {
+            return RenderState::empty_placeholder("Array is empty", "Insert a value to add elements");
+        }
+
         RenderState {
             elements: self
                 .elements
@@ -270,9 +1165,11 @@ impl Visualizable for VisualizableArray {
                     RenderElement::new(value)
                         .with_label(value.to_string())
                         .with_sublabel(format!("[{}]", i))
+                        .with_id(self.ids[i])
                 })
                 .collect(),
             connections: Vec::new(),
+            cols: None,
         }
     }
 }
@@ -318,4 +1215,303 @@ mod tests {
         assert_eq!(arr.search(20), Some(1));
         assert_eq!(arr.search(99), None);
     }
+
+    #[test]
+    fn test_array_search_budget_exhausted() {
+        let mut arr = VisualizableArray::new(5);
+        for value in [3, 1, 4, 1, 5] {
+            arr.push(value).unwrap();
+        }
+
+        let steps = arr.search_with_budget_steps(5, 2);
+        let last = steps.last().unwrap();
+        assert_eq!(last.metadata["budget_exhausted"], true);
+        assert_eq!(last.metadata["comparisons"], 2);
+        assert_eq!(steps.iter().filter(|s| s.metadata.get("checking").is_some()).count(), 2);
+
+        let steps = arr.search_with_budget_steps(5, 5);
+        assert_eq!(steps.last().unwrap().active_indices, vec![4]);
+    }
+
+    #[test]
+    fn test_array_append_to_empty() {
+        let mut arr = VisualizableArray::new(4);
+        let steps = arr.execute_with_steps(Operation::Append(7)).unwrap();
+
+        assert_eq!(arr.len(), 1);
+        assert_eq!(arr.get(0).unwrap(), 7);
+        assert_eq!(steps.last().unwrap().active_indices, vec![0]);
+
+        arr.push(8).unwrap();
+        assert_eq!(arr.get(1).unwrap(), 8);
+    }
+
+    #[test]
+    fn test_array_append_when_full() {
+        let mut arr = VisualizableArray::new(2);
+        arr.push(1).unwrap();
+        arr.push(2).unwrap();
+
+        assert!(matches!(arr.push(3), Err(DsavError::Full { capacity: 2 })));
+        assert!(matches!(
+            arr.execute_with_steps(Operation::Append(3)),
+            Err(DsavError::Full { capacity: 2 })
+        ));
+        assert_eq!(arr.len(), 2);
+    }
+
+    #[test]
+    fn test_array_pretty_print() {
+        let mut arr = VisualizableArray::new(4);
+        assert_eq!(arr.pretty_print(), "[]");
+
+        arr.push(1).unwrap();
+        arr.push(2).unwrap();
+        arr.push(3).unwrap();
+        assert_eq!(arr.pretty_print(), "[1, 2, 3]");
+    }
+
+    #[test]
+    fn test_array_replace_all_occurrences() {
+        let mut arr = VisualizableArray::new(8);
+        for value in [2, 1, 2, 3] {
+            arr.push(value).unwrap();
+        }
+
+        let steps = arr.execute_with_steps(Operation::Replace(2, 9)).unwrap();
+        assert_eq!(arr.pretty_print(), "[9, 1, 9, 3]");
+
+        let last = steps.last().unwrap();
+        assert_eq!(last.metadata["replacements"], 2);
+        assert_eq!(last.active_indices, vec![0, 2]);
+
+        assert_eq!(arr.replace_all(5, 6), 0);
+    }
+
+    #[test]
+    fn test_array_get_range() {
+        let mut arr = VisualizableArray::new(8);
+        for value in [10, 20, 30, 40] {
+            arr.push(value).unwrap();
+        }
+
+        let steps = arr.execute_with_steps(Operation::GetRange(1, 3)).unwrap();
+        let last = steps.last().unwrap();
+        assert_eq!(last.metadata["range"], serde_json::json!([20, 30]));
+        assert_eq!(last.active_indices, vec![1, 2]);
+
+        assert_eq!(arr.get_range(4, 4).unwrap(), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn test_array_get_range_invalid() {
+        let mut arr = VisualizableArray::new(8);
+        for value in [10, 20, 30, 40] {
+            arr.push(value).unwrap();
+        }
+
+        assert!(matches!(
+            arr.execute_with_steps(Operation::GetRange(2, 5)),
+            Err(DsavError::IndexOutOfBounds { index: 5, size: 4 })
+        ));
+        assert!(matches!(
+            arr.get_range(3, 1),
+            Err(DsavError::IndexOutOfBounds { index: 3, size: 4 })
+        ));
+    }
+
+    #[test]
+    fn test_array_fill_range() {
+        let mut arr = VisualizableArray::new(8);
+        for _ in 0..4 {
+            arr.push(0).unwrap();
+        }
+
+        let steps = arr.execute_with_steps(Operation::FillRange(1, 3, 5)).unwrap();
+        assert_eq!(arr.pretty_print(), "[0, 5, 5, 0]");
+
+        let writes: Vec<&Step> = steps.iter().filter(|s| s.metadata["operation"] == "write").collect();
+        assert_eq!(writes.len(), 2);
+        assert_eq!(writes[0].active_indices, vec![1]);
+        assert_eq!(writes[1].metadata["array_state"], serde_json::json!([0, 5, 5, 0]));
+
+        assert!(matches!(
+            arr.execute_with_steps(Operation::FillRange(2, 6, 1)),
+            Err(DsavError::IndexOutOfBounds { index: 6, size: 4 })
+        ));
+    }
+
+    #[test]
+    fn test_array_count_inversions_leaves_array() {
+        let mut arr = VisualizableArray::new(8);
+        for value in [3, 1, 2] {
+            arr.push(value).unwrap();
+        }
+
+        let steps = arr.execute_with_steps(Operation::CountInversions).unwrap();
+        assert_eq!(steps.last().unwrap().metadata["inversions"], 2);
+        assert_eq!(arr.pretty_print(), "[3, 1, 2]");
+    }
+
+    #[test]
+    fn test_array_clamp() {
+        let mut arr = VisualizableArray::new(8);
+        for value in [-5, 3, 200] {
+            arr.push(value).unwrap();
+        }
+
+        let steps = arr.execute_with_steps(Operation::Clamp(0, 100)).unwrap();
+        assert_eq!(arr.pretty_print(), "[0, 3, 100]");
+
+        let changes: Vec<(i64, i64)> = steps
+            .iter()
+            .filter(|s| s.metadata["operation"] == "clamp_element")
+            .map(|s| (s.metadata["before"].as_i64().unwrap(), s.metadata["after"].as_i64().unwrap()))
+            .collect();
+        assert_eq!(changes, vec![(-5, 0), (200, 100)]);
+        assert_eq!(steps.last().unwrap().metadata["clamped"], 2);
+
+        assert!(matches!(arr.clamp(5, 1), Err(DsavError::InvalidState { .. })));
+    }
+
+    #[test]
+    fn test_array_remove_duplicates() {
+        let mut arr = VisualizableArray::new(8);
+        for value in [3, 1, 3, 2, 1] {
+            arr.push(value).unwrap();
+        }
+
+        let steps = arr.execute_with_steps(Operation::RemoveDuplicates).unwrap();
+        assert_eq!(arr.pretty_print(), "[3, 1, 2]");
+
+        let seen_sizes: Vec<usize> = steps[1..6]
+            .iter()
+            .map(|s| s.metadata["seen"].as_array().unwrap().len())
+            .collect();
+        assert_eq!(seen_sizes, vec![1, 2, 2, 3, 3]);
+        assert_eq!(steps.last().unwrap().metadata["removed"], 2);
+        assert_eq!(steps.last().unwrap().metadata["seen"], serde_json::json!([3, 1, 2]));
+
+        assert_eq!(arr.remove_duplicates(), 0);
+    }
+
+    #[test]
+    fn test_array_remove_value() {
+        let mut arr = VisualizableArray::new(8);
+        for value in [0, 1, 0, 2, 3] {
+            arr.push(value).unwrap();
+        }
+
+        let steps = arr.execute_with_steps(Operation::RemoveValue(0)).unwrap();
+        assert_eq!(arr.pretty_print(), "[1, 2, 3]");
+
+        let pointers: Vec<(u64, u64)> = steps[1..6]
+            .iter()
+            .map(|s| (s.metadata["read"].as_u64().unwrap(), s.metadata["write"].as_u64().unwrap()))
+            .collect();
+        assert_eq!(pointers, vec![(0, 0), (1, 0), (2, 1), (3, 1), (4, 2)]);
+        assert_eq!(steps[1].metadata["operation"], "skip");
+        assert_eq!(steps[2].metadata["operation"], "keep");
+        assert_eq!(steps.last().unwrap().metadata["removed"], 2);
+
+        assert_eq!(arr.remove_value(0), 0);
+        assert_eq!(arr.remove_value(2), 1);
+        assert_eq!(arr.pretty_print(), "[1, 3]");
+    }
+
+    #[test]
+    fn test_array_reverse() {
+        let mut arr = VisualizableArray::new(8);
+        for value in [1, 2, 3, 4] {
+            arr.push(value).unwrap();
+        }
+        let steps = arr.execute_with_steps(Operation::ReverseArray).unwrap();
+        assert_eq!(arr.pretty_print(), "[4, 3, 2, 1]");
+        assert_eq!(steps.iter().filter(|s| s.metadata["operation"] == "swap").count(), 2);
+        assert!(steps.iter().all(|s| s.metadata["operation"] != "middle"));
+
+        let mut arr = VisualizableArray::new(8);
+        for value in [1, 2, 3] {
+            arr.push(value).unwrap();
+        }
+        let steps = arr.execute_with_steps(Operation::ReverseArray).unwrap();
+        assert_eq!(arr.pretty_print(), "[3, 2, 1]");
+        assert_eq!(steps.iter().filter(|s| s.metadata["operation"] == "swap").count(), 1);
+        assert!(steps.iter().any(|s| s.metadata["operation"] == "middle"));
+    }
+
+    #[test]
+    fn test_array_partition_by_parity() {
+        let input = [3, 8, 5, 2, 7, 4, -6, 1];
+        let mut arr = VisualizableArray::new(8);
+        for value in input {
+            arr.push(value).unwrap();
+        }
+
+        let steps = arr.execute_with_steps(Operation::PartitionByParity).unwrap();
+        let values: Vec<i32> = arr.render_state().elements.iter().map(|e| e.value).collect();
+
+        let boundary = values.iter().position(|v| v % 2 != 0).unwrap();
+        assert!(values[boundary..].iter().all(|v| v % 2 != 0));
+        assert_eq!(steps.last().unwrap().metadata["partition_index"], boundary);
+
+        let mut sorted = values.clone();
+        sorted.sort();
+        let mut expected = input.to_vec();
+        expected.sort();
+        assert_eq!(sorted, expected);
+    }
+
+    #[test]
+    fn test_array_contains_all() {
+        let mut arr = VisualizableArray::new(8);
+        for value in [10, 20, 30] {
+            arr.push(value).unwrap();
+        }
+
+        let steps = arr.contains_all_with_steps(&[20, 99]);
+        let last = steps.last().unwrap();
+
+        assert_eq!(last.metadata["results"], serde_json::json!({ "20": 1, "99": null }));
+        assert_eq!(last.description, "Results: 20→1, 99→not found");
+        assert_eq!(last.active_indices, vec![1]);
+        assert_eq!(steps.iter().filter(|s| s.metadata["operation"] == "query").count(), 2);
+    }
+
+    #[test]
+    fn test_array_empty_placeholder() {
+        let state = VisualizableArray::new(4).render_state();
+        assert!(state.is_empty_placeholder());
+        assert!(state.connections.is_empty());
+    }
+
+    fn ids(array: &VisualizableArray) -> Vec<Option<u64>> {
+        array.render_state().elements.iter().map(|e| e.id).collect()
+    }
+
+    #[test]
+    fn test_array_ids_follow_elements_across_swap() {
+        let mut array = VisualizableArray::new(10);
+        array.push(3).unwrap();
+        array.push(1).unwrap();
+        let before = ids(&array);
+        assert_eq!(before, ids(&array));
+
+        array.execute(Operation::BubbleSort).unwrap();
+        assert_eq!(array.pretty_print(), "[1, 3]");
+        assert_eq!(ids(&array), vec![before[1], before[0]]);
+    }
+
+    #[test]
+    fn test_array_ids_survive_delete_and_dedup() {
+        let mut array = VisualizableArray::new(10);
+        for value in [5, 7, 5, 9] {
+            array.push(value).unwrap();
+        }
+        let before = ids(&array);
+
+        array.execute(Operation::Delete(1)).unwrap();
+        array.execute(Operation::RemoveDuplicates).unwrap();
+        assert_eq!(ids(&array), vec![before[0], before[3]]);
+    }
 }
@@ -0,0 +1,499 @@
+//! Educational binary tree with no ordering rule, built node by node.
+//!
+//! Unlike the BST, values are placed at explicit positions using the same
+//! `idx * 2 + 1` / `idx * 2 + 2` child indices as the tree layout, so any
+//! shape can be built for traversal demos.
+
+use crate::error::{DsavError, Result};
+use crate::state::{RenderElement, RenderState};
+use crate::instrument::traced;
+use crate::traits::{Operation, Step, Visualizable};
+
+#[derive(Debug, Clone, Default)]
+pub struct VisualizableBinaryTree {
+    /// Node values by layout index; `None` marks an absent node.
+    slots: Vec<Option<i32>>,
+}
+
+impl VisualizableBinaryTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn left(idx: usize) -> usize {
+        idx * 2 + 1
+    }
+
+    fn right(idx: usize) -> usize {
+        idx * 2 + 2
+    }
+
+    fn value_at(&self, idx: usize) -> Option<i32> {
+        self.slots.get(idx).copied().flatten()
+    }
+
+    /// Place `value` at layout index `idx`, replacing any value already there.
+    /// Every index except the root needs an existing parent.
+    pub fn insert_at_index(&mut self, idx: usize, value: i32) -> Result<()> {
+        if idx > 0 && self.value_at((idx - 1) / 2).is_none() {
+            return Err(DsavError::InvalidState {
+                reason: format!("Node {} has no parent at index {}", idx, (idx - 1) / 2),
+            });
+        }
+
+        if self.slots.len() <= idx {
+            self.slots.resize(idx + 1, None);
+        }
+        self.slots[idx] = Some(value);
+        Ok(())
+    }
+
+    /// Remove the node at `idx` together with its whole subtree.
+    pub fn remove_subtree(&mut self, idx: usize) -> Result<()> {
+        if self.value_at(idx).is_none() {
+            return Err(DsavError::IndexOutOfBounds {
+                index: idx,
+                size: self.slots.len(),
+            });
+        }
+
+        let mut pending = vec![idx];
+        while let Some(i) = pending.pop() {
+            if self.value_at(i).is_some() {
+                self.slots[i] = None;
+                pending.push(Self::left(i));
+                pending.push(Self::right(i));
+            }
+        }
+
+        // Drop trailing empty slots so the layout stays compact
+        while self.slots.last() == Some(&None) {
+            self.slots.pop();
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, idx: usize) -> Option<i32> {
+        self.value_at(idx)
+    }
+
+    pub fn size(&self) -> usize {
+        self.slots.iter().flatten().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.value_at(0).is_none()
+    }
+
+    pub fn clear(&mut self) {
+        self.slots.clear();
+    }
+
+    /// Layout indices in pre-order (root, left, right).
+    fn preorder_indices(&self, idx: usize, out: &mut Vec<usize>) {
+        if self.value_at(idx).is_some() {
+            out.push(idx);
+            self.preorder_indices(Self::left(idx), out);
+            self.preorder_indices(Self::right(idx), out);
+        }
+    }
+
+    /// Layout indices in in-order (left, root, right).
+    fn inorder_indices(&self, idx: usize, out: &mut Vec<usize>) {
+        if self.value_at(idx).is_some() {
+            self.inorder_indices(Self::left(idx), out);
+            out.push(idx);
+            self.inorder_indices(Self::right(idx), out);
+        }
+    }
+
+    /// Layout indices in post-order (left, right, root).
+    fn postorder_indices(&self, idx: usize, out: &mut Vec<usize>) {
+        if self.value_at(idx).is_some() {
+            self.postorder_indices(Self::left(idx), out);
+            self.postorder_indices(Self::right(idx), out);
+            out.push(idx);
+        }
+    }
+
+    /// Layout indices level by level, left to right.
+    fn levelorder_indices(&self) -> Vec<usize> {
+        (0..self.slots.len()).filter(|&i| self.value_at(i).is_some()).collect()
+    }
+
+    fn values(&self, indices: &[usize]) -> Vec<i32> {
+        indices.iter().filter_map(|&i| self.value_at(i)).collect()
+    }
+
+    pub fn preorder(&self) -> Vec<i32> {
+        let mut indices = Vec::new();
+        self.preorder_indices(0, &mut indices);
+        self.values(&indices)
+    }
+
+    pub fn inorder(&self) -> Vec<i32> {
+        let mut indices = Vec::new();
+        self.inorder_indices(0, &mut indices);
+        self.values(&indices)
+    }
+
+    pub fn postorder(&self) -> Vec<i32> {
+        let mut indices = Vec::new();
+        self.postorder_indices(0, &mut indices);
+        self.values(&indices)
+    }
+
+    pub fn levelorder(&self) -> Vec<i32> {
+        self.values(&self.levelorder_indices())
+    }
+
+    /// Check whether the tree is a mirror image of itself, comparing the
+    /// left and right subtrees pair by pair. Each compared pair of mirror
+    /// nodes is highlighted; an empty tree counts as symmetric.
+    pub fn is_symmetric_with_steps(&self) -> (bool, Vec<Step>) {
+        let mut steps = Vec::new();
+
+        steps.push(Step {
+            description: "Checking symmetry: the left subtree must mirror the right".to_string(),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": "is_symmetric"
+            }),
+            detail: None,
+        });
+
+        let symmetric = self.value_at(0).is_none() || self.mirrors(Self::left(0), Self::right(0), &mut steps);
+
+        steps.push(Step {
+            description: if symmetric {
+                "Tree is symmetric".to_string()
+            } else {
+                "Tree is not symmetric".to_string()
+            },
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "symmetric": symmetric
+            }),
+            detail: None,
+        });
+
+        (symmetric, steps)
+    }
+
+    /// Whether the subtree at `a` is the mirror image of the subtree at `b`:
+    /// equal values, with `a`'s left mirroring `b`'s right and vice versa.
+    fn mirrors(&self, a: usize, b: usize, steps: &mut Vec<Step>) -> bool {
+        let (left, right) = (self.value_at(a), self.value_at(b));
+        if left.is_none() && right.is_none() {
+            return true;
+        }
+
+        let matched = left == right;
+        let show = |value: Option<i32>| value.map_or("nothing".to_string(), |v| v.to_string());
+        steps.push(Step {
+            description: if matched {
+                format!("{} mirrors {}", show(left), show(right))
+            } else {
+                format!("Mismatch: {} faces {}", show(left), show(right))
+            },
+            highlight_indices: [(a, left), (b, right)]
+                .into_iter()
+                .filter(|(_, value)| value.is_some())
+                .map(|(idx, _)| idx)
+                .collect(),
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": "compare",
+                "left": left,
+                "right": right,
+                "match": matched
+            }),
+            detail: None,
+        });
+
+        matched
+            && self.mirrors(Self::left(a), Self::right(b), steps)
+            && self.mirrors(Self::right(a), Self::left(b), steps)
+    }
+
+    /// Animate visiting `indices` in order, bracketed by start and end steps.
+    fn traversal_steps(&self, name: &str, operation: &str, indices: &[usize]) -> Vec<Step> {
+        let mut steps = Vec::new();
+
+        steps.push(Step {
+            description: format!("Starting {} traversal", name),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": operation
+            }),
+            detail: None,
+        });
+
+        for &idx in indices {
+            let value = self.value_at(idx).unwrap_or_default();
+            steps.push(Step {
+                description: format!("Visiting node {}", value),
+                highlight_indices: vec![idx],
+                active_indices: vec![],
+                metadata: serde_json::json!({
+                    "value": value,
+                    "index": idx
+                }),
+                detail: None,
+            });
+        }
+
+        steps.push(Step {
+            description: format!("{} traversal complete", name),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "order": self.values(indices)
+            }),
+            detail: None,
+        });
+
+        steps
+    }
+}
+
+impl Visualizable for VisualizableBinaryTree {
+    fn execute_with_steps(&mut self, operation: Operation) -> Result<Vec<Step>> {
+        traced("binary_tree", operation, || match operation {
+            Operation::Insert(idx, value) => {
+                let mut steps = Vec::new();
+
+                steps.push(Step {
+                    description: format!("Placing {} at index {}", value, idx),
+                    highlight_indices: if idx > 0 { vec![(idx - 1) / 2] } else { vec![] },
+                    active_indices: vec![],
+                    metadata: serde_json::json!({
+                        "operation": "insert_at_index",
+                        "index": idx,
+                        "value": value
+                    }),
+                    detail: None,
+                });
+
+                self.insert_at_index(idx, value)?;
+
+                let position = match idx {
+                    0 => "the root".to_string(),
+                    _ if idx % 2 == 1 => format!("the left child of node {}", (idx - 1) / 2),
+                    _ => format!("the right child of node {}", (idx - 1) / 2),
+                };
+                steps.push(Step {
+                    description: format!("{} is now {}", value, position),
+                    highlight_indices: vec![],
+                    active_indices: vec![idx],
+                    metadata: serde_json::json!({
+                        "index": idx
+                    }),
+                    detail: None,
+                });
+
+                Ok(steps)
+            }
+
+            Operation::Delete(idx) => {
+                let mut subtree = Vec::new();
+                self.preorder_indices(idx, &mut subtree);
+
+                let mut steps = vec![Step {
+                    description: format!("Removing the subtree rooted at index {}", idx),
+                    highlight_indices: subtree.clone(),
+                    active_indices: vec![],
+                    metadata: serde_json::json!({
+                        "operation": "remove_subtree",
+                        "index": idx,
+                        "removed": self.values(&subtree)
+                    }),
+                    detail: None,
+                }];
+
+                self.remove_subtree(idx)?;
+
+                steps.push(Step {
+                    description: format!("Removed {} node(s)", subtree.len()),
+                    highlight_indices: vec![],
+                    active_indices: vec![],
+                    metadata: serde_json::json!({}),
+                    detail: None,
+                });
+
+                Ok(steps)
+            }
+
+            Operation::Traverse => {
+                let mut indices = Vec::new();
+                self.inorder_indices(0, &mut indices);
+                Ok(self.traversal_steps("In-order", "traverse", &indices))
+            }
+
+            Operation::PreOrderTraverse => {
+                let mut indices = Vec::new();
+                self.preorder_indices(0, &mut indices);
+                Ok(self.traversal_steps("Pre-order", "preorder_traverse", &indices))
+            }
+
+            Operation::PostOrderTraverse => {
+                let mut indices = Vec::new();
+                self.postorder_indices(0, &mut indices);
+                Ok(self.traversal_steps("Post-order", "postorder_traverse", &indices))
+            }
+
+            Operation::LevelOrderTraverse => {
+                let indices = self.levelorder_indices();
+                Ok(self.traversal_steps("Level-order", "levelorder_traverse", &indices))
+            }
+
+            Operation::IsSymmetric => Ok(self.is_symmetric_with_steps().1),
+
+            _ => Err(DsavError::Visualization(
+                "Operation not supported for binary trees".to_string(),
+            )),
+        })
+    }
+
+    fn execute(&mut self, operation: Operation) -> Result<()> {
+        match operation {
+            Operation::Insert(idx, value) => self.insert_at_index(idx, value),
+            Operation::Delete(idx) => self.remove_subtree(idx),
+            _ => self.execute_with_steps(operation).map(|_| ()),
+        }
+    }
+
+    fn render_state(&self) -> RenderState {
+        if self.is_empty() {
+            return RenderState::empty_placeholder("Binary tree is empty", "Insert a value at index 0 to add the root");
+        }
+
+        let elements = self
+            .slots
+            .iter()
+            .enumerate()
+            .map(|(idx, slot)| match slot {
+                Some(value) => RenderElement::new(*value)
+                    .with_label(value.to_string())
+                    .with_sublabel(format!("Node {}", idx)),
+                None => RenderElement::new(0).with_label("".to_string()),
+            })
+            .collect();
+
+        let connections = self
+            .levelorder_indices()
+            .into_iter()
+            .flat_map(|idx| [(idx, Self::left(idx)), (idx, Self::right(idx))])
+            .filter(|&(_, child)| self.value_at(child).is_some())
+            .collect();
+
+        RenderState {
+            elements,
+            connections,
+            cols: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// ```text
+    ///       1
+    ///      / \
+    ///     2   3
+    ///      \   \
+    ///       5   7
+    /// ```
+    fn sample_tree() -> VisualizableBinaryTree {
+        let mut tree = VisualizableBinaryTree::new();
+        for (idx, value) in [(0, 1), (1, 2), (2, 3), (4, 5), (6, 7)] {
+            tree.insert_at_index(idx, value).unwrap();
+        }
+        tree
+    }
+
+    #[test]
+    fn test_binary_tree_traversals() {
+        let tree = sample_tree();
+
+        assert_eq!(tree.preorder(), vec![1, 2, 5, 3, 7]);
+        assert_eq!(tree.inorder(), vec![2, 5, 1, 3, 7]);
+        assert_eq!(tree.postorder(), vec![5, 2, 7, 3, 1]);
+        assert_eq!(tree.levelorder(), vec![1, 2, 3, 5, 7]);
+    }
+
+    #[test]
+    fn test_binary_tree_traversal_steps() {
+        let mut tree = sample_tree();
+        let steps = tree.execute_with_steps(Operation::PostOrderTraverse).unwrap();
+
+        let visited: Vec<usize> = steps[1..steps.len() - 1].iter().map(|s| s.highlight_indices[0]).collect();
+        assert_eq!(visited, vec![4, 1, 6, 2, 0]);
+        assert_eq!(steps.last().unwrap().metadata["order"], serde_json::json!([5, 2, 7, 3, 1]));
+    }
+
+    #[test]
+    fn test_binary_tree_symmetric() {
+        // 1 / (2, 2) / (3, 4, 4, 3)
+        let mut tree = VisualizableBinaryTree::new();
+        for (idx, value) in [(0, 1), (1, 2), (2, 2), (3, 3), (4, 4), (5, 4), (6, 3)] {
+            tree.insert_at_index(idx, value).unwrap();
+        }
+
+        let (symmetric, steps) = tree.is_symmetric_with_steps();
+        assert!(symmetric);
+        let pairs: Vec<Vec<usize>> = steps[1..steps.len() - 1].iter().map(|s| s.highlight_indices.clone()).collect();
+        assert_eq!(pairs, vec![vec![1, 2], vec![3, 6], vec![4, 5]]);
+
+        assert!(VisualizableBinaryTree::new().is_symmetric_with_steps().0);
+    }
+
+    #[test]
+    fn test_binary_tree_asymmetric() {
+        let mut tree = sample_tree();
+        assert!(!tree.is_symmetric_with_steps().0);
+
+        // The root's children 2 and 3 already differ
+        let steps = tree.execute_with_steps(Operation::IsSymmetric).unwrap();
+        assert_eq!(steps.last().unwrap().metadata["symmetric"], false);
+        assert_eq!(steps[1].metadata["match"], false);
+    }
+
+    #[test]
+    fn test_binary_tree_requires_parent() {
+        let mut tree = VisualizableBinaryTree::new();
+        assert!(tree.insert_at_index(1, 2).is_err());
+
+        tree.insert_at_index(0, 1).unwrap();
+        assert!(tree.insert_at_index(3, 4).is_err());
+        assert!(tree.insert_at_index(1, 2).is_ok());
+    }
+
+    #[test]
+    fn test_binary_tree_remove_subtree() {
+        let mut tree = sample_tree();
+        tree.execute_with_steps(Operation::Delete(2)).unwrap();
+
+        assert_eq!(tree.preorder(), vec![1, 2, 5]);
+        assert_eq!(tree.size(), 3);
+        assert!(tree.remove_subtree(2).is_err());
+    }
+
+    #[test]
+    fn test_binary_tree_render_state() {
+        let tree = sample_tree();
+        let state = tree.render_state();
+
+        assert_eq!(state.elements.len(), 7);
+        assert_eq!(state.elements[4].label, "5");
+        assert_eq!(state.connections, vec![(0, 1), (0, 2), (1, 4), (2, 6)]);
+        assert!(state.validate().is_ok());
+
+        assert!(VisualizableBinaryTree::new().render_state().is_empty_placeholder());
+    }
+}
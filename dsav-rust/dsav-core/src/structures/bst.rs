@@ -5,7 +5,9 @@
 
 use crate::error::{DsavError, Result};
 use crate::state::{RenderElement, RenderState};
+use crate::instrument::traced;
 use crate::traits::{Operation, Step, Visualizable};
+use std::cell::RefCell;
 
 #[derive(Debug, Clone)]
 struct Node {
@@ -24,10 +26,18 @@ impl Node {
     }
 }
 
+/// Default weight-balance threshold for [`Operation::InsertBalanced`].
+const DEFAULT_BALANCE_ALPHA: f64 = 0.7;
+
 #[derive(Debug, Clone)]
 pub struct VisualizableBST {
     root: Option<Box<Node>>,
     size: usize,
+    /// A child subtree may hold at most this fraction of its parent's nodes
+    /// before a balanced insert rebuilds the parent.
+    balance_alpha: f64,
+    /// Last computed render state, cleared whenever the tree changes.
+    render_cache: RefCell<Option<RenderState>>,
 }
 
 impl VisualizableBST {
@@ -35,10 +45,14 @@ impl VisualizableBST {
         Self {
             root: None,
             size: 0,
+            balance_alpha: DEFAULT_BALANCE_ALPHA,
+            render_cache: RefCell::new(None),
         }
     }
 
     pub fn insert(&mut self, value: i32) {
+        self.invalidate_render_cache();
+
         if self.root.is_none() {
             self.root = Some(Box::new(Node::new(value)));
             self.size += 1;
@@ -50,6 +64,18 @@ impl VisualizableBST {
         }
     }
 
+    /// Insert a value, returning whether it was added and the render index
+    /// (`idx*2+1` / `idx*2+2` layout) it landed at. A duplicate returns
+    /// `(false, None)`.
+    pub fn insert_returning_index(&mut self, value: i32) -> (bool, Option<usize>) {
+        let before = self.size;
+        self.insert(value);
+        if self.size == before {
+            return (false, None);
+        }
+        (true, self.find_node_index(value))
+    }
+
     fn insert_recursive(node: &mut Option<Box<Node>>, value: i32) -> bool {
         if let Some(n) = node {
             if value < n.value {
@@ -76,6 +102,8 @@ impl VisualizableBST {
     }
 
     pub fn delete(&mut self, value: i32) -> bool {
+        self.invalidate_render_cache();
+
         let (new_root, deleted) = Self::delete_recursive(self.root.take(), value);
         self.root = new_root;
         if deleted {
@@ -171,15 +199,1294 @@ impl VisualizableBST {
     pub fn clear(&mut self) {
         self.root = None;
         self.size = 0;
+        self.invalidate_render_cache();
+    }
+
+    /// Whether every node respects BST ordering (left < node < right).
+    pub fn is_valid_bst(&self) -> bool {
+        Self::is_valid_recursive(&self.root, None, None)
+    }
+
+    fn is_valid_recursive(node: &Option<Box<Node>>, min: Option<i32>, max: Option<i32>) -> bool {
+        match node {
+            None => true,
+            Some(n) => {
+                min.is_none_or(|min| n.value > min)
+                    && max.is_none_or(|max| n.value < max)
+                    && Self::is_valid_recursive(&n.left, min, Some(n.value))
+                    && Self::is_valid_recursive(&n.right, Some(n.value), max)
+            }
+        }
+    }
+
+    /// Mirror the tree by swapping the children of every node.
+    ///
+    /// The result is still a binary tree but no longer a search tree: values
+    /// now decrease from left to right, so `is_valid_bst` returns false and
+    /// insert/search will not find the right paths until it is mirrored back.
+    pub fn mirror_with_steps(&mut self) -> Vec<Step> {
+        self.invalidate_render_cache();
+
+        let mut steps = Vec::new();
+
+        steps.push(Step {
+            description: "Mirroring tree by swapping every node's children".to_string(),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": "mirror"
+            }),
+            detail: None,
+        });
+
+        Self::mirror_recursive(&mut self.root, 0, &mut steps);
+
+        steps.push(Step {
+            description: "Mirror complete (the tree is no longer a valid BST)".to_string(),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({}),
+            detail: None,
+        });
+
+        steps
+    }
+
+    fn mirror_recursive(node: &mut Option<Box<Node>>, idx: usize, steps: &mut Vec<Step>) {
+        if let Some(n) = node {
+            if n.left.is_some() || n.right.is_some() {
+                let mut left_subtree = Vec::new();
+                let mut right_subtree = Vec::new();
+                Self::subtree_indices(&n.left, idx * 2 + 1, &mut left_subtree);
+                Self::subtree_indices(&n.right, idx * 2 + 2, &mut right_subtree);
+
+                steps.push(Step {
+                    description: format!("Swapping the left and right subtrees of {}", n.value),
+                    highlight_indices: vec![idx],
+                    active_indices: [left_subtree.clone(), right_subtree.clone()].concat(),
+                    metadata: serde_json::json!({
+                        "operation": "swap_children",
+                        "value": n.value,
+                        "index": idx,
+                        "left_subtree": left_subtree,
+                        "right_subtree": right_subtree
+                    }),
+                    detail: None,
+                });
+
+                std::mem::swap(&mut n.left, &mut n.right);
+            }
+
+            Self::mirror_recursive(&mut n.left, idx * 2 + 1, steps);
+            Self::mirror_recursive(&mut n.right, idx * 2 + 2, steps);
+        }
+    }
+
+    fn subtree_indices(node: &Option<Box<Node>>, idx: usize, indices: &mut Vec<usize>) {
+        if let Some(n) = node {
+            indices.push(idx);
+            Self::subtree_indices(&n.left, idx * 2 + 1, indices);
+            Self::subtree_indices(&n.right, idx * 2 + 2, indices);
+        }
+    }
+
+    /// Check whether every node's subtrees differ in height by at most one.
+    ///
+    /// Heights are computed bottom-up, annotating each node with its balance
+    /// factor (left height minus right height). The first node found out of
+    /// balance is highlighted in the final step.
+    pub fn is_balanced_with_steps(&self) -> (bool, Vec<Step>) {
+        let mut steps = Vec::new();
+        let mut unbalanced = None;
+
+        steps.push(Step {
+            description: "Checking balance: computing subtree heights bottom-up".to_string(),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": "is_balanced"
+            }),
+            detail: None,
+        });
+
+        Self::balance_recursive(&self.root, 0, &mut steps, &mut unbalanced);
+
+        let balanced = unbalanced.is_none();
+        let final_step = match unbalanced {
+            Some((idx, value, factor)) => Step {
+                description: format!("Tree is unbalanced: node {} has balance factor {}", value, factor),
+                highlight_indices: vec![idx],
+                active_indices: vec![],
+                metadata: serde_json::json!({
+                    "balanced": false,
+                    "index": idx,
+                    "value": value,
+                    "balance_factor": factor
+                }),
+                detail: None,
+            },
+            None => Step {
+                description: "Tree is balanced".to_string(),
+                highlight_indices: vec![],
+                active_indices: vec![],
+                metadata: serde_json::json!({
+                    "balanced": true
+                }),
+                detail: None,
+            },
+        };
+        steps.push(final_step);
+
+        (balanced, steps)
+    }
+
+    /// Returns the subtree height, recording the first (index, value, factor) out of balance.
+    fn balance_recursive(
+        node: &Option<Box<Node>>,
+        idx: usize,
+        steps: &mut Vec<Step>,
+        unbalanced: &mut Option<(usize, i32, i64)>,
+    ) -> i64 {
+        let Some(n) = node else {
+            return 0;
+        };
+
+        let left_height = Self::balance_recursive(&n.left, idx * 2 + 1, steps, unbalanced);
+        let right_height = Self::balance_recursive(&n.right, idx * 2 + 2, steps, unbalanced);
+        let factor = left_height - right_height;
+
+        steps.push(Step {
+            description: format!(
+                "Node {}: left height {}, right height {}, balance factor {}",
+                n.value, left_height, right_height, factor
+            ),
+            highlight_indices: vec![idx],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": "balance_factor",
+                "value": n.value,
+                "index": idx,
+                "left_height": left_height,
+                "right_height": right_height,
+                "balance_factor": factor
+            }),
+            detail: None,
+        });
+
+        if factor.abs() > 1 && unbalanced.is_none() {
+            *unbalanced = Some((idx, n.value, factor));
+        }
+
+        1 + left_height.max(right_height)
+    }
+
+    /// Number of nodes on each level, root first. Skewed trees have long
+    /// profiles of ones; balanced trees roughly double per level.
+    pub fn level_widths(&self) -> Vec<usize> {
+        self.level_indices().iter().map(Vec::len).collect()
+    }
+
+    /// Number of nodes in the subtree rooted at render index `index`, or
+    /// `None` if no node sits there.
+    pub fn subtree_size(&self, index: usize) -> Option<usize> {
+        self.node_at_index(index).map(|node| Self::count(Some(node)))
+    }
+
+    fn count(node: Option<&Node>) -> usize {
+        node.map_or(0, |n| 1 + Self::count(n.left.as_deref()) + Self::count(n.right.as_deref()))
+    }
+
+    /// How many values in the tree are less than `x`.
+    pub fn rank(&self, x: i32) -> usize {
+        self.rank_with_steps(x).0
+    }
+
+    /// The `k`-th smallest value (zero-based), or `None` if `k >= size()`.
+    pub fn select(&self, k: usize) -> Option<i32> {
+        self.select_with_steps(k).0
+    }
+
+    /// The value nearest to `target`, preferring the smaller on a tie, or
+    /// `None` for an empty tree.
+    pub fn closest(&self, target: i32) -> Option<i32> {
+        self.closest_with_steps(target).0
+    }
+
+    /// Descend as if searching for `target`, keeping the nearest value seen.
+    /// Only nodes on the search path can be nearest: any other subtree lies
+    /// entirely beyond a node already compared.
+    fn closest_with_steps(&self, target: i32) -> (Option<i32>, Vec<Step>) {
+        let mut steps = Vec::new();
+
+        steps.push(Step {
+            description: format!("Finding the value closest to {}", target),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": "find_closest",
+                "target": target
+            }),
+            detail: None,
+        });
+
+        // (value, render index, distance to target)
+        let mut best: Option<(i32, usize, u64)> = None;
+        let mut current = self.root.as_deref();
+        let mut idx = 0;
+
+        while let Some(node) = current {
+            let difference = (node.value as i64 - target as i64).unsigned_abs();
+            let improves = best.is_none_or(|(value, _, best_difference)| {
+                difference < best_difference || (difference == best_difference && node.value < value)
+            });
+            if improves {
+                best = Some((node.value, idx, difference));
+            }
+
+            let (best_value, best_idx, best_difference) = best.unwrap_or((node.value, idx, difference));
+            steps.push(Step {
+                description: if improves {
+                    format!("{} is {} away, the closest so far", node.value, difference)
+                } else {
+                    format!("{} is {} away; {} stays the closest", node.value, difference, best_value)
+                },
+                highlight_indices: vec![idx],
+                active_indices: vec![best_idx],
+                metadata: serde_json::json!({
+                    "operation": "compare",
+                    "index": idx,
+                    "value": node.value,
+                    "best": best_value,
+                    "difference": best_difference
+                }),
+                detail: None,
+            });
+
+            if target == node.value {
+                break;
+            } else if target < node.value {
+                current = node.left.as_deref();
+                idx = idx * 2 + 1;
+            } else {
+                current = node.right.as_deref();
+                idx = idx * 2 + 2;
+            }
+        }
+
+        let Some((value, best_idx, difference)) = best else {
+            steps.push(Step {
+                description: "Tree is empty, nothing is closest".to_string(),
+                highlight_indices: vec![],
+                active_indices: vec![],
+                metadata: serde_json::json!({
+                    "closest": null
+                }),
+                detail: None,
+            });
+            return (None, steps);
+        };
+
+        steps.push(Step {
+            description: format!("Closest to {} is {} (difference {})", target, value, difference),
+            highlight_indices: vec![],
+            active_indices: vec![best_idx],
+            metadata: serde_json::json!({
+                "closest": value,
+                "difference": difference
+            }),
+            detail: None,
+        });
+
+        (Some(value), steps)
+    }
+
+    /// Count the values less than `x` by descending from the root. Going
+    /// right past a node adds that node and its whole left subtree.
+    fn rank_with_steps(&self, x: i32) -> (usize, Vec<Step>) {
+        let mut steps = Vec::new();
+
+        steps.push(Step {
+            description: format!("Counting values less than {}", x),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": "rank",
+                "target": x
+            }),
+            detail: None,
+        });
+
+        let mut rank = 0;
+        let mut current = self.root.as_deref();
+        let mut idx = 0;
+        let mut path = Vec::new();
+
+        while let Some(node) = current {
+            path.push(idx);
+            let left_size = Self::count(node.left.as_deref());
+
+            let description = if x <= node.value {
+                format!("{} <= {}, going left (rank stays {})", x, node.value, rank)
+            } else {
+                rank += left_size + 1;
+                format!(
+                    "{} > {}, counting it and its {} left descendant(s): rank is now {}",
+                    x, node.value, left_size, rank
+                )
+            };
+
+            steps.push(Step {
+                description,
+                highlight_indices: vec![idx],
+                active_indices: vec![],
+                metadata: serde_json::json!({
+                    "operation": "compare",
+                    "index": idx,
+                    "left_size": left_size,
+                    "rank": rank
+                }),
+                detail: None,
+            });
+
+            if x <= node.value {
+                current = node.left.as_deref();
+                idx = idx * 2 + 1;
+            } else {
+                current = node.right.as_deref();
+                idx = idx * 2 + 2;
+            }
+        }
+
+        steps.push(Step {
+            description: format!("{} value(s) are less than {}", rank, x),
+            highlight_indices: path.clone(),
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "rank": rank,
+                "path": path
+            }),
+            detail: None,
+        });
+
+        (rank, steps)
+    }
+
+    /// Find the `k`-th smallest value by comparing `k` with left subtree
+    /// sizes on the way down.
+    fn select_with_steps(&self, k: usize) -> (Option<i32>, Vec<Step>) {
+        let mut steps = Vec::new();
+
+        steps.push(Step {
+            description: format!("Selecting the value at rank {}", k),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": "select",
+                "k": k
+            }),
+            detail: None,
+        });
+
+        let mut remaining = k;
+        let mut current = self.root.as_deref();
+        let mut idx = 0;
+
+        while let Some(node) = current {
+            let left_size = Self::count(node.left.as_deref());
+
+            let (description, next) = match remaining.cmp(&left_size) {
+                std::cmp::Ordering::Less => (
+                    format!("{} < left size {}, going left", remaining, left_size),
+                    Some((node.left.as_deref(), idx * 2 + 1)),
+                ),
+                std::cmp::Ordering::Equal => (format!("{} == left size {}, found {}", remaining, left_size, node.value), None),
+                std::cmp::Ordering::Greater => (
+                    format!(
+                        "{} > left size {}, skipping {} value(s) and going right",
+                        remaining,
+                        left_size,
+                        left_size + 1
+                    ),
+                    Some((node.right.as_deref(), idx * 2 + 2)),
+                ),
+            };
+
+            steps.push(Step {
+                description,
+                highlight_indices: vec![idx],
+                active_indices: vec![],
+                metadata: serde_json::json!({
+                    "operation": "compare",
+                    "index": idx,
+                    "k": remaining,
+                    "left_size": left_size
+                }),
+                detail: None,
+            });
+
+            match next {
+                Some((child, child_idx)) => {
+                    if remaining > left_size {
+                        remaining -= left_size + 1;
+                    }
+                    current = child;
+                    idx = child_idx;
+                }
+                None => {
+                    steps.push(Step {
+                        description: format!("Value at rank {} is {}", k, node.value),
+                        highlight_indices: vec![],
+                        active_indices: vec![idx],
+                        metadata: serde_json::json!({
+                            "found": true,
+                            "value": node.value,
+                            "index": idx
+                        }),
+                        detail: None,
+                    });
+                    return (Some(node.value), steps);
+                }
+            }
+        }
+
+        steps.push(Step {
+            description: format!("Rank {} is out of range for {} value(s)", k, self.size),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "found": false
+            }),
+            detail: None,
+        });
+
+        (None, steps)
+    }
+
+    /// Walk from the node holding `value` up to the root. Nodes have no
+    /// parent pointers, so the path is first rebuilt by searching from the
+    /// root, then replayed bottom-up. The last step's `ancestors` metadata
+    /// lists them from the parent up to the root.
+    fn path_to_root_with_steps(&self, value: i32) -> Result<Vec<Step>> {
+        let mut path = self.search_path(value);
+        if path.last().map(|&(found, _)| found) != Some(value) {
+            return Err(DsavError::NotFound { value });
+        }
+
+        let mut steps = Vec::new();
+        steps.push(Step {
+            description: format!(
+                "No parent pointers: searching from the root rebuilds the {} node(s) on the path to {}",
+                path.len(),
+                value
+            ),
+            highlight_indices: path.iter().map(|&(_, idx)| idx).collect(),
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": "path_to_root",
+                "value": value,
+                "path": [value]
+            }),
+            detail: None,
+        });
+
+        path.reverse();
+        let mut visited = vec![path[0].1];
+        for (i, &(ancestor, idx)) in path.iter().enumerate().skip(1) {
+            let walked: Vec<i32> = path[..=i].iter().map(|&(value, _)| value).collect();
+            steps.push(Step {
+                description: format!("Parent of {} is {}", path[i - 1].0, ancestor),
+                highlight_indices: vec![idx],
+                active_indices: visited.clone(),
+                metadata: serde_json::json!({
+                    "operation": "ascend",
+                    "value": ancestor,
+                    "path": walked
+                }),
+                detail: None,
+            });
+            visited.push(idx);
+        }
+
+        let values: Vec<i32> = path.iter().map(|&(value, _)| value).collect();
+        steps.push(Step {
+            description: format!(
+                "Reached the root: {}",
+                values.iter().map(i32::to_string).collect::<Vec<_>>().join(" → ")
+            ),
+            highlight_indices: vec![],
+            active_indices: visited,
+            metadata: serde_json::json!({
+                "path": values,
+                "ancestors": values[1..]
+            }),
+            detail: None,
+        });
+
+        Ok(steps)
+    }
+
+    /// The in-order predecessor and successor of `value`, which must be in
+    /// the tree. Either is `None` at the ends of the order.
+    pub fn neighbors(&self, value: i32) -> Result<(Option<i32>, Option<i32>)> {
+        if !self.search(value) {
+            return Err(DsavError::NotFound { value });
+        }
+        Ok((
+            self.neighbor_search(value, true, &mut Vec::new()).map(|(v, _)| v),
+            self.neighbor_search(value, false, &mut Vec::new()).map(|(v, _)| v),
+        ))
+    }
+
+    /// Descend from the root looking for the largest value below `value`
+    /// (`below`) or the smallest above it. Every node passed on the correct
+    /// side becomes the new candidate, so the last one kept is the neighbor;
+    /// this also covers the case where it is the extreme of a child subtree.
+    fn neighbor_search(&self, value: i32, below: bool, steps: &mut Vec<Step>) -> Option<(i32, usize)> {
+        let phase = if below { "predecessor" } else { "successor" };
+        let mut candidate: Option<(i32, usize)> = None;
+        let mut path = Vec::new();
+        let mut current = self.root.as_deref();
+        let mut idx = 0;
+
+        while let Some(node) = current {
+            path.push(node.value);
+            let is_candidate = if below { node.value < value } else { node.value > value };
+            if is_candidate {
+                candidate = Some((node.value, idx));
+            }
+
+            // Candidates below the value send the search right, towards
+            // larger ones; everything else sends it left (and mirrored).
+            let go_right = if below { is_candidate } else { node.value <= value };
+            let description = match (is_candidate, go_right) {
+                (true, true) => format!("{} < {}: {} candidate, look right for a larger one", node.value, value, phase),
+                (true, false) => format!("{} > {}: {} candidate, look left for a smaller one", node.value, value, phase),
+                (false, true) => format!("{} ≤ {}: go right", node.value, value),
+                (false, false) => format!("{} ≥ {}: go left", node.value, value),
+            };
+
+            steps.push(Step {
+                description,
+                highlight_indices: vec![idx],
+                active_indices: candidate.map(|(_, idx)| idx).into_iter().collect(),
+                metadata: serde_json::json!({
+                    "operation": "compare",
+                    "phase": phase,
+                    "value": node.value,
+                    "candidate": candidate.map(|(value, _)| value),
+                    "path": path
+                }),
+                detail: None,
+            });
+
+            if go_right {
+                current = node.right.as_deref();
+                idx = idx * 2 + 2;
+            } else {
+                current = node.left.as_deref();
+                idx = idx * 2 + 1;
+            }
+        }
+
+        steps.push(Step {
+            description: match candidate {
+                Some((neighbor, _)) => format!("In-order {} of {} is {}", phase, value, neighbor),
+                None => format!("{} has no in-order {}", value, phase),
+            },
+            highlight_indices: vec![],
+            active_indices: candidate.map(|(_, idx)| idx).into_iter().collect(),
+            metadata: serde_json::json!({
+                "phase": phase,
+                phase: candidate.map(|(value, _)| value),
+                "path": path
+            }),
+            detail: None,
+        });
+
+        candidate
+    }
+
+    /// Run both neighbor searches one after the other, then show the
+    /// predecessor and successor together around `value`.
+    fn neighbors_with_steps(&self, value: i32) -> Result<Vec<Step>> {
+        let target_idx = self.find_node_index(value).ok_or(DsavError::NotFound { value })?;
+
+        let mut steps = vec![Step {
+            description: format!("Finding the in-order neighbors of {}", value),
+            highlight_indices: vec![],
+            active_indices: vec![target_idx],
+            metadata: serde_json::json!({
+                "operation": "predecessor_successor",
+                "value": value
+            }),
+            detail: None,
+        }];
+
+        let predecessor = self.neighbor_search(value, true, &mut steps);
+        let successor = self.neighbor_search(value, false, &mut steps);
+
+        let show = |neighbor: Option<(i32, usize)>| {
+            neighbor.map_or_else(|| "none".to_string(), |(value, _)| value.to_string())
+        };
+        steps.push(Step {
+            description: format!("{} ← {} → {}", show(predecessor), value, show(successor)),
+            highlight_indices: predecessor.into_iter().chain(successor).map(|(_, idx)| idx).collect(),
+            active_indices: vec![target_idx],
+            metadata: serde_json::json!({
+                "value": value,
+                "predecessor": predecessor.map(|(value, _)| value),
+                "successor": successor.map(|(value, _)| value)
+            }),
+            detail: None,
+        });
+
+        Ok(steps)
+    }
+
+    /// Every value in `[lo, hi]`, in sorted order.
+    pub fn range_query(&self, lo: i32, hi: i32) -> Vec<i32> {
+        let mut matches = Vec::new();
+        Self::range_recursive(&self.root, 0, lo, hi, &mut matches, None);
+        matches.into_iter().map(|(value, _)| value).collect()
+    }
+
+    /// Collect the values in `[lo, hi]` with an in-order walk that skips
+    /// whole subtrees: a node at or below `lo` has nothing in range to its
+    /// left, and one at or above `hi` nothing to its right. Skipped subtrees
+    /// get a `prune` step listing their indices in `pruned`.
+    fn range_query_with_steps(&self, lo: i32, hi: i32) -> Result<Vec<Step>> {
+        if lo > hi {
+            return Err(DsavError::InvalidState {
+                reason: format!("Range [{}, {}] is empty: low is above high", lo, hi),
+            });
+        }
+
+        let mut steps = vec![Step {
+            description: format!("Finding every value in [{}, {}]", lo, hi),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": "range_query",
+                "low": lo,
+                "high": hi
+            }),
+            detail: None,
+        }];
+
+        let mut matches = Vec::new();
+        Self::range_recursive(&self.root, 0, lo, hi, &mut matches, Some(&mut steps));
+
+        let values: Vec<i32> = matches.iter().map(|&(value, _)| value).collect();
+        steps.push(Step {
+            description: format!("{} value(s) in [{}, {}]: {:?}", values.len(), lo, hi, values),
+            highlight_indices: vec![],
+            active_indices: matches.iter().map(|&(_, idx)| idx).collect(),
+            metadata: serde_json::json!({
+                "matches": values,
+                "count": values.len()
+            }),
+            detail: None,
+        });
+
+        Ok(steps)
+    }
+
+    /// Push `(value, render index)` for each match, recording steps only
+    /// when `steps` is given.
+    fn range_recursive(
+        node: &Option<Box<Node>>,
+        idx: usize,
+        lo: i32,
+        hi: i32,
+        matches: &mut Vec<(i32, usize)>,
+        mut steps: Option<&mut Vec<Step>>,
+    ) {
+        let Some(n) = node else {
+            return;
+        };
+
+        if n.value > lo {
+            Self::range_recursive(&n.left, idx * 2 + 1, lo, hi, matches, steps.as_deref_mut());
+        } else if let Some(steps) = steps.as_deref_mut() {
+            Self::push_prune_step(n, idx * 2 + 1, format!("all below {} ≤ {}", n.value, lo), matches, steps);
+        }
+
+        let in_range = lo <= n.value && n.value <= hi;
+        if in_range {
+            matches.push((n.value, idx));
+        }
+        if let Some(steps) = steps.as_deref_mut() {
+            steps.push(Step {
+                description: if in_range {
+                    format!("{} is in [{}, {}]: match", n.value, lo, hi)
+                } else {
+                    format!("{} is outside [{}, {}]", n.value, lo, hi)
+                },
+                highlight_indices: vec![idx],
+                active_indices: matches.iter().map(|&(_, idx)| idx).collect(),
+                metadata: serde_json::json!({
+                    "operation": "visit",
+                    "value": n.value,
+                    "in_range": in_range
+                }),
+                detail: None,
+            });
+        }
+
+        if n.value < hi {
+            Self::range_recursive(&n.right, idx * 2 + 2, lo, hi, matches, steps);
+        } else if let Some(steps) = steps {
+            Self::push_prune_step(n, idx * 2 + 2, format!("all above {} ≥ {}", n.value, hi), matches, steps);
+        }
+    }
+
+    /// Record skipping the child of `parent` at render index `child_idx`, if
+    /// there is one.
+    fn push_prune_step(parent: &Node, child_idx: usize, reason: String, matches: &[(i32, usize)], steps: &mut Vec<Step>) {
+        let (child, side) = if child_idx % 2 == 1 { (&parent.left, "left") } else { (&parent.right, "right") };
+        if child.is_none() {
+            return;
+        }
+
+        let mut pruned = Vec::new();
+        Self::subtree_indices(child, child_idx, &mut pruned);
+        steps.push(Step {
+            description: format!("Pruned the {} subtree of {} ({} node(s)): {}", side, parent.value, pruned.len(), reason),
+            highlight_indices: pruned.clone(),
+            active_indices: matches.iter().map(|&(_, idx)| idx).collect(),
+            metadata: serde_json::json!({
+                "operation": "prune",
+                "value": parent.value,
+                "side": side,
+                "pruned": pruned
+            }),
+            detail: None,
+        });
+    }
+
+    /// Node at render index `index`, found by walking down from its parent.
+    fn node_at_index(&self, index: usize) -> Option<&Node> {
+        if index == 0 {
+            return self.root.as_deref();
+        }
+
+        let parent = self.node_at_index((index - 1) / 2)?;
+        if index % 2 == 1 {
+            parent.left.as_deref()
+        } else {
+            parent.right.as_deref()
+        }
+    }
+
+    /// Render indices of the nodes on each level, gathered breadth-first.
+    fn level_indices(&self) -> Vec<Vec<usize>> {
+        self.level_nodes()
+            .into_iter()
+            .map(|level| level.into_iter().map(|(_, idx)| idx).collect())
+            .collect()
+    }
+
+    /// `(value, render index)` of the nodes on each level, left to right.
+    fn level_nodes(&self) -> Vec<Vec<(i32, usize)>> {
+        let mut levels = Vec::new();
+        let mut current: Vec<(&Node, usize)> = self.root.as_deref().map(|n| (n, 0)).into_iter().collect();
+
+        while !current.is_empty() {
+            levels.push(current.iter().map(|&(node, idx)| (node.value, idx)).collect());
+
+            current = current
+                .iter()
+                .flat_map(|&(node, idx)| {
+                    let left = node.left.as_deref().map(|n| (n, idx * 2 + 1));
+                    let right = node.right.as_deref().map(|n| (n, idx * 2 + 2));
+                    left.into_iter().chain(right)
+                })
+                .collect();
+        }
+
+        levels
+    }
+
+    fn level_widths_with_steps(&self) -> Vec<Step> {
+        let mut steps = Vec::new();
+        let mut widths = Vec::new();
+
+        steps.push(Step {
+            description: "Counting nodes per level with a breadth-first walk".to_string(),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": "level_widths"
+            }),
+            detail: None,
+        });
+
+        for (level, indices) in self.level_indices().into_iter().enumerate() {
+            widths.push(indices.len());
+
+            steps.push(Step {
+                description: format!("Level {} has {} node(s)", level, indices.len()),
+                highlight_indices: indices,
+                active_indices: vec![],
+                metadata: serde_json::json!({
+                    "level": level,
+                    "width": widths[level],
+                    "widths": widths
+                }),
+                detail: None,
+            });
+        }
+
+        // First level with the maximum width
+        let widest = widths
+            .iter()
+            .enumerate()
+            .fold(None, |best: Option<(usize, usize)>, (level, &width)| match best {
+                Some((_, best_width)) if best_width >= width => best,
+                _ => Some((level, width)),
+            });
+
+        steps.push(Step {
+            description: match widest {
+                Some((level, width)) => format!("Level widths {:?}; widest is level {} with {} node(s)", widths, level, width),
+                None => "Tree is empty".to_string(),
+            },
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "widths": widths,
+                "widest_level": widest.map(|(level, _)| level),
+                "max_width": widest.map(|(_, width)| width)
+            }),
+            detail: None,
+        });
+
+        steps
+    }
+
+    /// Breadth-first traversal that alternates direction on every level:
+    /// the root level left to right, the next right to left, and so on.
+    fn zigzag_with_steps(&self) -> Vec<Step> {
+        let mut steps = Vec::new();
+        let mut order = Vec::new();
+
+        steps.push(Step {
+            description: "Starting zigzag level-order traversal".to_string(),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": "zigzag_traverse"
+            }),
+            detail: None,
+        });
+
+        for (level, mut nodes) in self.level_nodes().into_iter().enumerate() {
+            let direction = if level % 2 == 0 { "left_to_right" } else { "right_to_left" };
+            if level % 2 == 1 {
+                nodes.reverse();
+            }
+
+            steps.push(Step {
+                description: format!(
+                    "Level {}: reading {}",
+                    level,
+                    if level % 2 == 0 { "left to right" } else { "right to left" }
+                ),
+                highlight_indices: vec![],
+                active_indices: nodes.iter().map(|&(_, idx)| idx).collect(),
+                metadata: serde_json::json!({
+                    "level": level,
+                    "direction": direction
+                }),
+                detail: None,
+            });
+
+            for (value, idx) in nodes {
+                order.push(value);
+
+                steps.push(Step {
+                    description: format!("Visiting node {}", value),
+                    highlight_indices: vec![idx],
+                    active_indices: vec![],
+                    metadata: serde_json::json!({
+                        "value": value,
+                        "index": idx,
+                        "level": level,
+                        "direction": direction
+                    }),
+                    detail: None,
+                });
+            }
+        }
+
+        steps.push(Step {
+            description: format!("Zigzag traversal complete: {:?}", order),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "order": order
+            }),
+            detail: None,
+        });
+
+        steps
+    }
+
+    /// Merge `other` into this tree in three phases: flatten both trees to
+    /// sorted lists, merge the lists, then rebuild a balanced tree from the
+    /// result. Values present in both trees are kept once.
+    pub fn merge_with_steps(&mut self, other: &VisualizableBST) -> Vec<Step> {
+        self.invalidate_render_cache();
+
+        let mut steps = Vec::new();
+        let ours: Vec<i32> = self.iter().collect();
+        let theirs: Vec<i32> = other.iter().collect();
+
+        for (tree, values) in [("this", &ours), ("other", &theirs)] {
+            steps.push(Step {
+                description: format!("Flattening {} tree in order: {:?}", tree, values),
+                highlight_indices: vec![],
+                active_indices: vec![],
+                metadata: serde_json::json!({
+                    "operation": "flatten",
+                    "phase": "flatten",
+                    "tree": tree,
+                    "values": values
+                }),
+                detail: None,
+            });
+        }
+
+        let mut merged = Vec::with_capacity(ours.len() + theirs.len());
+        let (mut i, mut j) = (0, 0);
+        while i < ours.len() || j < theirs.len() {
+            let (value, description) = match (ours.get(i), theirs.get(j)) {
+                (Some(&a), Some(&b)) if a == b => {
+                    i += 1;
+                    j += 1;
+                    (a, format!("{} is in both trees, keeping it once", a))
+                }
+                (Some(&a), Some(&b)) if a < b => {
+                    i += 1;
+                    (a, format!("{} < {}, taking {} from this tree", a, b, a))
+                }
+                (Some(&a), Some(&b)) => {
+                    j += 1;
+                    (b, format!("{} < {}, taking {} from the other tree", b, a, b))
+                }
+                (Some(&a), None) => {
+                    i += 1;
+                    (a, format!("Taking remaining {} from this tree", a))
+                }
+                (None, Some(&b)) => {
+                    j += 1;
+                    (b, format!("Taking remaining {} from the other tree", b))
+                }
+                (None, None) => unreachable!(),
+            };
+            merged.push(value);
+
+            steps.push(Step {
+                description,
+                highlight_indices: vec![],
+                active_indices: vec![],
+                metadata: serde_json::json!({
+                    "operation": "merge",
+                    "phase": "merge",
+                    "value": value,
+                    "merged": merged
+                }),
+                detail: None,
+            });
+        }
+
+        let mut placed = Vec::new();
+        self.root = Self::build_balanced(&merged, 0, &mut placed);
+        self.size = merged.len();
+
+        let mut indices = Vec::new();
+        for (value, idx) in placed {
+            steps.push(Step {
+                description: format!("Placing {} at index {}", value, idx),
+                highlight_indices: vec![idx],
+                active_indices: indices.clone(),
+                metadata: serde_json::json!({
+                    "operation": "place",
+                    "phase": "rebuild",
+                    "value": value,
+                    "index": idx
+                }),
+                detail: None,
+            });
+            indices.push(idx);
+        }
+
+        steps.push(Step {
+            description: format!("Merged tree holds {} values", self.size),
+            highlight_indices: vec![],
+            active_indices: indices,
+            metadata: serde_json::json!({
+                "values": merged
+            }),
+            detail: None,
+        });
+
+        steps
+    }
+
+    pub fn balance_alpha(&self) -> f64 {
+        self.balance_alpha
+    }
+
+    /// Set the threshold used by balanced inserts. Must lie strictly between
+    /// 0.5 (rebuild almost always, perfectly balanced) and 1 (never rebuild).
+    pub fn set_balance_alpha(&mut self, alpha: f64) -> Result<()> {
+        if alpha.is_nan() || alpha <= 0.5 || alpha >= 1.0 {
+            return Err(DsavError::InvalidState {
+                reason: format!("Balance alpha must be between 0.5 and 1, got {}", alpha),
+            });
+        }
+        self.balance_alpha = alpha;
+        Ok(())
+    }
+
+    /// Insert `value` as a plain BST insert, then keep the tree balanced the
+    /// scapegoat way.
+    ///
+    /// If the new node is deeper than `log(n) / log(1/alpha)`, walk back up
+    /// its path to the first ancestor (the scapegoat) with a child holding
+    /// more than `alpha` of its nodes, and rebuild only that subtree
+    /// perfectly balanced. Rebuilds are rare, so the cost is amortized
+    /// O(log n) per insert.
+    fn insert_balanced_with_steps(&mut self, value: i32) -> Result<Vec<Step>> {
+        let size_before = self.size;
+        let mut steps = self.execute_with_steps(Operation::Insert(0, value))?;
+        if self.size == size_before {
+            return Ok(steps);
+        }
+
+        let path = self.search_path(value);
+        let depth = path.len() - 1;
+        let limit = (self.size as f64).ln() / (1.0 / self.balance_alpha).ln();
+        let too_deep = depth as f64 > limit;
+
+        steps.push(Step {
+            description: if too_deep {
+                format!("Depth {} exceeds the limit log(n)/log(1/α) = {:.2}; looking for a scapegoat", depth, limit)
+            } else {
+                format!("Depth {} is within the limit log(n)/log(1/α) = {:.2}; no rebuild needed", depth, limit)
+            },
+            highlight_indices: path.iter().map(|&(_, idx)| idx).collect(),
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": "check_depth",
+                "depth": depth,
+                "limit": limit,
+                "alpha": self.balance_alpha
+            }),
+            detail: None,
+        });
+
+        if !too_deep {
+            return Ok(steps);
+        }
+
+        let subtree_size = |idx: usize| Self::count(self.node_at_index(idx));
+        let mut scapegoat = None;
+        for pair in path.windows(2).rev() {
+            let ((node_value, node_idx), (child_value, child_idx)) = (pair[0], pair[1]);
+            let (size, child_size) = (subtree_size(node_idx), subtree_size(child_idx));
+            let unbalanced = child_size as f64 > self.balance_alpha * size as f64;
+
+            steps.push(Step {
+                description: format!(
+                    "Node {} has {} node(s), its child {} has {}: {}",
+                    node_value,
+                    size,
+                    child_value,
+                    child_size,
+                    if unbalanced { "too heavy, this is the scapegoat" } else { "still balanced" }
+                ),
+                highlight_indices: vec![node_idx, child_idx],
+                active_indices: vec![],
+                metadata: serde_json::json!({
+                    "operation": "check_scapegoat",
+                    "value": node_value,
+                    "size": size,
+                    "child_size": child_size,
+                    "scapegoat": unbalanced
+                }),
+                detail: None,
+            });
+
+            if unbalanced {
+                scapegoat = Some((node_value, node_idx));
+                break;
+            }
+        }
+
+        // A node deeper than the limit always has an alpha-unbalanced ancestor
+        let Some((scapegoat_value, scapegoat_idx)) = scapegoat else {
+            return Ok(steps);
+        };
+
+        self.invalidate_render_cache();
+        let slot = self.subtree_slot(scapegoat_value);
+        let mut iter = InorderIter { stack: Vec::new() };
+        iter.push_left(slot.as_deref());
+        let values: Vec<i32> = iter.collect();
+        let mut placed = Vec::new();
+        *slot = Self::build_balanced(&values, scapegoat_idx, &mut placed);
+
+        let mut indices = Vec::new();
+        for (value, idx) in placed {
+            indices.push(idx);
+            steps.push(Step {
+                description: format!("Rebuilding: placing {} at index {}", value, idx),
+                highlight_indices: vec![idx],
+                active_indices: indices.clone(),
+                metadata: serde_json::json!({
+                    "operation": "place",
+                    "phase": "rebuild",
+                    "value": value,
+                    "index": idx
+                }),
+                detail: None,
+            });
+        }
+
+        steps.push(Step {
+            description: format!("Rebuilt the {} node(s) under scapegoat {} as a balanced subtree", values.len(), scapegoat_value),
+            highlight_indices: vec![],
+            active_indices: indices,
+            metadata: serde_json::json!({
+                "scapegoat": scapegoat_value,
+                "rebuilt": values
+            }),
+            detail: None,
+        });
+
+        Ok(steps)
+    }
+
+    /// The link holding the node with `value`, or the empty link where it
+    /// would go.
+    fn subtree_slot(&mut self, value: i32) -> &mut Option<Box<Node>> {
+        let mut slot = &mut self.root;
+        while slot.as_ref().is_some_and(|node| node.value != value) {
+            let node = slot.as_mut().unwrap();
+            slot = if value < node.value { &mut node.left } else { &mut node.right };
+        }
+        slot
+    }
+
+    /// Build a balanced subtree from sorted `values` by rooting it at the
+    /// middle value, recording each `(value, render index)` as it is placed.
+    fn build_balanced(values: &[i32], idx: usize, placed: &mut Vec<(i32, usize)>) -> Option<Box<Node>> {
+        if values.is_empty() {
+            return None;
+        }
+
+        let mid = values.len() / 2;
+        placed.push((values[mid], idx));
+
+        let mut node = Box::new(Node::new(values[mid]));
+        node.left = Self::build_balanced(&values[..mid], idx * 2 + 1, placed);
+        node.right = Self::build_balanced(&values[mid + 1..], idx * 2 + 2, placed);
+        Some(node)
+    }
+
+    fn invalidate_render_cache(&mut self) {
+        *self.render_cache.get_mut() = None;
+    }
+
+    /// Format the tree sideways: the root on the left, right subtrees above
+    /// their parent and left subtrees below, indented four spaces per level.
+    pub fn pretty_print(&self) -> String {
+        if self.root.is_none() {
+            return "(empty)".to_string();
+        }
+
+        let mut lines = Vec::new();
+        Self::pretty_print_helper(&self.root, 0, &mut lines);
+        lines.join("\n")
+    }
+
+    fn pretty_print_helper(node: &Option<Box<Node>>, depth: usize, lines: &mut Vec<String>) {
+        if let Some(n) = node {
+            Self::pretty_print_helper(&n.right, depth + 1, lines);
+            lines.push(format!("{}{}", "    ".repeat(depth), n.value));
+            Self::pretty_print_helper(&n.left, depth + 1, lines);
+        }
+    }
+
+    /// Build a tree from its level-order array form, where `None` marks a missing node.
+    ///
+    /// Children of index `i` live at `i * 2 + 1` and `i * 2 + 2`, matching the
+    /// layout produced by `render_state`. Returns `DsavError::InvalidState` if a
+    /// node has no parent or the values break BST ordering.
+    pub fn from_level_order(levels: &[Option<i32>]) -> Result<Self> {
+        for (idx, value) in levels.iter().enumerate().skip(1) {
+            if value.is_some() && levels[(idx - 1) / 2].is_none() {
+                return Err(DsavError::InvalidState {
+                    reason: format!("Node at index {} has no parent", idx),
+                });
+            }
+        }
+
+        let mut size = 0;
+        let root = Self::build_from_level_order(levels, 0, None, None, &mut size)?;
+
+        Ok(Self {
+            root,
+            size,
+            balance_alpha: DEFAULT_BALANCE_ALPHA,
+            render_cache: RefCell::new(None),
+        })
+    }
+
+    fn build_from_level_order(
+        levels: &[Option<i32>],
+        idx: usize,
+        min: Option<i32>,
+        max: Option<i32>,
+        size: &mut usize,
+    ) -> Result<Option<Box<Node>>> {
+        let value = match levels.get(idx).copied().flatten() {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+
+        if min.is_some_and(|min| value <= min) || max.is_some_and(|max| value >= max) {
+            return Err(DsavError::InvalidState {
+                reason: format!("Value {} at index {} violates BST ordering", value, idx),
+            });
+        }
+
+        let mut node = Box::new(Node::new(value));
+        node.left = Self::build_from_level_order(levels, idx * 2 + 1, min, Some(value), size)?;
+        node.right = Self::build_from_level_order(levels, idx * 2 + 2, Some(value), max, size)?;
+        *size += 1;
+
+        Ok(Some(node))
+    }
+
+    /// Iterate over the values in sorted (in-order) order.
+    pub fn iter(&self) -> impl Iterator<Item = i32> + '_ {
+        let mut iter = InorderIter { stack: Vec::new() };
+        iter.push_left(self.root.as_deref());
+        iter
     }
 
     // Helper to collect nodes for visualization (in-order traversal)
+    #[cfg(test)]
     fn collect_nodes(&self) -> Vec<i32> {
         let mut nodes = Vec::new();
         Self::inorder_collect(&self.root, &mut nodes);
         nodes
     }
 
+    #[cfg(test)]
     fn inorder_collect(node: &Option<Box<Node>>, nodes: &mut Vec<i32>) {
         if let Some(n) = node {
             Self::inorder_collect(&n.left, nodes);
@@ -189,6 +1496,30 @@ impl VisualizableBST {
     }
 }
 
+/// In-order iterator driven by an explicit stack of pending ancestors.
+struct InorderIter<'a> {
+    stack: Vec<&'a Node>,
+}
+
+impl<'a> InorderIter<'a> {
+    fn push_left(&mut self, mut node: Option<&'a Node>) {
+        while let Some(n) = node {
+            self.stack.push(n);
+            node = n.left.as_deref();
+        }
+    }
+}
+
+impl Iterator for InorderIter<'_> {
+    type Item = i32;
+
+    fn next(&mut self) -> Option<i32> {
+        let node = self.stack.pop()?;
+        self.push_left(node.right.as_deref());
+        Some(node.value)
+    }
+}
+
 impl Default for VisualizableBST {
     fn default() -> Self {
         Self::new()
@@ -197,7 +1528,7 @@ impl Default for VisualizableBST {
 
 impl Visualizable for VisualizableBST {
     fn execute_with_steps(&mut self, operation: Operation) -> Result<Vec<Step>> {
-        match operation {
+        traced("bst", operation, || match operation {
             Operation::Insert(_, value) => {
                 let mut steps = Vec::new();
 
@@ -209,6 +1540,7 @@ impl Visualizable for VisualizableBST {
                         "operation": "insert",
                         "value": value
                     }),
+                    detail: None,
                 });
 
                 if self.root.is_none() {
@@ -217,6 +1549,7 @@ impl Visualizable for VisualizableBST {
                         highlight_indices: vec![],
                         active_indices: vec![0],
                         metadata: serde_json::json!({}),
+                        detail: None,
                     });
                     self.insert(value);
                 } else {
@@ -233,6 +1566,7 @@ impl Visualizable for VisualizableBST {
                             highlight_indices: path.clone(),
                             active_indices: vec![],
                             metadata: serde_json::json!({}),
+                            detail: None,
                         });
 
                         if value < node.value {
@@ -248,6 +1582,7 @@ impl Visualizable for VisualizableBST {
                                 highlight_indices: path.clone(),
                                 active_indices: vec![],
                                 metadata: serde_json::json!({}),
+                                detail: None,
                             });
                             return Ok(steps);
                         }
@@ -255,11 +1590,17 @@ impl Visualizable for VisualizableBST {
 
                     self.insert(value);
 
+                    // Look up the slot after inserting so the highlight matches render_state
+                    let idx = self.find_node_index(value).unwrap_or(idx);
+
                     steps.push(Step {
                         description: format!("Inserted {} successfully", value),
                         highlight_indices: vec![],
                         active_indices: vec![idx],
-                        metadata: serde_json::json!({}),
+                        metadata: serde_json::json!({
+                            "index": idx
+                        }),
+                        detail: None,
                     });
                 }
 
@@ -278,6 +1619,7 @@ impl Visualizable for VisualizableBST {
                         "operation": "delete",
                         "value": value
                     }),
+                    detail: None,
                 });
 
                 // First, search for the node to visualize the path
@@ -291,6 +1633,7 @@ impl Visualizable for VisualizableBST {
                         highlight_indices: vec![idx],
                         active_indices: vec![],
                         metadata: serde_json::json!({}),
+                        detail: None,
                     });
 
                     if value == node.value {
@@ -313,6 +1656,7 @@ impl Visualizable for VisualizableBST {
                                 "found": true,
                                 "index": idx
                             }),
+                            detail: None,
                         });
                         break;
                     } else if value < node.value {
@@ -332,6 +1676,7 @@ impl Visualizable for VisualizableBST {
                         metadata: serde_json::json!({
                             "found": false
                         }),
+                        detail: None,
                     });
                     return Ok(steps);
                 }
@@ -345,6 +1690,7 @@ impl Visualizable for VisualizableBST {
                         highlight_indices: vec![],
                         active_indices: vec![],
                         metadata: serde_json::json!({}),
+                        detail: None,
                     });
                 }
 
@@ -362,29 +1708,40 @@ impl Visualizable for VisualizableBST {
                         "operation": "search",
                         "target": target
                     }),
+                    detail: None,
                 });
 
                 let mut current = self.root.as_ref();
                 let mut idx = 0;
                 let mut found = false;
+                // Every node compared, root first, for reviewing the whole path afterwards
+                let mut path = Vec::new();
 
                 while let Some(node) = current {
+                    path.push(idx);
+
                     steps.push(Step {
                         description: format!("Checking node with value {}", node.value),
                         highlight_indices: vec![idx],
                         active_indices: vec![],
-                        metadata: serde_json::json!({}),
+                        metadata: serde_json::json!({
+                            "operation": "compare",
+                            "index": idx
+                        }),
+                        detail: None,
                     });
 
                     if target == node.value {
                         steps.push(Step {
                             description: format!("Found {} at node", target),
-                            highlight_indices: vec![],
+                            highlight_indices: path[..path.len() - 1].to_vec(),
                             active_indices: vec![idx],
                             metadata: serde_json::json!({
                                 "found": true,
-                                "index": idx
+                                "index": idx,
+                                "path": path
                             }),
+                            detail: None,
                         });
                         found = true;
                         break;
@@ -400,17 +1757,30 @@ impl Visualizable for VisualizableBST {
                 if !found {
                     steps.push(Step {
                         description: format!("Value {} not found in tree", target),
-                        highlight_indices: vec![],
+                        highlight_indices: path.clone(),
                         active_indices: vec![],
                         metadata: serde_json::json!({
-                            "found": false
+                            "found": false,
+                            "path": path
                         }),
+                        detail: None,
                     });
                 }
 
                 Ok(steps)
             }
 
+            Operation::Traverse
+            | Operation::PreOrderTraverse
+            | Operation::PostOrderTraverse
+            | Operation::LevelOrderTraverse
+            | Operation::ZigzagTraverse
+            | Operation::MorrisInOrder
+                if self.root.is_none() =>
+            {
+                Ok(vec![Step::empty_traversal()])
+            }
+
             Operation::Traverse => {
                 let mut steps = Vec::new();
 
@@ -421,6 +1791,7 @@ impl Visualizable for VisualizableBST {
                     metadata: serde_json::json!({
                         "operation": "traverse"
                     }),
+                    detail: None,
                 });
 
                 Self::inorder_traverse(&self.root, 0, &mut steps);
@@ -430,6 +1801,7 @@ impl Visualizable for VisualizableBST {
                     highlight_indices: vec![],
                     active_indices: vec![],
                     metadata: serde_json::json!({}),
+                    detail: None,
                 });
 
                 Ok(steps)
@@ -445,6 +1817,7 @@ impl Visualizable for VisualizableBST {
                     metadata: serde_json::json!({
                         "operation": "preorder_traverse"
                     }),
+                    detail: None,
                 });
 
                 Self::preorder_traverse(&self.root, 0, &mut steps);
@@ -454,6 +1827,7 @@ impl Visualizable for VisualizableBST {
                     highlight_indices: vec![],
                     active_indices: vec![],
                     metadata: serde_json::json!({}),
+                    detail: None,
                 });
 
                 Ok(steps)
@@ -469,6 +1843,7 @@ impl Visualizable for VisualizableBST {
                     metadata: serde_json::json!({
                         "operation": "postorder_traverse"
                     }),
+                    detail: None,
                 });
 
                 Self::postorder_traverse(&self.root, 0, &mut steps);
@@ -478,6 +1853,7 @@ impl Visualizable for VisualizableBST {
                     highlight_indices: vec![],
                     active_indices: vec![],
                     metadata: serde_json::json!({}),
+                    detail: None,
                 });
 
                 Ok(steps)
@@ -493,6 +1869,7 @@ impl Visualizable for VisualizableBST {
                     metadata: serde_json::json!({
                         "operation": "levelorder_traverse"
                     }),
+                    detail: None,
                 });
 
                 Self::levelorder_traverse(&self.root, &mut steps);
@@ -502,27 +1879,86 @@ impl Visualizable for VisualizableBST {
                     highlight_indices: vec![],
                     active_indices: vec![],
                     metadata: serde_json::json!({}),
+                    detail: None,
                 });
 
                 Ok(steps)
             }
 
+            Operation::ZigzagTraverse => Ok(self.zigzag_with_steps()),
+
+            Operation::MorrisInOrder => Ok(self.morris_inorder_with_steps()),
+
+            Operation::MirrorTree => Ok(self.mirror_with_steps()),
+
+            Operation::IsBalanced => Ok(self.is_balanced_with_steps().1),
+
+            Operation::ShowLevelWidths => Ok(self.level_widths_with_steps()),
+
+            Operation::InsertBalanced(value) => self.insert_balanced_with_steps(value),
+
+            Operation::Rank(x) => Ok(self.rank_with_steps(x).1),
+
+            Operation::PathToRoot(value) => self.path_to_root_with_steps(value),
+
+            Operation::FindClosest(target) => Ok(self.closest_with_steps(target).1),
+
+            Operation::InOrderPredecessorSuccessorPair(value) => self.neighbors_with_steps(value),
+
+            Operation::RangeQuery(lo, hi) => self.range_query_with_steps(lo, hi),
+
+            Operation::Select(k) => {
+                if k >= self.size {
+                    return Err(DsavError::IndexOutOfBounds {
+                        index: k,
+                        size: self.size,
+                    });
+                }
+                Ok(self.select_with_steps(k).1)
+            }
+
             _ => Err(DsavError::Visualization(
                 "Operation not supported for BST".to_string(),
             )),
+        })
+    }
+
+    fn execute(&mut self, operation: Operation) -> Result<()> {
+        match operation {
+            Operation::Insert(_, value) => {
+                self.insert(value);
+                Ok(())
+            }
+            Operation::Delete(value_usize) => {
+                self.delete(value_usize as i32);
+                Ok(())
+            }
+            _ => self.execute_with_steps(operation).map(|_| ()),
         }
     }
 
     fn render_state(&self) -> RenderState {
+        if self.root.is_none() {
+            return RenderState::empty_placeholder("Binary Search Tree is empty", "Insert a value to add nodes");
+        }
+
+        if let Some(state) = self.render_cache.borrow().as_ref() {
+            return state.clone();
+        }
+
         let mut elements = Vec::new();
         let mut connections = Vec::new();
 
         Self::build_render_state(&self.root, 0, &mut elements, &mut connections);
+        elements[0].badge = Some("root".to_string());
 
-        RenderState {
+        let state = RenderState {
             elements,
             connections,
-        }
+            cols: None,
+        };
+        *self.render_cache.borrow_mut() = Some(state.clone());
+        state
     }
 }
 
@@ -539,6 +1975,7 @@ impl VisualizableBST {
                     "value": n.value,
                     "index": idx
                 }),
+                detail: None,
             });
 
             Self::inorder_traverse(&n.right, idx * 2 + 2, steps);
@@ -555,6 +1992,7 @@ impl VisualizableBST {
                     "value": n.value,
                     "index": idx
                 }),
+                detail: None,
             });
 
             Self::preorder_traverse(&n.left, idx * 2 + 1, steps);
@@ -575,6 +2013,7 @@ impl VisualizableBST {
                     "value": n.value,
                     "index": idx
                 }),
+                detail: None,
             });
         }
     }
@@ -599,6 +2038,7 @@ impl VisualizableBST {
                         "value": node.value,
                         "index": idx
                     }),
+                    detail: None,
                 });
 
                 // Enqueue left child
@@ -614,6 +2054,171 @@ impl VisualizableBST {
         }
     }
 
+    /// Morris in-order traversal: visits nodes in order without a stack by
+    /// temporarily threading each predecessor's empty right link back to the
+    /// current node.
+    ///
+    /// Nodes are uniquely owned, so the threads are formed on an index-based
+    /// copy of the tree. Every thread is removed again before the traversal
+    /// finishes, and the tree itself is never modified.
+    fn morris_inorder_with_steps(&self) -> Vec<Step> {
+        let mut steps = Vec::new();
+
+        // (value, left, right, render index) for each node
+        let mut nodes: Vec<(i32, Option<usize>, Option<usize>, usize)> = Vec::new();
+        let root = Self::build_morris_nodes(&self.root, 0, &mut nodes);
+        let mut order = Vec::new();
+
+        steps.push(Step {
+            description: "Starting Morris in-order traversal (no stack, temporary threads)".to_string(),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": "morris_inorder"
+            }),
+            detail: None,
+        });
+
+        let mut current = root;
+        while let Some(cur) = current {
+            let (value, left, right, pos) = nodes[cur];
+
+            let Some(left) = left else {
+                order.push(value);
+                steps.push(Step {
+                    description: format!("No left subtree, visiting {}", value),
+                    highlight_indices: vec![],
+                    active_indices: vec![pos],
+                    metadata: serde_json::json!({
+                        "operation": "visit",
+                        "value": value,
+                        "index": pos
+                    }),
+                    detail: None,
+                });
+                current = right;
+                continue;
+            };
+
+            // Rightmost node of the left subtree, stopping at an existing thread
+            let mut pred = left;
+            while let Some(next) = nodes[pred].2.filter(|&next| next != cur) {
+                pred = next;
+            }
+            let pred_pos = nodes[pred].3;
+
+            if nodes[pred].2.is_none() {
+                nodes[pred].2 = Some(cur);
+                steps.push(Step {
+                    description: format!(
+                        "Threading predecessor {} back to {}, then moving left",
+                        nodes[pred].0, value
+                    ),
+                    highlight_indices: vec![pred_pos],
+                    active_indices: vec![pos],
+                    metadata: serde_json::json!({
+                        "operation": "thread",
+                        "current": pos,
+                        "predecessor": pred_pos,
+                        "thread": [pred_pos, pos]
+                    }),
+                    detail: None,
+                });
+                current = Some(left);
+            } else {
+                nodes[pred].2 = None;
+                order.push(value);
+                steps.push(Step {
+                    description: format!(
+                        "Thread from {} found, removing it and visiting {}",
+                        nodes[pred].0, value
+                    ),
+                    highlight_indices: vec![pred_pos],
+                    active_indices: vec![pos],
+                    metadata: serde_json::json!({
+                        "operation": "unthread",
+                        "current": pos,
+                        "predecessor": pred_pos,
+                        "value": value,
+                        "index": pos
+                    }),
+                    detail: None,
+                });
+                current = right;
+            }
+        }
+
+        steps.push(Step {
+            description: "Morris traversal complete, all threads removed".to_string(),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "order": order
+            }),
+            detail: None,
+        });
+
+        steps
+    }
+
+    fn build_morris_nodes(
+        node: &Option<Box<Node>>,
+        pos: usize,
+        nodes: &mut Vec<(i32, Option<usize>, Option<usize>, usize)>,
+    ) -> Option<usize> {
+        let n = node.as_ref()?;
+        let id = nodes.len();
+        nodes.push((n.value, None, None, pos));
+
+        nodes[id].1 = Self::build_morris_nodes(&n.left, pos * 2 + 1, nodes);
+        nodes[id].2 = Self::build_morris_nodes(&n.right, pos * 2 + 2, nodes);
+
+        Some(id)
+    }
+
+    /// `(value, render index)` of every node a search for `value` visits,
+    /// from the root down. The last entry holds `value` if it is present.
+    fn search_path(&self, value: i32) -> Vec<(i32, usize)> {
+        let mut path = Vec::new();
+        let mut current = self.root.as_deref();
+        let mut idx = 0;
+
+        while let Some(node) = current {
+            path.push((node.value, idx));
+            if value == node.value {
+                break;
+            } else if value < node.value {
+                current = node.left.as_deref();
+                idx = idx * 2 + 1;
+            } else {
+                current = node.right.as_deref();
+                idx = idx * 2 + 2;
+            }
+        }
+
+        path
+    }
+
+    /// Find the render index (`idx*2+1` / `idx*2+2` layout) of the node holding `value`.
+    fn find_node_index(&self, value: i32) -> Option<usize> {
+        let mut current = self.root.as_ref();
+        let mut idx = 0;
+
+        while let Some(node) = current {
+            if value == node.value {
+                return Some(idx);
+            } else if value < node.value {
+                current = node.left.as_ref();
+                idx = idx * 2 + 1;
+            } else {
+                current = node.right.as_ref();
+                idx = idx * 2 + 2;
+            }
+        }
+
+        None
+    }
+
     fn build_render_state(
         node: &Option<Box<Node>>,
         idx: usize,
@@ -702,4 +2307,548 @@ mod tests {
 
         assert_eq!(bst.size(), 1);
     }
+
+    #[test]
+    fn test_bst_from_level_order() {
+        let bst = VisualizableBST::from_level_order(&[
+            Some(50),
+            Some(30),
+            Some(70),
+            None,
+            Some(40),
+        ])
+        .unwrap();
+
+        assert_eq!(bst.size(), 4);
+        assert_eq!(bst.collect_nodes(), vec![30, 40, 50, 70]);
+        assert!(VisualizableBST::from_level_order(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_bst_from_level_order_round_trip() {
+        let mut bst = VisualizableBST::new();
+        for value in [50, 30, 70, 20, 40, 80, 75] {
+            bst.insert(value);
+        }
+
+        let state = bst.render_state();
+        let levels: Vec<Option<i32>> = state
+            .elements
+            .iter()
+            .map(|e| if e.label.is_empty() { None } else { Some(e.value) })
+            .collect();
+
+        let rebuilt = VisualizableBST::from_level_order(&levels).unwrap();
+        let rebuilt_state = rebuilt.render_state();
+
+        assert_eq!(rebuilt.size(), bst.size());
+        assert_eq!(rebuilt.collect_nodes(), bst.collect_nodes());
+        assert_eq!(rebuilt_state.connections, state.connections);
+        let values = |s: &RenderState| s.elements.iter().map(|e| (e.value, e.label.clone())).collect::<Vec<_>>();
+        assert_eq!(values(&rebuilt_state), values(&state));
+    }
+
+    #[test]
+    fn test_bst_from_level_order_invalid() {
+        // 60 sits in the left subtree of 50
+        assert!(matches!(
+            VisualizableBST::from_level_order(&[Some(50), Some(30), Some(70), None, Some(60)]),
+            Err(DsavError::InvalidState { .. })
+        ));
+
+        // Index 3 has no parent at index 1
+        assert!(matches!(
+            VisualizableBST::from_level_order(&[Some(50), None, Some(70), Some(10)]),
+            Err(DsavError::InvalidState { .. })
+        ));
+    }
+
+    #[test]
+    fn test_bst_pretty_print() {
+        let mut bst = VisualizableBST::new();
+        assert_eq!(bst.pretty_print(), "(empty)");
+
+        for value in [50, 30, 70, 20] {
+            bst.insert(value);
+        }
+        assert_eq!(bst.pretty_print(), "    70\n50\n    30\n        20");
+    }
+
+    #[test]
+    fn test_bst_insert_reports_render_index() {
+        let mut bst = VisualizableBST::new();
+        for value in [50, 30, 70, 20] {
+            bst.insert(value);
+        }
+
+        for value in [40, 10, 80, 60] {
+            let steps = bst.execute_with_steps(Operation::Insert(0, value)).unwrap();
+            let idx = steps.last().unwrap().active_indices[0];
+
+            let state = bst.render_state();
+            assert_eq!(state.elements[idx].value, value);
+            assert_eq!(state.elements[idx].label, value.to_string());
+        }
+    }
+
+    #[test]
+    fn test_bst_insert_returning_index() {
+        let mut bst = VisualizableBST::new();
+        assert_eq!(bst.insert_returning_index(50), (true, Some(0)));
+
+        for value in [30, 70, 40, 35] {
+            let (inserted, idx) = bst.insert_returning_index(value);
+            assert!(inserted);
+            let state = bst.render_state();
+            assert_eq!(state.elements[idx.unwrap()].value, value);
+        }
+        assert_eq!(bst.insert_returning_index(35), (false, None));
+    }
+
+    #[test]
+    fn test_bst_iter_in_order() {
+        let mut bst = VisualizableBST::new();
+        assert_eq!(bst.iter().count(), 0);
+
+        for value in [50, 30, 70, 20, 40, 60, 80, 35] {
+            bst.insert(value);
+        }
+
+        let values: Vec<i32> = bst.iter().collect();
+        assert_eq!(values, vec![20, 30, 35, 40, 50, 60, 70, 80]);
+        assert_eq!(values, bst.collect_nodes());
+    }
+
+    #[test]
+    fn test_bst_execute_matches_execute_with_steps() {
+        let values = [50, 30, 70, 20, 40, 60, 80, 30];
+
+        let mut animated = VisualizableBST::new();
+        let mut instant = VisualizableBST::new();
+        for value in values {
+            animated.execute_with_steps(Operation::Insert(0, value)).unwrap();
+            instant.execute(Operation::Insert(0, value)).unwrap();
+        }
+
+        assert_eq!(instant.size(), animated.size());
+        assert_eq!(instant.pretty_print(), animated.pretty_print());
+
+        animated.execute_with_steps(Operation::Delete(30)).unwrap();
+        instant.execute(Operation::Delete(30)).unwrap();
+        assert_eq!(instant.pretty_print(), animated.pretty_print());
+    }
+
+    #[test]
+    fn test_bst_root_badge() {
+        let mut bst = VisualizableBST::new();
+        for value in [50, 30, 70] {
+            bst.insert(value);
+        }
+
+        let state = bst.render_state();
+        assert_eq!(state.elements[0].badge.as_deref(), Some("root"));
+        assert!(state.elements[1..].iter().all(|elem| elem.badge.is_none()));
+    }
+
+    #[test]
+    fn test_bst_render_state_cache() {
+        let mut bst = VisualizableBST::new();
+        for value in [50, 30, 70] {
+            bst.insert(value);
+        }
+        assert!(bst.render_cache.borrow().is_none());
+
+        let first = bst.render_state();
+        assert!(bst.render_cache.borrow().is_some());
+        let second = bst.render_state();
+        assert_eq!(second.connections, first.connections);
+        assert_eq!(
+            second.elements.iter().map(|e| e.value).collect::<Vec<_>>(),
+            first.elements.iter().map(|e| e.value).collect::<Vec<_>>()
+        );
+
+        bst.insert(20);
+        assert!(bst.render_cache.borrow().is_none());
+        assert_eq!(bst.render_state().elements[3].value, 20);
+
+        bst.execute_with_steps(Operation::Delete(20)).unwrap();
+        assert!(bst.render_cache.borrow().is_none());
+        assert_eq!(bst.render_state().elements.len(), 3);
+    }
+
+    #[test]
+    fn test_bst_morris_inorder() {
+        let mut bst = VisualizableBST::new();
+        for value in [50, 30, 70, 20, 40, 60, 80, 35, 45] {
+            bst.insert(value);
+        }
+        let before = bst.render_state();
+
+        let steps = bst.execute_with_steps(Operation::MorrisInOrder).unwrap();
+
+        let order = &steps.last().unwrap().metadata["order"];
+        assert_eq!(*order, serde_json::json!(bst.iter().collect::<Vec<_>>()));
+        assert_eq!(*order, serde_json::json!([20, 30, 35, 40, 45, 50, 60, 70, 80]));
+
+        // Every thread created is removed again
+        let threads = steps.iter().filter(|s| s.metadata["operation"] == "thread").count();
+        let unthreads = steps.iter().filter(|s| s.metadata["operation"] == "unthread").count();
+        assert_eq!(threads, unthreads);
+
+        let after = bst.render_state();
+        assert_eq!(after.connections, before.connections);
+        assert_eq!(
+            after.elements.iter().map(|e| e.label.clone()).collect::<Vec<_>>(),
+            before.elements.iter().map(|e| e.label.clone()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_bst_mirror_reverses_inorder() {
+        let mut bst = VisualizableBST::new();
+        for value in [50, 30, 70, 20, 40, 80] {
+            bst.insert(value);
+        }
+        assert!(bst.is_valid_bst());
+
+        let mut original: Vec<i32> = bst.iter().collect();
+        let steps = bst.execute_with_steps(Operation::MirrorTree).unwrap();
+
+        original.reverse();
+        assert_eq!(bst.iter().collect::<Vec<_>>(), original);
+        assert!(!bst.is_valid_bst());
+        assert_eq!(bst.size(), 6);
+        assert!(steps.iter().any(|s| s.metadata["operation"] == "swap_children"));
+
+        bst.mirror_with_steps();
+        assert!(bst.is_valid_bst());
+    }
+
+    #[test]
+    fn test_bst_is_balanced_degenerate() {
+        let mut bst = VisualizableBST::new();
+        for value in [1, 2, 3, 4] {
+            bst.insert(value);
+        }
+
+        let (balanced, steps) = bst.is_balanced_with_steps();
+        assert!(!balanced);
+
+        // Node 2 (render index 2) is the first found out of balance bottom-up
+        let last = steps.last().unwrap();
+        assert_eq!(last.metadata["balanced"], false);
+        assert_eq!(last.metadata["value"], 2);
+        assert_eq!(last.highlight_indices, vec![2]);
+    }
+
+    #[test]
+    fn test_bst_is_balanced_perfect() {
+        let mut bst = VisualizableBST::new();
+        for value in [40, 20, 60, 10, 30, 50, 70] {
+            bst.insert(value);
+        }
+
+        let (balanced, steps) = bst.is_balanced_with_steps();
+        assert!(balanced);
+        assert_eq!(steps.last().unwrap().metadata["balanced"], true);
+        assert!(steps
+            .iter()
+            .filter(|s| s.metadata["operation"] == "balance_factor")
+            .all(|s| s.metadata["balance_factor"] == 0));
+    }
+
+    #[test]
+    fn test_bst_subtree_size() {
+        let mut bst = VisualizableBST::new();
+        for value in [50, 30, 70, 20, 40, 35] {
+            bst.insert(value);
+        }
+
+        assert_eq!(bst.subtree_size(0), Some(6));
+        assert_eq!(bst.subtree_size(1), Some(4));
+        assert_eq!(bst.subtree_size(4), Some(2));
+        assert_eq!(bst.subtree_size(2), Some(1));
+        assert_eq!(bst.subtree_size(5), None);
+        assert_eq!(VisualizableBST::new().subtree_size(0), None);
+    }
+
+    #[test]
+    fn test_bst_search_path() {
+        let mut bst = VisualizableBST::new();
+        for value in [50, 30, 70, 20, 40] {
+            bst.insert(value);
+        }
+
+        let steps = bst.execute_with_steps(Operation::Search(40)).unwrap();
+        let comparisons = steps.iter().filter(|s| s.metadata["operation"] == "compare").count();
+        let last = steps.last().unwrap();
+        assert_eq!(last.metadata["path"], serde_json::json!([0, 1, 4]));
+        assert_eq!(comparisons, 3);
+        assert_eq!(last.highlight_indices, vec![0, 1]);
+
+        // A failed search ends at the leaf where it ran out of children
+        let steps = bst.execute_with_steps(Operation::Search(45)).unwrap();
+        let comparisons = steps.iter().filter(|s| s.metadata["operation"] == "compare").count();
+        let last = steps.last().unwrap();
+        assert_eq!(last.metadata["found"], false);
+        assert_eq!(last.metadata["path"].as_array().unwrap().len(), comparisons);
+        assert_eq!(last.highlight_indices, vec![0, 1, 4]);
+    }
+
+    #[test]
+    fn test_bst_zigzag_traversal() {
+        let mut bst = VisualizableBST::new();
+        for value in [4, 2, 6, 1, 3, 5, 7] {
+            bst.insert(value);
+        }
+
+        let steps = bst.execute_with_steps(Operation::ZigzagTraverse).unwrap();
+        let visited: Vec<i64> = steps
+            .iter()
+            .filter(|s| s.metadata.get("value").is_some())
+            .map(|s| s.metadata["value"].as_i64().unwrap())
+            .collect();
+
+        assert_eq!(visited, vec![4, 6, 2, 1, 3, 5, 7]);
+        assert_eq!(steps.last().unwrap().metadata["order"], serde_json::json!([4, 6, 2, 1, 3, 5, 7]));
+
+        let directions: Vec<&str> = steps
+            .iter()
+            .filter(|s| s.metadata.get("value").is_none() && s.metadata.get("direction").is_some())
+            .map(|s| s.metadata["direction"].as_str().unwrap())
+            .collect();
+        assert_eq!(directions, vec!["left_to_right", "right_to_left", "left_to_right"]);
+    }
+
+    #[test]
+    fn test_bst_level_widths() {
+        let mut balanced = VisualizableBST::new();
+        for value in [40, 20, 60, 10, 30, 50, 70] {
+            balanced.insert(value);
+        }
+
+        let mut degenerate = VisualizableBST::new();
+        for value in [10, 20, 30, 40] {
+            degenerate.insert(value);
+        }
+
+        assert_eq!(balanced.level_widths(), vec![1, 2, 4]);
+        assert_eq!(degenerate.level_widths(), vec![1, 1, 1, 1]);
+        assert!(VisualizableBST::new().level_widths().is_empty());
+
+        let steps = balanced.execute_with_steps(Operation::ShowLevelWidths).unwrap();
+        assert_eq!(steps[3].highlight_indices, vec![3, 4, 5, 6]);
+
+        let last = steps.last().unwrap();
+        assert_eq!(last.metadata["widths"], serde_json::json!([1, 2, 4]));
+        assert_eq!(last.metadata["widest_level"], 2);
+        assert_eq!(last.metadata["max_width"], 4);
+    }
+
+    #[test]
+    fn test_bst_empty_placeholder() {
+        let state = VisualizableBST::new().render_state();
+        assert!(state.is_empty_placeholder());
+        assert!(state.connections.is_empty());
+    }
+
+    #[test]
+    fn test_bst_merge_trees() {
+        let mut left = VisualizableBST::new();
+        for value in [3, 1, 5] {
+            left.insert(value);
+        }
+        let mut right = VisualizableBST::new();
+        for value in [4, 2, 6] {
+            right.insert(value);
+        }
+
+        let steps = left.merge_with_steps(&right);
+
+        assert_eq!(left.collect_nodes(), vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(left.size(), 6);
+        assert!(left.is_valid_bst());
+        assert!(left.is_balanced_with_steps().0);
+        for phase in ["flatten", "merge", "rebuild"] {
+            assert!(steps.iter().any(|s| s.metadata["phase"] == phase));
+        }
+    }
+
+    #[test]
+    fn test_bst_merge_drops_shared_values() {
+        let mut left = VisualizableBST::new();
+        for value in [2, 1, 3] {
+            left.insert(value);
+        }
+        let mut right = VisualizableBST::new();
+        for value in [3, 4] {
+            right.insert(value);
+        }
+
+        left.merge_with_steps(&right);
+
+        assert_eq!(left.collect_nodes(), vec![1, 2, 3, 4]);
+        assert_eq!(left.size(), 4);
+    }
+
+    #[test]
+    fn test_bst_traverse_empty() {
+        let mut bst = VisualizableBST::new();
+        for operation in [
+            Operation::Traverse,
+            Operation::PreOrderTraverse,
+            Operation::PostOrderTraverse,
+            Operation::LevelOrderTraverse,
+            Operation::ZigzagTraverse,
+            Operation::MorrisInOrder,
+        ] {
+            let steps = bst.execute_with_steps(operation).unwrap();
+            assert_eq!(steps.len(), 1);
+            assert_eq!(steps[0].description, "Structure is empty, nothing to traverse");
+        }
+    }
+
+    #[test]
+    fn test_bst_insert_balanced_bounds_height() {
+        // Levels in the tree; render indices of a 100-deep chain would overflow
+        fn height(node: &Option<Box<Node>>) -> usize {
+            node.as_ref().map_or(0, |n| 1 + height(&n.left).max(height(&n.right)))
+        }
+
+        let mut plain = VisualizableBST::new();
+        let mut balanced = VisualizableBST::new();
+        for value in 1..=100 {
+            plain.insert(value);
+            balanced.execute_with_steps(Operation::InsertBalanced(value)).unwrap();
+        }
+
+        // log(100) / log(1 / 0.7) is about 12.9, so depth stays at most 13
+        assert_eq!(height(&plain.root), 100);
+        assert!(height(&balanced.root) <= 14);
+        assert!(balanced.is_valid_bst());
+        assert_eq!(balanced.iter().collect::<Vec<_>>(), (1..=100).collect::<Vec<_>>());
+
+        let steps = balanced.execute_with_steps(Operation::InsertBalanced(101)).unwrap();
+        assert!(steps.iter().any(|s| s.metadata["operation"] == "check_depth"));
+    }
+
+    #[test]
+    fn test_bst_balance_alpha_range() {
+        let mut bst = VisualizableBST::new();
+        assert!(bst.set_balance_alpha(0.5).is_err());
+        assert!(bst.set_balance_alpha(1.0).is_err());
+        bst.set_balance_alpha(0.6).unwrap();
+        assert_eq!(bst.balance_alpha(), 0.6);
+    }
+
+    #[test]
+    fn test_bst_path_to_root() {
+        let mut bst = VisualizableBST::new();
+        for value in [50, 30, 70, 20, 40, 35] {
+            bst.insert(value);
+        }
+
+        let steps = bst.execute_with_steps(Operation::PathToRoot(35)).unwrap();
+        let last = steps.last().unwrap();
+        assert_eq!(last.metadata["ancestors"], serde_json::json!([40, 30, 50]));
+        assert_eq!(last.active_indices, vec![9, 4, 1, 0]);
+        assert!(matches!(
+            bst.execute_with_steps(Operation::PathToRoot(99)),
+            Err(DsavError::NotFound { value: 99 })
+        ));
+    }
+
+    #[test]
+    fn test_bst_closest() {
+        let mut bst = VisualizableBST::new();
+        for value in [40, 20, 60, 10, 30, 50, 70] {
+            bst.insert(value);
+        }
+
+        assert_eq!(bst.closest(33), Some(30));
+        assert_eq!(bst.closest(47), Some(50));
+        assert_eq!(bst.closest(40), Some(40));
+        assert_eq!(bst.closest(-100), Some(10));
+        // 25 and 35 are as far from 20 or 30 as from 30 or 40; the smaller wins
+        assert_eq!(bst.closest(25), Some(20));
+        assert_eq!(bst.closest(35), Some(30));
+        assert_eq!(VisualizableBST::new().closest(5), None);
+
+        let steps = bst.execute_with_steps(Operation::FindClosest(47)).unwrap();
+        let last = steps.last().unwrap();
+        assert_eq!(last.metadata["closest"], 50);
+        assert_eq!(last.metadata["difference"], 3);
+        assert_eq!(last.active_indices, vec![5]);
+    }
+
+    #[test]
+    fn test_bst_predecessor_successor_pair() {
+        let mut bst = VisualizableBST::new();
+        for value in [40, 20, 60, 10, 30, 50, 70, 25] {
+            bst.insert(value);
+        }
+
+        // 40's predecessor is the rightmost node of its left subtree
+        let steps = bst.execute_with_steps(Operation::InOrderPredecessorSuccessorPair(40)).unwrap();
+        let last = steps.last().unwrap();
+        assert_eq!(last.metadata["predecessor"], 30);
+        assert_eq!(last.metadata["successor"], 50);
+        assert_eq!(last.highlight_indices, vec![4, 5]);
+        assert_eq!(last.active_indices, vec![0]);
+
+        // 25's successor is an ancestor rather than a descendant
+        assert_eq!(bst.neighbors(25).unwrap(), (Some(20), Some(30)));
+        assert_eq!(bst.neighbors(10).unwrap(), (None, Some(20)));
+        assert_eq!(bst.neighbors(70).unwrap(), (Some(60), None));
+        assert!(matches!(bst.neighbors(45), Err(DsavError::NotFound { value: 45 })));
+    }
+
+    #[test]
+    fn test_bst_range_query() {
+        let mut bst = VisualizableBST::new();
+        for value in [40, 20, 60, 10, 30, 50, 70, 5, 65] {
+            bst.insert(value);
+        }
+
+        assert_eq!(bst.range_query(25, 55), vec![30, 40, 50]);
+        assert_eq!(bst.range_query(0, 100), bst.iter().collect::<Vec<_>>());
+        assert!(bst.range_query(41, 49).is_empty());
+
+        let steps = bst.execute_with_steps(Operation::RangeQuery(25, 55)).unwrap();
+        assert_eq!(steps.last().unwrap().metadata["matches"], serde_json::json!([30, 40, 50]));
+
+        // 10 (with 5 below it) lies left of 20 and 70 (with 65) right of 60
+        let pruned: Vec<&serde_json::Value> = steps
+            .iter()
+            .filter(|s| s.metadata["operation"] == "prune")
+            .map(|s| &s.metadata["pruned"])
+            .collect();
+        assert_eq!(pruned, vec![&serde_json::json!([3, 7]), &serde_json::json!([6, 13])]);
+        assert!(bst.execute_with_steps(Operation::RangeQuery(5, 1)).is_err());
+    }
+
+    #[test]
+    fn test_bst_rank_and_select() {
+        let mut bst = VisualizableBST::new();
+        for value in [40, 20, 60, 10, 30, 50, 70] {
+            bst.insert(value);
+        }
+
+        assert_eq!(bst.rank(40), 3);
+        assert_eq!(bst.rank(35), 3);
+        assert_eq!(bst.rank(5), 0);
+        assert_eq!(bst.rank(100), 7);
+
+        assert_eq!(bst.select(0), Some(10));
+        assert_eq!(bst.select(3), Some(40));
+        assert_eq!(bst.select(6), Some(70));
+        assert_eq!(bst.select(7), None);
+
+        let steps = bst.execute_with_steps(Operation::Select(3)).unwrap();
+        assert_eq!(steps.last().unwrap().metadata["value"], 40);
+        assert!(matches!(
+            bst.execute_with_steps(Operation::Select(7)),
+            Err(DsavError::IndexOutOfBounds { index: 7, size: 7 })
+        ));
+    }
 }
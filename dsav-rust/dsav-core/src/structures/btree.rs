@@ -0,0 +1,527 @@
+//! Educational B-tree implementation with visualization support.
+//!
+//! Every node holds between `t - 1` and `2t - 1` sorted keys (the root may
+//! hold fewer), and an internal node with `k` keys has `k + 1` children. All
+//! leaves sit at the same depth. Inserts split full nodes on the way down:
+//! the median key moves up into the parent and the rest divides into two
+//! nodes, so the tree only ever grows taller at the root.
+
+use crate::error::{DsavError, Result};
+use crate::state::{ElementState, RenderElement, RenderState};
+use crate::instrument::traced;
+use crate::traits::{Operation, Step, Visualizable};
+
+const DEFAULT_MIN_DEGREE: usize = 2;
+
+#[derive(Debug, Clone, Default)]
+struct Node {
+    keys: Vec<i32>,
+    /// Empty for a leaf, otherwise one more than `keys`.
+    children: Vec<Node>,
+}
+
+impl Node {
+    fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct VisualizableBTree {
+    root: Node,
+    /// Minimum degree `t`: nodes hold at most `2t - 1` keys.
+    min_degree: usize,
+    size: usize,
+}
+
+impl VisualizableBTree {
+    pub fn new() -> Self {
+        Self::with_min_degree(DEFAULT_MIN_DEGREE)
+    }
+
+    /// Create a tree of minimum degree `t`, raised to 2 if smaller. With
+    /// `t = 2` this is a 2-3-4 tree.
+    pub fn with_min_degree(min_degree: usize) -> Self {
+        Self {
+            root: Node::default(),
+            min_degree: min_degree.max(2),
+            size: 0,
+        }
+    }
+
+    /// Insert a value. Returns `false` if it was already present.
+    pub fn insert(&mut self, value: i32) -> bool {
+        self.insert_impl(value, None)
+    }
+
+    pub fn search(&self, value: i32) -> bool {
+        let mut node = &self.root;
+        loop {
+            match node.keys.binary_search(&value) {
+                Ok(_) => return true,
+                Err(_) if node.is_leaf() => return false,
+                Err(i) => node = &node.children[i],
+            }
+        }
+    }
+
+    pub fn min_degree(&self) -> usize {
+        self.min_degree
+    }
+
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.root = Node::default();
+        self.size = 0;
+    }
+
+    /// Number of levels; every leaf is at this depth.
+    pub fn height(&self) -> usize {
+        if self.is_empty() {
+            return 0;
+        }
+        std::iter::successors(Some(&self.root), |node| node.children.first()).count()
+    }
+
+    /// Keys of every node, level by level, each level left to right.
+    pub fn levels(&self) -> Vec<Vec<Vec<i32>>> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+
+        let mut levels = Vec::new();
+        let mut current = vec![&self.root];
+        while !current.is_empty() {
+            levels.push(current.iter().map(|node| node.keys.clone()).collect());
+            current = current.iter().flat_map(|node| node.children.iter()).collect();
+        }
+        levels
+    }
+
+    fn max_keys(&self) -> usize {
+        2 * self.min_degree - 1
+    }
+
+    fn node_mut(&mut self, path: &[usize]) -> &mut Node {
+        path.iter().fold(&mut self.root, |node, &i| &mut node.children[i])
+    }
+
+    /// Render index of the node reached by following child positions `path`
+    /// from the root. Nodes are numbered breadth-first, as in `render_state`.
+    fn render_index(&self, path: &[usize]) -> usize {
+        let mut current: Vec<(&Node, Vec<usize>)> = vec![(&self.root, Vec::new())];
+        let mut offset = 0;
+
+        while !current.is_empty() {
+            if let Some(pos) = current.iter().position(|(_, p)| p.as_slice() == path) {
+                return offset + pos;
+            }
+            offset += current.len();
+            current = current
+                .iter()
+                .flat_map(|(node, p)| {
+                    node.children.iter().enumerate().map(move |(i, child)| {
+                        let mut child_path = p.clone();
+                        child_path.push(i);
+                        (child, child_path)
+                    })
+                })
+                .collect();
+        }
+
+        offset
+    }
+
+    fn keys_text(keys: &[i32]) -> String {
+        keys.iter().map(i32::to_string).collect::<Vec<_>>().join(" | ")
+    }
+
+    fn insert_impl(&mut self, value: i32, mut steps: Option<&mut Vec<Step>>) -> bool {
+        if self.search(value) {
+            if let Some(steps) = steps.as_deref_mut() {
+                steps.push(Step {
+                    description: format!("{} is already in the tree", value),
+                    highlight_indices: vec![],
+                    active_indices: vec![],
+                    metadata: serde_json::json!({
+                        "duplicate": true
+                    }),
+                    detail: None,
+                });
+            }
+            return false;
+        }
+
+        if self.root.keys.len() == self.max_keys() {
+            if let Some(steps) = steps.as_deref_mut() {
+                steps.push(Step {
+                    description: format!(
+                        "Root [{}] is full; a new root is added above it before splitting",
+                        Self::keys_text(&self.root.keys)
+                    ),
+                    highlight_indices: vec![0],
+                    active_indices: vec![],
+                    metadata: serde_json::json!({
+                        "operation": "grow_root",
+                        "keys": self.root.keys
+                    }),
+                    detail: None,
+                });
+            }
+
+            let old_root = std::mem::take(&mut self.root);
+            self.root.children.push(old_root);
+            self.split_child(&[], 0, steps.as_deref_mut());
+        }
+
+        let mut path = Vec::new();
+        loop {
+            let max_keys = self.max_keys();
+            let node = self.node_mut(&path);
+            let mut i = node.keys.partition_point(|&key| key < value);
+
+            if node.is_leaf() {
+                node.keys.insert(i, value);
+                if let Some(steps) = steps.as_deref_mut() {
+                    let keys = self.node_mut(&path).keys.clone();
+                    steps.push(Step {
+                        description: format!("Inserted {} into leaf, now [{}]", value, Self::keys_text(&keys)),
+                        highlight_indices: vec![],
+                        active_indices: vec![self.render_index(&path)],
+                        metadata: serde_json::json!({
+                            "operation": "insert_key",
+                            "value": value,
+                            "keys": keys
+                        }),
+                        detail: None,
+                    });
+                }
+                break;
+            }
+
+            if node.children[i].keys.len() == max_keys {
+                let parent_path = path.clone();
+                self.split_child(&parent_path, i, steps.as_deref_mut());
+                if value > self.node_mut(&path).keys[i] {
+                    i += 1;
+                }
+            }
+
+            if let Some(steps) = steps.as_deref_mut() {
+                let keys = self.node_mut(&path).keys.clone();
+                let mut child_path = path.clone();
+                child_path.push(i);
+                steps.push(Step {
+                    description: format!("{} belongs in child {} of [{}]", value, i, Self::keys_text(&keys)),
+                    highlight_indices: vec![self.render_index(&path), self.render_index(&child_path)],
+                    active_indices: vec![],
+                    metadata: serde_json::json!({
+                        "operation": "descend",
+                        "child": i,
+                        "keys": keys
+                    }),
+                    detail: None,
+                });
+            }
+            path.push(i);
+        }
+
+        self.size += 1;
+        true
+    }
+
+    /// Split the full child `i` of the node at `parent_path`: its median key
+    /// moves up into the parent, the keys and children to its right move
+    /// into a new sibling at position `i + 1`.
+    fn split_child(&mut self, parent_path: &[usize], i: usize, steps: Option<&mut Vec<Step>>) {
+        let t = self.min_degree;
+        let mut child_path = parent_path.to_vec();
+        child_path.push(i);
+        let child_idx = self.render_index(&child_path);
+
+        let parent = self.node_mut(parent_path);
+        let child = &mut parent.children[i];
+        let full_keys = child.keys.clone();
+
+        let right = Node {
+            keys: child.keys.split_off(t),
+            children: if child.is_leaf() { Vec::new() } else { child.children.split_off(t) },
+        };
+        let median = child.keys.pop().unwrap_or_default();
+        let left_keys = child.keys.clone();
+        let right_keys = right.keys.clone();
+
+        parent.keys.insert(i, median);
+        parent.children.insert(i + 1, right);
+
+        let Some(steps) = steps else {
+            return;
+        };
+
+        steps.push(Step {
+            description: format!(
+                "Node [{}] is full ({} keys); its median {} will move up",
+                Self::keys_text(&full_keys),
+                full_keys.len(),
+                median
+            ),
+            highlight_indices: vec![child_idx],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": "split",
+                "keys": full_keys,
+                "median": median
+            }),
+            detail: None,
+        });
+
+        let mut right_path = parent_path.to_vec();
+        right_path.push(i + 1);
+        steps.push(Step {
+            description: format!(
+                "Promoted {} into the parent; split into [{}] and [{}]",
+                median,
+                Self::keys_text(&left_keys),
+                Self::keys_text(&right_keys)
+            ),
+            highlight_indices: vec![self.render_index(parent_path)],
+            active_indices: vec![self.render_index(&child_path), self.render_index(&right_path)],
+            metadata: serde_json::json!({
+                "operation": "promote",
+                "median": median,
+                "left": left_keys,
+                "right": right_keys
+            }),
+            detail: None,
+        });
+    }
+
+    fn search_with_steps(&self, value: i32) -> Vec<Step> {
+        let mut steps = Vec::new();
+        let mut node = &self.root;
+        let mut path = Vec::new();
+
+        loop {
+            let idx = self.render_index(&path);
+            match node.keys.binary_search(&value) {
+                Ok(_) => {
+                    steps.push(Step {
+                        description: format!("Found {} in [{}]", value, Self::keys_text(&node.keys)),
+                        highlight_indices: vec![],
+                        active_indices: vec![idx],
+                        metadata: serde_json::json!({
+                            "found": true
+                        }),
+                        detail: None,
+                    });
+                    return steps;
+                }
+                Err(_) if node.is_leaf() => {
+                    steps.push(Step {
+                        description: format!("{} is not in leaf [{}]; not found", value, Self::keys_text(&node.keys)),
+                        highlight_indices: vec![idx],
+                        active_indices: vec![],
+                        metadata: serde_json::json!({
+                            "found": false
+                        }),
+                        detail: None,
+                    });
+                    return steps;
+                }
+                Err(i) => {
+                    steps.push(Step {
+                        description: format!("{} is not in [{}], following child {}", value, Self::keys_text(&node.keys), i),
+                        highlight_indices: vec![idx],
+                        active_indices: vec![],
+                        metadata: serde_json::json!({
+                            "operation": "descend",
+                            "child": i
+                        }),
+                        detail: None,
+                    });
+                    node = &node.children[i];
+                    path.push(i);
+                }
+            }
+        }
+    }
+}
+
+impl Default for VisualizableBTree {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Visualizable for VisualizableBTree {
+    fn execute_with_steps(&mut self, operation: Operation) -> Result<Vec<Step>> {
+        traced("btree", operation, || match operation {
+            Operation::Insert(_, value) => {
+                let mut steps = vec![Step {
+                    description: format!("Inserting {} into B-tree of minimum degree {}", value, self.min_degree),
+                    highlight_indices: vec![],
+                    active_indices: vec![],
+                    metadata: serde_json::json!({
+                        "operation": "insert",
+                        "value": value,
+                        "max_keys": self.max_keys()
+                    }),
+                    detail: None,
+                }];
+                self.insert_impl(value, Some(&mut steps));
+                Ok(steps)
+            }
+
+            Operation::Search(value) => Ok(self.search_with_steps(value)),
+
+            _ => Err(DsavError::Visualization(
+                "Operation not supported for B-trees".to_string(),
+            )),
+        })
+    }
+
+    fn execute(&mut self, operation: Operation) -> Result<()> {
+        match operation {
+            Operation::Insert(_, value) => {
+                self.insert(value);
+                Ok(())
+            }
+            _ => self.execute_with_steps(operation).map(|_| ()),
+        }
+    }
+
+    /// One element per node, numbered breadth-first. A node's keys are joined
+    /// in its label as `10 | 20 | 30`, its value is the first key and its
+    /// sublabel the key count; full nodes are marked `Comparing`.
+    fn render_state(&self) -> RenderState {
+        if self.is_empty() {
+            return RenderState::empty_placeholder("B-tree is empty", "Insert a value to add keys");
+        }
+
+        let mut elements = Vec::new();
+        let mut connections = Vec::new();
+        let mut current = vec![(&self.root, None)];
+
+        while !current.is_empty() {
+            let mut next = Vec::new();
+
+            for (node, parent) in current {
+                let idx = elements.len();
+                elements.push(
+                    RenderElement::new(node.keys[0])
+                        .with_label(Self::keys_text(&node.keys))
+                        .with_sublabel(format!("{} key(s)", node.keys.len()))
+                        .with_state(if node.keys.len() == self.max_keys() {
+                            ElementState::Comparing
+                        } else {
+                            ElementState::Normal
+                        }),
+                );
+
+                if let Some(parent) = parent {
+                    connections.push((parent, idx));
+                }
+                next.extend(node.children.iter().map(|child| (child, Some(idx))));
+            }
+
+            current = next;
+        }
+
+        RenderState {
+            elements,
+            connections,
+            cols: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Check key counts, key order, child counts and leaf depth, returning
+    /// the depth of the leaves below `node`.
+    fn check_node(node: &Node, t: usize, is_root: bool) -> usize {
+        assert!(node.keys.len() < 2 * t);
+        if !is_root {
+            assert!(node.keys.len() >= t - 1, "node {:?} has too few keys", node.keys);
+        }
+        assert!(node.keys.windows(2).all(|pair| pair[0] < pair[1]));
+
+        if node.is_leaf() {
+            return 1;
+        }
+
+        assert_eq!(node.children.len(), node.keys.len() + 1);
+        for (i, child) in node.children.iter().enumerate() {
+            if i > 0 {
+                assert!(child.keys.iter().all(|&key| key > node.keys[i - 1]));
+            }
+            if i < node.keys.len() {
+                assert!(child.keys.iter().all(|&key| key < node.keys[i]));
+            }
+        }
+
+        let depths: Vec<usize> = node.children.iter().map(|child| check_node(child, t, false)).collect();
+        assert!(depths.windows(2).all(|pair| pair[0] == pair[1]), "leaves at different depths");
+        depths[0] + 1
+    }
+
+    #[test]
+    fn test_btree_invariants_hold() {
+        for t in 2..=4 {
+            let mut tree = VisualizableBTree::with_min_degree(t);
+            for value in (0..200).map(|i| (i * 37) % 200) {
+                assert!(tree.insert(value));
+                check_node(&tree.root, t, true);
+            }
+
+            assert_eq!(tree.len(), 200);
+            assert!(!tree.insert(37));
+            assert!((0..200).all(|value| tree.search(value)));
+            assert!(!tree.search(200));
+        }
+    }
+
+    #[test]
+    fn test_btree_split_steps() {
+        let mut tree = VisualizableBTree::new();
+        for value in [10, 20, 30] {
+            tree.insert(value);
+        }
+        assert_eq!(tree.levels(), vec![vec![vec![10, 20, 30]]]);
+
+        // The root is full, so inserting splits it around the median 20
+        let steps = tree.execute_with_steps(Operation::Insert(0, 40)).unwrap();
+        let promote = steps.iter().find(|s| s.metadata["operation"] == "promote").unwrap();
+        assert_eq!(promote.metadata["median"], 20);
+        assert_eq!(promote.active_indices, vec![1, 2]);
+
+        assert_eq!(tree.levels(), vec![vec![vec![20]], vec![vec![10], vec![30, 40]]]);
+        assert_eq!(tree.height(), 2);
+    }
+
+    #[test]
+    fn test_btree_render_state() {
+        let mut tree = VisualizableBTree::new();
+        for value in [10, 20, 30, 40, 50] {
+            tree.insert(value);
+        }
+
+        let state = tree.render_state();
+        let labels: Vec<&str> = state.elements.iter().map(|elem| elem.label.as_str()).collect();
+        assert_eq!(labels, vec!["20", "10", "30 | 40 | 50"]);
+        assert_eq!(state.connections, vec![(0, 1), (0, 2)]);
+        assert_eq!(state.elements[2].state, ElementState::Comparing);
+
+        let steps = tree.execute_with_steps(Operation::Search(50)).unwrap();
+        assert_eq!(steps.last().unwrap().active_indices, vec![2]);
+    }
+}
@@ -0,0 +1,413 @@
+//! Educational gap buffer implementation with visualization support.
+//!
+//! Text editors keep an empty "gap" at the cursor so typing there is O(1).
+//! Editing elsewhere first moves the gap, shifting every element between the
+//! old and new position across it; this contrasts with a plain array, which
+//! shifts the whole tail on every insert.
+
+use crate::error::{DsavError, Result};
+use crate::state::{ElementState, RenderElement, RenderState};
+use crate::instrument::traced;
+use crate::traits::{Operation, Step, Visualizable};
+
+const DEFAULT_CAPACITY: usize = 8;
+
+#[derive(Debug, Clone)]
+pub struct VisualizableGapBuffer {
+    /// Backing storage; slots in `gap_start..gap_end` hold no element.
+    buffer: Vec<i32>,
+    gap_start: usize,
+    gap_end: usize,
+}
+
+impl VisualizableGapBuffer {
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+
+        Self {
+            buffer: vec![0; capacity],
+            gap_start: 0,
+            gap_end: capacity,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.buffer.len() - self.gap_len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
+    fn gap_len(&self) -> usize {
+        self.gap_end - self.gap_start
+    }
+
+    /// Logical index where the gap (the cursor) currently sits.
+    pub fn cursor(&self) -> usize {
+        self.gap_start
+    }
+
+    /// The elements in order, skipping the gap.
+    pub fn to_vec(&self) -> Vec<i32> {
+        let mut values = self.buffer[..self.gap_start].to_vec();
+        values.extend_from_slice(&self.buffer[self.gap_end..]);
+        values
+    }
+
+    pub fn get(&self, index: usize) -> Result<i32> {
+        self.check_index(index, self.len())?;
+
+        Ok(if index < self.gap_start {
+            self.buffer[index]
+        } else {
+            self.buffer[index + self.gap_len()]
+        })
+    }
+
+    pub fn insert_at(&mut self, index: usize, value: i32) -> Result<()> {
+        self.insert_impl(index, value, None)
+    }
+
+    pub fn delete_at(&mut self, index: usize) -> Result<i32> {
+        self.delete_impl(index, None)
+    }
+
+    fn check_index(&self, index: usize, limit: usize) -> Result<()> {
+        if index >= limit {
+            return Err(DsavError::IndexOutOfBounds {
+                index,
+                size: self.len(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Snapshot of the buffer with `null` in the gap slots.
+    fn buffer_state(&self) -> serde_json::Value {
+        let slots: Vec<Option<i32>> = self
+            .buffer
+            .iter()
+            .enumerate()
+            .map(|(i, &value)| (!(self.gap_start..self.gap_end).contains(&i)).then_some(value))
+            .collect();
+        serde_json::json!(slots)
+    }
+
+    /// Move the gap so it starts at logical index `index`, one element at a time.
+    fn move_gap(&mut self, index: usize, mut steps: Option<&mut Vec<Step>>) {
+        if index != self.gap_start {
+            if let Some(steps) = steps.as_deref_mut() {
+                steps.push(Step {
+                    description: format!("Moving gap from {} to {}", self.gap_start, index),
+                    highlight_indices: vec![],
+                    active_indices: (self.gap_start..self.gap_end).collect(),
+                    metadata: serde_json::json!({
+                        "operation": "move_gap",
+                        "from": self.gap_start,
+                        "to": index,
+                        "buffer_state": self.buffer_state()
+                    }),
+                    detail: None,
+                });
+            }
+        }
+
+        while self.gap_start > index {
+            // Shift the element left of the gap to its right end
+            self.gap_start -= 1;
+            self.gap_end -= 1;
+            self.buffer[self.gap_end] = self.buffer[self.gap_start];
+            self.record_shift(self.gap_start, self.gap_end, steps.as_deref_mut());
+        }
+
+        while self.gap_start < index {
+            // Shift the element right of the gap to its left end
+            self.buffer[self.gap_start] = self.buffer[self.gap_end];
+            self.gap_start += 1;
+            self.gap_end += 1;
+            self.record_shift(self.gap_end - 1, self.gap_start - 1, steps.as_deref_mut());
+        }
+    }
+
+    fn record_shift(&self, from: usize, to: usize, steps: Option<&mut Vec<Step>>) {
+        if let Some(steps) = steps {
+            steps.push(Step {
+                description: format!("Shifting {} across the gap (slot {} to {})", self.buffer[to], from, to),
+                highlight_indices: vec![to],
+                active_indices: (self.gap_start..self.gap_end).collect(),
+                metadata: serde_json::json!({
+                    "operation": "shift",
+                    "from": from,
+                    "to": to,
+                    "buffer_state": self.buffer_state()
+                }),
+                detail: None,
+            });
+        }
+    }
+
+    /// Double the capacity, keeping the gap at the same logical position.
+    fn grow(&mut self, steps: Option<&mut Vec<Step>>) {
+        let old_capacity = self.buffer.len();
+        let new_capacity = old_capacity * 2;
+        let tail = self.buffer.split_off(self.gap_end);
+
+        self.buffer.resize(new_capacity - tail.len(), 0);
+        self.gap_end = self.buffer.len();
+        self.buffer.extend(tail);
+
+        if let Some(steps) = steps {
+            steps.push(Step {
+                description: format!("Gap is full, growing buffer from {} to {} slots", old_capacity, new_capacity),
+                highlight_indices: vec![],
+                active_indices: (self.gap_start..self.gap_end).collect(),
+                metadata: serde_json::json!({
+                    "operation": "grow",
+                    "capacity": new_capacity,
+                    "buffer_state": self.buffer_state()
+                }),
+                detail: None,
+            });
+        }
+    }
+
+    fn insert_impl(&mut self, index: usize, value: i32, mut steps: Option<&mut Vec<Step>>) -> Result<()> {
+        self.check_index(index, self.len() + 1)?;
+
+        self.move_gap(index, steps.as_deref_mut());
+        if self.gap_len() == 0 {
+            self.grow(steps.as_deref_mut());
+        }
+
+        self.buffer[self.gap_start] = value;
+        self.gap_start += 1;
+
+        if let Some(steps) = steps {
+            steps.push(Step {
+                description: format!("Wrote {} into the gap at {}", value, index),
+                highlight_indices: vec![],
+                active_indices: vec![index],
+                metadata: serde_json::json!({
+                    "operation": "write",
+                    "value": value,
+                    "buffer_state": self.buffer_state()
+                }),
+                detail: None,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn delete_impl(&mut self, index: usize, mut steps: Option<&mut Vec<Step>>) -> Result<i32> {
+        self.check_index(index, self.len())?;
+
+        // Put the gap right after the element so deleting just widens it
+        self.move_gap(index + 1, steps.as_deref_mut());
+        self.gap_start -= 1;
+        let value = self.buffer[self.gap_start];
+
+        if let Some(steps) = steps {
+            steps.push(Step {
+                description: format!("Deleted {} by widening the gap", value),
+                highlight_indices: vec![],
+                active_indices: (self.gap_start..self.gap_end).collect(),
+                metadata: serde_json::json!({
+                    "operation": "delete",
+                    "value": value,
+                    "buffer_state": self.buffer_state()
+                }),
+                detail: None,
+            });
+        }
+
+        Ok(value)
+    }
+}
+
+impl Default for VisualizableGapBuffer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Visualizable for VisualizableGapBuffer {
+    fn execute_with_steps(&mut self, operation: Operation) -> Result<Vec<Step>> {
+        traced("gap_buffer", operation, || {
+            let mut steps = Vec::new();
+
+            match operation {
+                Operation::Insert(index, value) => {
+                    steps.push(Step {
+                        description: format!("Inserting {} at index {} (gap is at {})", value, index, self.gap_start),
+                        highlight_indices: vec![],
+                        active_indices: (self.gap_start..self.gap_end).collect(),
+                        metadata: serde_json::json!({
+                            "operation": "insert",
+                            "index": index,
+                            "value": value,
+                            "buffer_state": self.buffer_state()
+                        }),
+                        detail: None,
+                    });
+                    self.insert_impl(index, value, Some(&mut steps))?;
+                }
+
+                Operation::Delete(index) => {
+                    steps.push(Step {
+                        description: format!("Deleting index {} (gap is at {})", index, self.gap_start),
+                        highlight_indices: vec![],
+                        active_indices: (self.gap_start..self.gap_end).collect(),
+                        metadata: serde_json::json!({
+                            "operation": "delete",
+                            "index": index,
+                            "buffer_state": self.buffer_state()
+                        }),
+                        detail: None,
+                    });
+                    self.delete_impl(index, Some(&mut steps))?;
+                }
+
+                _ => {
+                    return Err(DsavError::InvalidState {
+                        reason: "Operation not supported for gap buffers".to_string(),
+                    })
+                }
+            }
+
+            Ok(steps)
+        })
+    }
+
+    fn execute(&mut self, operation: Operation) -> Result<()> {
+        match operation {
+            Operation::Insert(index, value) => self.insert_at(index, value),
+            Operation::Delete(index) => self.delete_at(index).map(|_| ()),
+            _ => self.execute_with_steps(operation).map(|_| ()),
+        }
+    }
+
+    /// One element per buffer slot; gap slots are blank placeholders so the
+    /// gap's size and position are visible.
+    fn render_state(&self) -> RenderState {
+        if self.is_empty() {
+            return RenderState::empty_placeholder("Gap buffer is empty", "Insert a value to add elements");
+        }
+
+        let elements = self
+            .buffer
+            .iter()
+            .enumerate()
+            .map(|(i, &value)| {
+                if (self.gap_start..self.gap_end).contains(&i) {
+                    RenderElement::new(0)
+                        .with_label(String::new())
+                        .with_sublabel("gap".to_string())
+                        .with_state(ElementState::Gap)
+                } else {
+                    RenderElement::new(value)
+                        .with_label(value.to_string())
+                        .with_sublabel(format!("[{}]", i))
+                }
+            })
+            .collect();
+
+        RenderState {
+            elements,
+            connections: Vec::new(),
+            cols: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shift_count(steps: &[Step]) -> usize {
+        steps.iter().filter(|s| s.metadata["operation"] == "shift").count()
+    }
+
+    fn filled(values: &[i32]) -> VisualizableGapBuffer {
+        let mut buffer = VisualizableGapBuffer::with_capacity(16);
+        for (i, &value) in values.iter().enumerate() {
+            buffer.insert_at(i, value).unwrap();
+        }
+        buffer
+    }
+
+    #[test]
+    fn test_gap_buffer_insert_and_delete() {
+        let mut buffer = filled(&[1, 2, 3]);
+        buffer.insert_at(1, 9).unwrap();
+        assert_eq!(buffer.to_vec(), vec![1, 9, 2, 3]);
+
+        assert_eq!(buffer.delete_at(3).unwrap(), 3);
+        assert_eq!(buffer.delete_at(0).unwrap(), 1);
+        assert_eq!(buffer.to_vec(), vec![9, 2]);
+        assert_eq!(buffer.get(1).unwrap(), 2);
+
+        assert!(buffer.insert_at(5, 0).is_err());
+        assert!(buffer.delete_at(2).is_err());
+    }
+
+    #[test]
+    fn test_gap_buffer_insert_at_gap_is_cheap() {
+        let mut buffer = filled(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(buffer.cursor(), 8);
+
+        let steps = buffer.execute_with_steps(Operation::Insert(8, 9)).unwrap();
+        assert_eq!(shift_count(&steps), 0);
+    }
+
+    #[test]
+    fn test_gap_buffer_insert_far_from_gap_shifts() {
+        let mut buffer = filled(&[1, 2, 3, 4, 5, 6, 7, 8]);
+
+        let steps = buffer.execute_with_steps(Operation::Insert(0, 0)).unwrap();
+        assert_eq!(shift_count(&steps), 8);
+        assert_eq!(buffer.to_vec(), vec![0, 1, 2, 3, 4, 5, 6, 7, 8]);
+
+        // The gap now follows the cursor, so typing on is cheap again
+        let steps = buffer.execute_with_steps(Operation::Insert(1, 10)).unwrap();
+        assert_eq!(shift_count(&steps), 0);
+    }
+
+    #[test]
+    fn test_gap_buffer_grows() {
+        let mut buffer = VisualizableGapBuffer::with_capacity(2);
+        for i in 0..5 {
+            buffer.insert_at(i, i as i32).unwrap();
+        }
+
+        assert_eq!(buffer.to_vec(), vec![0, 1, 2, 3, 4]);
+        assert!(buffer.capacity() >= 5);
+    }
+
+    #[test]
+    fn test_gap_buffer_render_state() {
+        let mut buffer = VisualizableGapBuffer::with_capacity(4);
+        assert!(buffer.render_state().is_empty_placeholder());
+
+        buffer.insert_at(0, 7).unwrap();
+        let state = buffer.render_state();
+        assert_eq!(state.elements.len(), 4);
+        assert_eq!(state.elements[0].label, "7");
+        assert!(state.elements[1..].iter().all(|e| e.state == ElementState::Gap));
+
+        // Filling a gap slot shows up as a change, not as an empty structure
+        buffer.insert_at(1, 8).unwrap();
+        let filled = buffer.render_state();
+        assert!(!filled.is_empty_placeholder());
+        assert_eq!(state.diff(&filled).added, vec![1]);
+    }
+}
@@ -5,6 +5,7 @@
 
 use crate::error::{DsavError, Result};
 use crate::state::{RenderElement, RenderState};
+use crate::instrument::traced;
 use crate::traits::{Operation, Step, Visualizable};
 
 #[derive(Debug, Clone)]
@@ -43,14 +44,13 @@ impl VisualizableLinkedList {
     pub fn insert_back(&mut self, value: i32) {
         let new_node = Box::new(Node::new(value));
 
-        if self.head.is_none() {
-            self.head = Some(new_node);
-        } else {
-            let mut current = self.head.as_mut().unwrap();
+        if let Some(mut current) = self.head.as_mut() {
             while current.next.is_some() {
                 current = current.next.as_mut().unwrap();
             }
             current.next = Some(new_node);
+        } else {
+            self.head = Some(new_node);
         }
 
         self.length += 1;
@@ -88,6 +88,133 @@ impl VisualizableLinkedList {
         Ok(())
     }
 
+    /// Detach the nodes from `index` onward and return them as a new list.
+    pub fn split_at(&mut self, index: usize) -> Result<VisualizableLinkedList> {
+        if index > self.length {
+            return Err(DsavError::IndexOutOfBounds {
+                index,
+                size: self.length,
+            });
+        }
+
+        let tail_head = if index == 0 {
+            self.head.take()
+        } else {
+            let mut current = self.head.as_mut().unwrap();
+            for _ in 0..index - 1 {
+                current = current.next.as_mut().unwrap();
+            }
+            current.next.take()
+        };
+
+        let tail = VisualizableLinkedList {
+            head: tail_head,
+            length: self.length - index,
+        };
+        self.length = index;
+
+        Ok(tail)
+    }
+
+    /// Move every node of `other` onto the end of this list, undoing a
+    /// [`split_at`](Self::split_at).
+    pub fn append(&mut self, mut other: VisualizableLinkedList) {
+        let mut slot = &mut self.head;
+        while let Some(node) = slot {
+            slot = &mut node.next;
+        }
+        *slot = other.head.take();
+        self.length += other.length;
+    }
+
+    /// Split at `index` like [`split_at`](Self::split_at), animating the walk
+    /// to the split point and the pointer cut.
+    pub fn split_at_with_steps(&mut self, index: usize) -> Result<(VisualizableLinkedList, Vec<Step>)> {
+        if index > self.length {
+            return Err(DsavError::IndexOutOfBounds {
+                index,
+                size: self.length,
+            });
+        }
+
+        let mut steps = Vec::new();
+
+        steps.push(Step {
+            description: format!("Splitting list at position {}", index),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": "split",
+                "index": index
+            }),
+            detail: None,
+        });
+
+        if index == 0 {
+            steps.push(Step {
+                description: "Split at head: the whole list moves to the new list".to_string(),
+                highlight_indices: vec![],
+                active_indices: (0..self.length).collect(),
+                metadata: serde_json::json!({}),
+                detail: None,
+            });
+        } else {
+            for i in 0..index {
+                steps.push(Step {
+                    description: format!("Traversing to position {}", i),
+                    highlight_indices: vec![i],
+                    active_indices: vec![],
+                    metadata: serde_json::json!({}),
+                    detail: None,
+                });
+            }
+
+            steps.push(Step {
+                description: format!("Cutting the next pointer of node {}", index - 1),
+                highlight_indices: vec![index - 1],
+                active_indices: (index..self.length).collect(),
+                metadata: serde_json::json!({}),
+                detail: None,
+            });
+        }
+
+        let tail = self.split_at(index)?;
+
+        steps.push(Step {
+            description: format!(
+                "Split complete: {} node(s) kept, {} node(s) detached",
+                self.length, tail.length
+            ),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "front": self.to_vec(),
+                "tail": tail.to_vec()
+            }),
+            detail: None,
+        });
+
+        Ok((tail, steps))
+    }
+
+    /// Move the first `k` nodes (modulo the length) to the end of the list.
+    pub fn rotate_left(&mut self, k: usize) {
+        if self.length == 0 || k % self.length == 0 {
+            return;
+        }
+
+        let mut front = self.head.take();
+        let mut back = Self::split_nodes(&mut front, k % self.length);
+
+        let mut tail = &mut back;
+        while tail.is_some() {
+            tail = &mut tail.as_mut().unwrap().next;
+        }
+        *tail = front;
+
+        self.head = back;
+    }
+
     pub fn delete_front(&mut self) -> Result<i32> {
         if let Some(mut old_head) = self.head.take() {
             self.head = old_head.next.take();
@@ -168,6 +295,22 @@ impl VisualizableLinkedList {
         Ok(old_value)
     }
 
+    /// Replace every occurrence of `old` with `new`, returning how many were replaced.
+    pub fn replace_all(&mut self, old: i32, new: i32) -> usize {
+        let mut count = 0;
+        let mut current = self.head.as_mut();
+
+        while let Some(node) = current {
+            if node.value == old {
+                node.value = new;
+                count += 1;
+            }
+            current = node.next.as_mut();
+        }
+
+        count
+    }
+
     pub fn len(&self) -> usize {
         self.length
     }
@@ -181,7 +324,15 @@ impl VisualizableLinkedList {
         self.length = 0;
     }
 
-    fn to_vec(&self) -> Vec<i32> {
+    /// Format the list as `10 -> 20 -> 30 -> NULL`.
+    pub fn pretty_print(&self) -> String {
+        let mut parts: Vec<String> = self.to_vec().iter().map(|v| v.to_string()).collect();
+        parts.push("NULL".to_string());
+        parts.join(" -> ")
+    }
+
+    /// The values from head to tail.
+    pub fn to_vec(&self) -> Vec<i32> {
         let mut result = Vec::new();
         let mut current = self.head.as_ref();
 
@@ -194,6 +345,154 @@ impl VisualizableLinkedList {
     }
 }
 
+// List-native merge sort
+impl VisualizableLinkedList {
+    /// Sort the list with merge sort, animating each split and merge.
+    ///
+    /// Merge sort only walks the list sequentially, so it suits linked lists
+    /// where random access is expensive.
+    pub fn merge_sort_with_steps(&mut self) -> Result<Vec<Step>> {
+        let mut steps = Vec::new();
+
+        steps.push(Step {
+            description: "Starting merge sort on linked list".to_string(),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": "merge_sort",
+                "list_state": self.to_vec()
+            }),
+            detail: None,
+        });
+
+        let head = self.head.take();
+        self.head = Self::merge_sort_nodes(head, 0, 0, &mut steps);
+
+        steps.push(Step {
+            description: "Merge sort complete".to_string(),
+            highlight_indices: vec![],
+            active_indices: (0..self.length).collect(),
+            metadata: serde_json::json!({
+                "list_state": self.to_vec()
+            }),
+            detail: None,
+        });
+
+        Ok(steps)
+    }
+
+    /// Sort the sublist starting at position `offset` of the original list.
+    fn merge_sort_nodes(
+        mut head: Option<Box<Node>>,
+        offset: usize,
+        depth: usize,
+        steps: &mut Vec<Step>,
+    ) -> Option<Box<Node>> {
+        if head.as_ref().is_none_or(|n| n.next.is_none()) {
+            return head;
+        }
+
+        // Fast pointer moves two nodes for every one the slow pointer moves,
+        // so when it runs off the end `mid` counts the nodes up to the middle
+        let mut mid = 1;
+        let mut fast = head.as_ref().and_then(|n| n.next.as_ref());
+        while let Some(next) = fast.and_then(|n| n.next.as_ref()) {
+            fast = next.next.as_ref();
+            mid += 1;
+        }
+
+        let values = Self::node_values(&head);
+        let right = Self::split_nodes(&mut head, mid);
+        let left_values = Self::node_values(&head);
+        let right_values = Self::node_values(&right);
+        let len = values.len();
+
+        steps.push(Step {
+            description: format!("Splitting {:?} into {:?} and {:?}", values, left_values, right_values),
+            highlight_indices: (offset..offset + mid).collect(),
+            active_indices: (offset + mid..offset + len).collect(),
+            metadata: serde_json::json!({
+                "operation": "split",
+                "depth": depth,
+                "left": left_values,
+                "right": right_values
+            }),
+            detail: None,
+        });
+
+        let left = Self::merge_sort_nodes(head, offset, depth + 1, steps);
+        let right = Self::merge_sort_nodes(right, offset + mid, depth + 1, steps);
+
+        let left_values = Self::node_values(&left);
+        let right_values = Self::node_values(&right);
+        let merged = Self::merge_sorted(left, right);
+        let merged_values = Self::node_values(&merged);
+
+        steps.push(Step {
+            description: format!("Merging {:?} and {:?} into {:?}", left_values, right_values, merged_values),
+            highlight_indices: vec![],
+            active_indices: (offset..offset + len).collect(),
+            metadata: serde_json::json!({
+                "operation": "merge",
+                "depth": depth,
+                "left": left_values,
+                "right": right_values,
+                "merged": merged_values
+            }),
+            detail: None,
+        });
+
+        merged
+    }
+
+    /// Detach and return the nodes after the first `at` nodes (`at >= 1`).
+    fn split_nodes(head: &mut Option<Box<Node>>, at: usize) -> Option<Box<Node>> {
+        let mut current = head.as_mut()?;
+        for _ in 0..at - 1 {
+            current = current.next.as_mut()?;
+        }
+        current.next.take()
+    }
+
+    /// Merge two sorted sublists by relinking their nodes.
+    fn merge_sorted(mut left: Option<Box<Node>>, mut right: Option<Box<Node>>) -> Option<Box<Node>> {
+        let mut head = None;
+        let mut tail = &mut head;
+
+        loop {
+            let next = match (left.take(), right.take()) {
+                (Some(mut l), Some(r)) if l.value <= r.value => {
+                    left = l.next.take();
+                    right = Some(r);
+                    l
+                }
+                (Some(l), Some(mut r)) => {
+                    right = r.next.take();
+                    left = Some(l);
+                    r
+                }
+                (rest, None) | (None, rest) => {
+                    *tail = rest;
+                    break;
+                }
+            };
+
+            tail = &mut tail.insert(next).next;
+        }
+
+        head
+    }
+
+    fn node_values(mut node: &Option<Box<Node>>) -> Vec<i32> {
+        let mut values = Vec::new();
+        while let Some(n) = node {
+            values.push(n.value);
+            node = &n.next;
+        }
+        values
+    }
+}
+
 impl Default for VisualizableLinkedList {
     fn default() -> Self {
         Self::new()
@@ -202,7 +501,7 @@ impl Default for VisualizableLinkedList {
 
 impl Visualizable for VisualizableLinkedList {
     fn execute_with_steps(&mut self, operation: Operation) -> Result<Vec<Step>> {
-        match operation {
+        traced("linked_list", operation, || match operation {
             Operation::Insert(index, value) => {
                 let mut steps = Vec::new();
 
@@ -215,6 +514,7 @@ impl Visualizable for VisualizableLinkedList {
                         "value": value,
                         "index": index
                     }),
+                    detail: None,
                 });
 
                 if index == 0 {
@@ -223,6 +523,7 @@ impl Visualizable for VisualizableLinkedList {
                         highlight_indices: vec![0],
                         active_indices: vec![],
                         metadata: serde_json::json!({}),
+                        detail: None,
                     });
 
                     self.insert_front(value);
@@ -233,6 +534,7 @@ impl Visualizable for VisualizableLinkedList {
                             highlight_indices: vec![i],
                             active_indices: vec![],
                             metadata: serde_json::json!({}),
+                            detail: None,
                         });
                     }
 
@@ -244,6 +546,7 @@ impl Visualizable for VisualizableLinkedList {
                     highlight_indices: vec![],
                     active_indices: vec![index],
                     metadata: serde_json::json!({}),
+                    detail: None,
                 });
 
                 Ok(steps)
@@ -269,6 +572,7 @@ impl Visualizable for VisualizableLinkedList {
                         "operation": "delete",
                         "index": index
                     }),
+                    detail: None,
                 });
 
                 for i in 0..index {
@@ -277,6 +581,7 @@ impl Visualizable for VisualizableLinkedList {
                         highlight_indices: vec![i],
                         active_indices: vec![],
                         metadata: serde_json::json!({}),
+                        detail: None,
                     });
                 }
 
@@ -298,6 +603,7 @@ impl Visualizable for VisualizableLinkedList {
                     highlight_indices: vec![],
                     active_indices: vec![],
                     metadata: serde_json::json!({}),
+                    detail: None,
                 });
 
                 Ok(steps)
@@ -314,6 +620,7 @@ impl Visualizable for VisualizableLinkedList {
                         "operation": "search",
                         "target": target
                     }),
+                    detail: None,
                 });
 
                 let mut current = self.head.as_ref();
@@ -326,6 +633,7 @@ impl Visualizable for VisualizableLinkedList {
                         highlight_indices: vec![index],
                         active_indices: vec![],
                         metadata: serde_json::json!({}),
+                        detail: None,
                     });
 
                     if node.value == target {
@@ -337,6 +645,7 @@ impl Visualizable for VisualizableLinkedList {
                                 "found": true,
                                 "index": index
                             }),
+                            detail: None,
                         });
                         found = true;
                         break;
@@ -354,6 +663,7 @@ impl Visualizable for VisualizableLinkedList {
                         metadata: serde_json::json!({
                             "found": false
                         }),
+                        detail: None,
                     });
                 }
 
@@ -382,6 +692,7 @@ impl Visualizable for VisualizableLinkedList {
                         "old_value": old_value,
                         "new_value": value
                     }),
+                    detail: None,
                 });
 
                 for i in 0..index {
@@ -390,6 +701,7 @@ impl Visualizable for VisualizableLinkedList {
                         highlight_indices: vec![i],
                         active_indices: vec![],
                         metadata: serde_json::json!({}),
+                        detail: None,
                     });
                 }
 
@@ -400,11 +712,167 @@ impl Visualizable for VisualizableLinkedList {
                     highlight_indices: vec![],
                     active_indices: vec![index],
                     metadata: serde_json::json!({}),
+                    detail: None,
+                });
+
+                Ok(steps)
+            }
+
+            Operation::Replace(old, new) => {
+                let mut steps = Vec::new();
+                let mut replaced = Vec::new();
+
+                steps.push(Step {
+                    description: format!("Replacing all {} with {}", old, new),
+                    highlight_indices: vec![],
+                    active_indices: vec![],
+                    metadata: serde_json::json!({
+                        "operation": "replace",
+                        "old_value": old,
+                        "new_value": new
+                    }),
+                    detail: None,
+                });
+
+                let mut index = 0;
+                let mut current = self.head.as_mut();
+
+                while let Some(node) = current {
+                    steps.push(Step {
+                        description: format!("Checking node at position {} (value: {})", index, node.value),
+                        highlight_indices: vec![index],
+                        active_indices: replaced.clone(),
+                        metadata: serde_json::json!({}),
+                        detail: None,
+                    });
+
+                    if node.value == old {
+                        node.value = new;
+                        replaced.push(index);
+
+                        steps.push(Step {
+                            description: format!("Replaced {} with {} at position {}", old, new, index),
+                            highlight_indices: vec![],
+                            active_indices: replaced.clone(),
+                            metadata: serde_json::json!({
+                                "index": index
+                            }),
+                            detail: None,
+                        });
+                    }
+
+                    current = node.next.as_mut();
+                    index += 1;
+                }
+
+                steps.push(Step {
+                    description: format!("Replaced {} occurrence(s) of {} with {}", replaced.len(), old, new),
+                    highlight_indices: vec![],
+                    active_indices: replaced.clone(),
+                    metadata: serde_json::json!({
+                        "replacements": replaced.len(),
+                        "list_state": self.to_vec()
+                    }),
+                    detail: None,
+                });
+
+                Ok(steps)
+            }
+
+            Operation::MergeSort => self.merge_sort_with_steps(),
+
+            Operation::RotateLeft(k) => {
+                let mut steps = Vec::new();
+                let shift = if self.length == 0 { 0 } else { k % self.length };
+
+                steps.push(Step {
+                    description: format!("Rotating list left by {} ({} mod {} = {})", k, k, self.length, shift),
+                    highlight_indices: vec![],
+                    active_indices: vec![],
+                    metadata: serde_json::json!({
+                        "operation": "rotate_left",
+                        "k": k,
+                        "shift": shift
+                    }),
+                    detail: None,
+                });
+
+                if shift == 0 {
+                    steps.push(Step {
+                        description: "Shift is 0, list is unchanged".to_string(),
+                        highlight_indices: vec![],
+                        active_indices: vec![],
+                        metadata: serde_json::json!({
+                            "list_state": self.to_vec()
+                        }),
+                        detail: None,
+                    });
+                    return Ok(steps);
+                }
+
+                for i in 0..shift {
+                    steps.push(Step {
+                        description: format!("Traversing to position {}", i),
+                        highlight_indices: vec![i],
+                        active_indices: vec![],
+                        metadata: serde_json::json!({}),
+                        detail: None,
+                    });
+                }
+
+                steps.push(Step {
+                    description: format!(
+                        "Cutting after node {}: node {} becomes the new head",
+                        shift - 1, shift
+                    ),
+                    highlight_indices: vec![shift - 1],
+                    active_indices: vec![shift],
+                    metadata: serde_json::json!({}),
+                    detail: None,
+                });
+
+                for i in shift..self.length {
+                    steps.push(Step {
+                        description: format!("Walking to the tail, at position {}", i),
+                        highlight_indices: vec![i],
+                        active_indices: vec![shift],
+                        metadata: serde_json::json!({}),
+                        detail: None,
+                    });
+                }
+
+                steps.push(Step {
+                    description: "Linking the old tail to the old head".to_string(),
+                    highlight_indices: vec![self.length - 1],
+                    active_indices: vec![0],
+                    metadata: serde_json::json!({}),
+                    detail: None,
                 });
 
+                self.rotate_left(shift);
+
+                steps.push(Step {
+                    description: format!("Rotation complete, head is now {}", self.get(0)?),
+                    highlight_indices: vec![],
+                    active_indices: vec![0],
+                    metadata: serde_json::json!({
+                        "list_state": self.to_vec()
+                    }),
+                    detail: None,
+                });
+
+                Ok(steps)
+            }
+
+            Operation::SplitAt(index) => {
+                // The tail is only reported in the step metadata here; callers
+                // that keep it use split_at_with_steps directly
+                let (_, steps) = self.split_at_with_steps(index)?;
                 Ok(steps)
             }
 
+            Operation::Traverse if self.head.is_none() => Ok(vec![Step::empty_traversal()]),
+
             Operation::Traverse => {
                 let mut steps = Vec::new();
 
@@ -415,6 +883,7 @@ impl Visualizable for VisualizableLinkedList {
                     metadata: serde_json::json!({
                         "operation": "traverse"
                     }),
+                    detail: None,
                 });
 
                 let mut current = self.head.as_ref();
@@ -429,6 +898,7 @@ impl Visualizable for VisualizableLinkedList {
                             "index": index,
                             "value": node.value
                         }),
+                        detail: None,
                     });
 
                     current = node.next.as_ref();
@@ -440,6 +910,7 @@ impl Visualizable for VisualizableLinkedList {
                     highlight_indices: vec![],
                     active_indices: vec![],
                     metadata: serde_json::json!({}),
+                    detail: None,
                 });
 
                 Ok(steps)
@@ -448,10 +919,14 @@ impl Visualizable for VisualizableLinkedList {
             _ => Err(DsavError::Visualization(
                 "Operation not supported for linked list".to_string(),
             )),
-        }
+        })
     }
 
     fn render_state(&self) -> RenderState {
+        if self.is_empty() {
+            return RenderState::empty_placeholder("Linked list is empty", "Insert a node at the front or back");
+        }
+
         let elements: Vec<RenderElement> = self
             .to_vec()
             .into_iter()
@@ -469,6 +944,7 @@ impl Visualizable for VisualizableLinkedList {
         RenderState {
             elements,
             connections,
+            cols: None,
         }
     }
 }
@@ -565,4 +1041,153 @@ mod tests {
         assert!(list.delete_front().is_err());
         assert!(list.delete_back().is_err());
     }
+
+    #[test]
+    fn test_linked_list_pretty_print() {
+        let mut list = VisualizableLinkedList::new();
+        assert_eq!(list.pretty_print(), "NULL");
+
+        list.insert_back(10);
+        list.insert_back(20);
+        list.insert_back(30);
+        assert_eq!(list.pretty_print(), "10 -> 20 -> 30 -> NULL");
+    }
+
+    #[test]
+    fn test_linked_list_replace_all_occurrences() {
+        let mut list = VisualizableLinkedList::new();
+        for value in [2, 1, 2, 3] {
+            list.insert_back(value);
+        }
+
+        let steps = list.execute_with_steps(Operation::Replace(2, 9)).unwrap();
+        assert_eq!(list.to_vec(), vec![9, 1, 9, 3]);
+
+        let last = steps.last().unwrap();
+        assert_eq!(last.metadata["replacements"], 2);
+        assert_eq!(last.active_indices, vec![0, 2]);
+
+        assert_eq!(list.replace_all(9, 4), 2);
+        assert_eq!(list.pretty_print(), "4 -> 1 -> 4 -> 3 -> NULL");
+    }
+
+    #[test]
+    fn test_linked_list_split_at_middle() {
+        let mut list = VisualizableLinkedList::new();
+        for value in [1, 2, 3, 4] {
+            list.insert_back(value);
+        }
+
+        let (tail, steps) = list.split_at_with_steps(2).unwrap();
+        assert_eq!(list.to_vec(), vec![1, 2]);
+        assert_eq!(tail.to_vec(), vec![3, 4]);
+        assert_eq!(list.len(), 2);
+        assert_eq!(tail.len(), 2);
+        assert_eq!(steps.last().unwrap().metadata["tail"], serde_json::json!([3, 4]));
+    }
+
+    #[test]
+    fn test_linked_list_split_at_ends() {
+        let mut list = VisualizableLinkedList::new();
+        for value in [1, 2, 3, 4] {
+            list.insert_back(value);
+        }
+
+        let empty = list.split_at(4).unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(list.to_vec(), vec![1, 2, 3, 4]);
+
+        let whole = list.split_at(0).unwrap();
+        assert!(list.is_empty());
+        assert_eq!(whole.to_vec(), vec![1, 2, 3, 4]);
+        assert_eq!(whole.len(), 4);
+
+        assert!(list.split_at(1).is_err());
+
+        list.append(whole);
+        let tail = list.split_at(1).unwrap();
+        list.append(tail);
+        assert_eq!(list.to_vec(), vec![1, 2, 3, 4]);
+        assert_eq!(list.len(), 4);
+    }
+
+    #[test]
+    fn test_linked_list_merge_sort() {
+        let mut list = VisualizableLinkedList::new();
+        for value in [3, 1, 2] {
+            list.insert_back(value);
+        }
+
+        let steps = list.execute_with_steps(Operation::MergeSort).unwrap();
+        assert_eq!(list.to_vec(), vec![1, 2, 3]);
+        assert_eq!(list.len(), 3);
+        assert!(steps.iter().any(|s| s.metadata["operation"] == "split"));
+        assert_eq!(steps.last().unwrap().metadata["list_state"], serde_json::json!([1, 2, 3]));
+    }
+
+    #[test]
+    fn test_linked_list_merge_sort_larger() {
+        let values = [5, -2, 9, 9, 0, 7, 3, 1, -8, 4, 6];
+        let mut list = VisualizableLinkedList::new();
+        for value in values {
+            list.insert_back(value);
+        }
+
+        list.merge_sort_with_steps().unwrap();
+
+        let mut expected = values.to_vec();
+        expected.sort();
+        assert_eq!(list.to_vec(), expected);
+        assert_eq!(list.len(), values.len());
+
+        let mut empty = VisualizableLinkedList::new();
+        empty.merge_sort_with_steps().unwrap();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_linked_list_rotate_left() {
+        let mut list = VisualizableLinkedList::new();
+        for value in [1, 2, 3, 4, 5] {
+            list.insert_back(value);
+        }
+
+        let steps = list.execute_with_steps(Operation::RotateLeft(2)).unwrap();
+        assert_eq!(list.to_vec(), vec![3, 4, 5, 1, 2]);
+        assert_eq!(list.len(), 5);
+        assert_eq!(steps.last().unwrap().metadata["list_state"], serde_json::json!([3, 4, 5, 1, 2]));
+
+        list.rotate_left(7);
+        assert_eq!(list.to_vec(), vec![5, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_linked_list_rotate_by_length_is_noop() {
+        let mut list = VisualizableLinkedList::new();
+        for value in [1, 2, 3, 4, 5] {
+            list.insert_back(value);
+        }
+
+        list.execute_with_steps(Operation::RotateLeft(10)).unwrap();
+        assert_eq!(list.to_vec(), vec![1, 2, 3, 4, 5]);
+
+        let mut empty = VisualizableLinkedList::new();
+        empty.execute_with_steps(Operation::RotateLeft(3)).unwrap();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_list_empty_placeholder() {
+        let state = VisualizableLinkedList::new().render_state();
+        assert!(state.is_empty_placeholder());
+        assert!(state.connections.is_empty());
+    }
+
+    #[test]
+    fn test_linked_list_traverse_empty() {
+        let mut list = VisualizableLinkedList::new();
+        let steps = list.execute_with_steps(Operation::Traverse).unwrap();
+        assert_eq!(steps.len(), 1);
+        assert_eq!(steps[0].description, "Structure is empty, nothing to traverse");
+    }
 }
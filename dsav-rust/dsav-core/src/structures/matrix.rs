@@ -0,0 +1,290 @@
+//! Educational matrix implementation with visualization support.
+//!
+//! Values are stored row by row in a single vector, so the element at row
+//! `r`, column `c` lives at index `r * cols + c`. Render indices and step
+//! indices use the same layout.
+
+use crate::error::{DsavError, Result};
+use crate::state::{RenderElement, RenderState};
+use crate::instrument::traced;
+use crate::traits::{Operation, Step, Visualizable};
+
+#[derive(Debug, Clone)]
+pub struct VisualizableMatrix {
+    data: Vec<i32>,
+    rows: usize,
+    cols: usize,
+}
+
+impl VisualizableMatrix {
+    /// A `rows` × `cols` matrix of zeros.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Self {
+            data: vec![0; rows * cols],
+            rows,
+            cols,
+        }
+    }
+
+    /// Build a matrix from its rows. Returns `DsavError::InvalidState` if
+    /// the rows differ in length.
+    pub fn from_rows(rows: &[Vec<i32>]) -> Result<Self> {
+        let cols = rows.first().map_or(0, Vec::len);
+        if let Some(row) = rows.iter().position(|row| row.len() != cols) {
+            return Err(DsavError::InvalidState {
+                reason: format!("Row {} has {} values, expected {}", row, rows[row].len(), cols),
+            });
+        }
+
+        Ok(Self {
+            data: rows.concat(),
+            rows: rows.len(),
+            cols,
+        })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<i32> {
+        (row < self.rows && col < self.cols).then(|| self.data[row * self.cols + col])
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, value: i32) -> Result<()> {
+        if row >= self.rows || col >= self.cols {
+            return Err(DsavError::IndexOutOfBounds {
+                index: row * self.cols + col,
+                size: self.data.len(),
+            });
+        }
+        self.data[row * self.cols + col] = value;
+        Ok(())
+    }
+
+    /// The values as a list of rows.
+    pub fn to_rows(&self) -> Vec<Vec<i32>> {
+        if self.cols == 0 {
+            return vec![Vec::new(); self.rows];
+        }
+        self.data.chunks(self.cols).map(<[i32]>::to_vec).collect()
+    }
+
+    /// Indices of every element in row `row`.
+    fn row_indices(&self, row: usize) -> Vec<usize> {
+        (row * self.cols..(row + 1) * self.cols).collect()
+    }
+
+    /// Indices of every element in column `col`.
+    fn col_indices(&self, col: usize) -> Vec<usize> {
+        (0..self.rows).map(|row| row * self.cols + col).collect()
+    }
+
+    fn state_metadata(&self, mut metadata: serde_json::Value) -> serde_json::Value {
+        metadata["matrix_state"] = serde_json::json!(self.data);
+        metadata["cols"] = serde_json::json!(self.cols);
+        metadata
+    }
+
+    /// Swap rows and columns.
+    ///
+    /// A square matrix is transposed in place: for each row `i`, every
+    /// element right of the diagonal swaps with its mirror below it, with
+    /// row `i` and column `i` marked active. A non-square matrix changes
+    /// shape, so each row is instead copied out as the matching column of a
+    /// new `cols` × `rows` matrix. Every step's metadata carries the
+    /// `matrix_state` and `cols` it shows.
+    pub fn transpose_with_steps(&mut self) -> Vec<Step> {
+        let mut steps = vec![Step {
+            description: format!("Transposing a {}×{} matrix into {}×{}", self.rows, self.cols, self.cols, self.rows),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: self.state_metadata(serde_json::json!({
+                "operation": "transpose",
+                "rows": self.rows
+            })),
+            detail: None,
+        }];
+
+        if self.rows == self.cols {
+            let n = self.rows;
+            for i in 0..n {
+                let mut active = self.row_indices(i);
+                active.extend(self.col_indices(i));
+
+                for j in i + 1..n {
+                    let (a, b) = (i * n + j, j * n + i);
+                    self.data.swap(a, b);
+                    steps.push(Step {
+                        description: format!(
+                            "Swapped ({}, {}) and ({}, {}) across the diagonal",
+                            i, j, j, i
+                        ),
+                        highlight_indices: vec![a, b],
+                        active_indices: active.clone(),
+                        metadata: self.state_metadata(serde_json::json!({
+                            "operation": "swap",
+                            "row": i,
+                            "col": j
+                        })),
+                        detail: None,
+                    });
+                }
+            }
+        } else {
+            let source = self.clone();
+            let mut transposed = Self::new(self.cols, self.rows);
+
+            for r in 0..source.rows {
+                steps.push(Step {
+                    description: format!("Row {} {:?} becomes column {}", r, source.to_rows()[r], r),
+                    highlight_indices: source.row_indices(r),
+                    active_indices: vec![],
+                    metadata: source.state_metadata(serde_json::json!({
+                        "operation": "read_row",
+                        "row": r
+                    })),
+                    detail: None,
+                });
+
+                for c in 0..source.cols {
+                    transposed.data[c * transposed.cols + r] = source.data[r * source.cols + c];
+                }
+                steps.push(Step {
+                    description: format!("Wrote row {} into column {}", r, r),
+                    highlight_indices: transposed.col_indices(r),
+                    active_indices: (0..r).flat_map(|col| transposed.col_indices(col)).collect(),
+                    metadata: transposed.state_metadata(serde_json::json!({
+                        "operation": "write_col",
+                        "col": r
+                    })),
+                    detail: None,
+                });
+            }
+
+            *self = transposed;
+        }
+
+        steps.push(Step {
+            description: format!("Transposed: now {}×{}", self.rows, self.cols),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: self.state_metadata(serde_json::json!({
+                "rows": self.rows
+            })),
+            detail: None,
+        });
+
+        steps
+    }
+}
+
+impl Visualizable for VisualizableMatrix {
+    fn execute_with_steps(&mut self, operation: Operation) -> Result<Vec<Step>> {
+        traced("matrix", operation, || match operation {
+            Operation::Transpose => Ok(self.transpose_with_steps()),
+
+            Operation::Scale(factor) => {
+                use crate::algorithms::scale::scale_with_steps;
+                let mut steps = scale_with_steps(&mut self.data, factor)?;
+                // Show the steps on the grid rather than as a flat array
+                for step in &mut steps {
+                    if let Some(state) = step.metadata.as_object_mut().and_then(|m| m.remove("array_state")) {
+                        step.metadata["matrix_state"] = state;
+                        step.metadata["cols"] = serde_json::json!(self.cols);
+                    }
+                }
+                Ok(steps)
+            }
+
+            _ => Err(DsavError::Visualization(
+                "Operation not supported for matrices".to_string(),
+            )),
+        })
+    }
+
+    /// Elements row by row with `cols` set, labelled with their value and
+    /// `(row, col)` position.
+    fn render_state(&self) -> RenderState {
+        if self.is_empty() {
+            return RenderState::empty_placeholder("Matrix is empty", "Randomize to fill it");
+        }
+
+        RenderState {
+            elements: self
+                .data
+                .iter()
+                .enumerate()
+                .map(|(i, &value)| {
+                    RenderElement::new(value)
+                        .with_label(value.to_string())
+                        .with_sublabel(format!("({}, {})", i / self.cols, i % self.cols))
+                })
+                .collect(),
+            connections: Vec::new(),
+            cols: Some(self.cols),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matrix_transpose_rectangular() {
+        let mut matrix = VisualizableMatrix::from_rows(&[vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+        let steps = matrix.execute_with_steps(Operation::Transpose).unwrap();
+
+        assert_eq!((matrix.rows(), matrix.cols()), (3, 2));
+        assert_eq!(matrix.to_rows(), vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+        assert_eq!(matrix.get(2, 0), Some(3));
+        assert_eq!(matrix.get(0, 2), None);
+
+        let last = steps.last().unwrap();
+        assert_eq!(last.metadata["matrix_state"], serde_json::json!([1, 4, 2, 5, 3, 6]));
+        assert_eq!(last.metadata["cols"], 2);
+
+        let state = matrix.render_state();
+        assert_eq!(state.cols, Some(2));
+        assert_eq!(state.elements[3].sublabel, "(1, 1)");
+        assert_eq!(state.elements[3].value, 5);
+    }
+
+    #[test]
+    fn test_matrix_transpose_square_swaps() {
+        let mut matrix = VisualizableMatrix::from_rows(&[vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]]).unwrap();
+        let steps = matrix.execute_with_steps(Operation::Transpose).unwrap();
+
+        assert_eq!(matrix.to_rows(), vec![vec![1, 4, 7], vec![2, 5, 8], vec![3, 6, 9]]);
+        let swaps: Vec<&Vec<usize>> = steps
+            .iter()
+            .filter(|s| s.metadata["operation"] == "swap")
+            .map(|s| &s.highlight_indices)
+            .collect();
+        assert_eq!(swaps, vec![&vec![1, 3], &vec![2, 6], &vec![5, 7]]);
+
+        assert!(VisualizableMatrix::from_rows(&[vec![1, 2], vec![3]]).is_err());
+    }
+
+    #[test]
+    fn test_matrix_scale() {
+        let mut matrix = VisualizableMatrix::from_rows(&[vec![1, 2], vec![3, 4]]).unwrap();
+        let steps = matrix.execute_with_steps(Operation::Scale(-2)).unwrap();
+
+        assert_eq!(matrix.to_rows(), vec![vec![-2, -4], vec![-6, -8]]);
+        assert_eq!(steps.last().unwrap().metadata["matrix_state"], serde_json::json!([-2, -4, -6, -8]));
+        assert!(steps.iter().all(|s| s.metadata.get("array_state").is_none()));
+
+        matrix.set(0, 0, i32::MIN).unwrap();
+        assert!(matrix.execute_with_steps(Operation::Scale(-1)).is_err());
+    }
+}
@@ -1,15 +1,25 @@
 //! Data structure implementations with visualization support.
 
 pub mod array;
+pub mod matrix;
 pub mod stack;
 pub mod queue;
 pub mod linked_list;
 pub mod bst;
+pub mod binary_tree;
 pub mod rb_tree;
+pub mod btree;
+pub mod skip_list;
+pub mod gap_buffer;
 
 pub use array::VisualizableArray;
+pub use matrix::VisualizableMatrix;
 pub use stack::VisualizableStack;
 pub use queue::VisualizableQueue;
 pub use linked_list::VisualizableLinkedList;
 pub use bst::VisualizableBST;
-pub use rb_tree::VisualizableRBTree;
\ No newline at end of file
+pub use binary_tree::VisualizableBinaryTree;
+pub use rb_tree::{HeightAnalysis, VisualizableRBTree};
+pub use btree::VisualizableBTree;
+pub use skip_list::VisualizableSkipList;
+pub use gap_buffer::VisualizableGapBuffer;
\ No newline at end of file
@@ -5,6 +5,7 @@
 
 use crate::error::{DsavError, Result};
 use crate::state::{ElementState, RenderElement, RenderState};
+use crate::instrument::traced;
 use crate::traits::{Operation, Step, Visualizable};
 
 const DEFAULT_CAPACITY: usize = 16;
@@ -73,6 +74,145 @@ impl VisualizableQueue {
     pub fn clear(&mut self) {
         self.data.clear();
     }
+
+    /// Merge `other` into this queue by dequeuing alternately from each,
+    /// starting with `self`. Once one side runs out the rest of the other is
+    /// appended. Both inputs are drained and `self` holds the result.
+    pub fn interleave_with_steps(&mut self, other: &mut VisualizableQueue) -> Result<Vec<Step>> {
+        let total = self.len() + other.len();
+        if total > self.capacity {
+            return Err(DsavError::Full {
+                capacity: self.capacity,
+            });
+        }
+
+        let mut steps = Vec::new();
+        let mut result = VisualizableQueue::with_capacity(self.capacity);
+
+        steps.push(Step {
+            description: format!(
+                "Interleaving {} element(s) from the first queue with {} from the second",
+                self.len(),
+                other.len()
+            ),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": "interleave",
+                "first": self.data.clone(),
+                "second": other.data.clone()
+            }),
+            detail: None,
+        });
+
+        let mut take_first = true;
+        while !self.is_empty() || !other.is_empty() {
+            let (source, value) = if (take_first && !self.is_empty()) || other.is_empty() {
+                ("first", self.dequeue()?)
+            } else {
+                ("second", other.dequeue()?)
+            };
+            take_first = source == "second";
+
+            result.enqueue(value)?;
+
+            steps.push(Step {
+                description: format!("Took {} from the {} queue", value, source),
+                highlight_indices: vec![],
+                active_indices: vec![result.len() - 1],
+                metadata: serde_json::json!({
+                    "source": source,
+                    "value": value,
+                    "first": self.data.clone(),
+                    "second": other.data.clone(),
+                    "queue_state": result.data.clone()
+                }),
+                detail: None,
+            });
+        }
+
+        self.data = result.data;
+
+        steps.push(Step {
+            description: format!("Interleaved queue has {} element(s)", self.len()),
+            highlight_indices: vec![],
+            active_indices: (0..self.len()).collect(),
+            metadata: serde_json::json!({
+                "queue_state": self.data.clone()
+            }),
+            detail: None,
+        });
+
+        Ok(steps)
+    }
+
+    /// Enqueue each value in order, animating every insertion followed by a
+    /// separator step. Stops with a "full" step instead of an error once
+    /// capacity is reached, leaving the remaining values out.
+    pub fn enqueue_many_with_steps(&mut self, values: &[i32]) -> Vec<Step> {
+        let mut steps = Vec::new();
+
+        steps.push(Step {
+            description: format!("Enqueuing {} value(s) into the queue", values.len()),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": "enqueue_many",
+                "values": values.to_vec()
+            }),
+            detail: None,
+        });
+
+        for (i, &value) in values.iter().enumerate() {
+            if self.is_full() {
+                steps.push(Step {
+                    description: format!(
+                        "Queue is full (capacity {}), {} value(s) not added",
+                        self.capacity,
+                        values.len() - i
+                    ),
+                    highlight_indices: vec![],
+                    active_indices: vec![],
+                    metadata: serde_json::json!({
+                        "operation": "full",
+                        "capacity": self.capacity,
+                        "remaining": values[i..].to_vec()
+                    }),
+                    detail: None,
+                });
+                break;
+            }
+
+            // Capacity was checked above, so the single-value operation succeeds
+            if let Ok(value_steps) = self.execute_with_steps(Operation::Enqueue(value)) {
+                steps.extend(value_steps);
+            }
+
+            steps.push(Step {
+                description: format!("Added {} of {}", i + 1, values.len()),
+                highlight_indices: vec![],
+                active_indices: vec![],
+                metadata: serde_json::json!({
+                    "operation": "separator",
+                    "added": i + 1,
+                    "queue_state": self.data.clone()
+                }),
+                detail: None,
+            });
+        }
+
+        steps
+    }
+
+    /// Format the queue front to back, as `FRONT -> 1, 2, 3 <- BACK`.
+    pub fn pretty_print(&self) -> String {
+        if self.data.is_empty() {
+            return "(empty)".to_string();
+        }
+
+        let values: Vec<String> = self.data.iter().map(|v| v.to_string()).collect();
+        format!("FRONT -> {} <- BACK", values.join(", "))
+    }
 }
 
 impl Default for VisualizableQueue {
@@ -83,7 +223,7 @@ impl Default for VisualizableQueue {
 
 impl Visualizable for VisualizableQueue {
     fn execute_with_steps(&mut self, operation: Operation) -> Result<Vec<Step>> {
-        match operation {
+        traced("queue", operation, || match operation {
             Operation::Enqueue(value) => {
                 let mut steps = Vec::new();
 
@@ -95,6 +235,7 @@ impl Visualizable for VisualizableQueue {
                         "operation": "enqueue",
                         "value": value
                     }),
+                    detail: None,
                 });
 
                 self.enqueue(value)?;
@@ -107,6 +248,7 @@ impl Visualizable for VisualizableQueue {
                     metadata: serde_json::json!({
                         "back_index": back_index
                     }),
+                    detail: None,
                 });
 
                 Ok(steps)
@@ -124,6 +266,7 @@ impl Visualizable for VisualizableQueue {
                     metadata: serde_json::json!({
                         "value": value
                     }),
+                    detail: None,
                 });
 
                 self.dequeue()?;
@@ -134,6 +277,7 @@ impl Visualizable for VisualizableQueue {
                         highlight_indices: (0..self.len()).collect(),
                         active_indices: vec![],
                         metadata: serde_json::json!({}),
+                        detail: None,
                     });
                 }
 
@@ -142,18 +286,40 @@ impl Visualizable for VisualizableQueue {
                     highlight_indices: vec![],
                     active_indices: vec![],
                     metadata: serde_json::json!({}),
+                    detail: None,
                 });
 
                 Ok(steps)
             }
 
+            Operation::Interleave => {
+                // Interleave the front half of the queue with the back half
+                let back = self.data.split_off(self.len().div_ceil(2));
+                let mut other = VisualizableQueue::with_capacity(self.capacity);
+                other.data = back;
+
+                self.interleave_with_steps(&mut other)
+            }
+
             _ => Err(DsavError::InvalidState {
                 reason: "Operation not supported for queues".to_string(),
             }),
+        })
+    }
+
+    fn execute(&mut self, operation: Operation) -> Result<()> {
+        match operation {
+            Operation::Enqueue(value) => self.enqueue(value),
+            Operation::Dequeue => self.dequeue().map(|_| ()),
+            _ => self.execute_with_steps(operation).map(|_| ()),
         }
     }
 
     fn render_state(&self) -> RenderState {
+        if self.data.is_empty() {
+            return RenderState::empty_placeholder("Queue is empty", "Enqueue a value to add elements");
+        }
+
         RenderState {
             elements: self
                 .data
@@ -182,6 +348,7 @@ impl Visualizable for VisualizableQueue {
                 })
                 .collect(),
             connections: Vec::new(),
+            cols: None,
         }
     }
 }
@@ -242,4 +409,98 @@ mod tests {
             assert_eq!(queue.dequeue().unwrap(), i * 10);
         }
     }
+
+    #[test]
+    fn test_queue_pretty_print() {
+        let mut queue = VisualizableQueue::new();
+        assert_eq!(queue.pretty_print(), "(empty)");
+
+        queue.enqueue(1).unwrap();
+        queue.enqueue(2).unwrap();
+        queue.enqueue(3).unwrap();
+        assert_eq!(queue.pretty_print(), "FRONT -> 1, 2, 3 <- BACK");
+    }
+
+    fn queue_of(values: &[i32]) -> VisualizableQueue {
+        let mut queue = VisualizableQueue::new();
+        for &value in values {
+            queue.enqueue(value).unwrap();
+        }
+        queue
+    }
+
+    #[test]
+    fn test_queue_enqueue_many() {
+        let mut queue = VisualizableQueue::new();
+        let steps = queue.enqueue_many_with_steps(&[1, 2, 3]);
+
+        assert_eq!(queue.dequeue().unwrap(), 1);
+        assert_eq!(queue.peek().unwrap(), 2);
+
+        // Start step, then two enqueue steps and a separator per value
+        assert_eq!(steps.len(), 1 + 3 * 3);
+        let separators: Vec<&Step> = steps.iter().filter(|s| s.metadata["operation"] == "separator").collect();
+        assert_eq!(separators.len(), 3);
+        assert_eq!(separators[2].metadata["queue_state"], serde_json::json!([1, 2, 3]));
+    }
+
+    #[test]
+    fn test_queue_enqueue_many_stops_when_full() {
+        let mut queue = VisualizableQueue::with_capacity(2);
+        let steps = queue.enqueue_many_with_steps(&[1, 2, 3]);
+
+        assert_eq!(queue.len(), 2);
+        assert_eq!(steps.last().unwrap().metadata["operation"], "full");
+    }
+
+    #[test]
+    fn test_queue_interleave() {
+        let mut first = queue_of(&[1, 3]);
+        let mut second = queue_of(&[2, 4]);
+
+        let steps = first.interleave_with_steps(&mut second).unwrap();
+        assert_eq!(first.pretty_print(), "FRONT -> 1, 2, 3, 4 <- BACK");
+        assert!(second.is_empty());
+
+        let sources: Vec<&str> = steps[1..5]
+            .iter()
+            .map(|s| s.metadata["source"].as_str().unwrap())
+            .collect();
+        assert_eq!(sources, vec!["first", "second", "first", "second"]);
+    }
+
+    #[test]
+    fn test_queue_interleave_uneven() {
+        let mut first = queue_of(&[1]);
+        let mut second = queue_of(&[2, 3, 4]);
+
+        first.interleave_with_steps(&mut second).unwrap();
+        assert_eq!(first.pretty_print(), "FRONT -> 1, 2, 3, 4 <- BACK");
+    }
+
+    #[test]
+    fn test_queue_interleave_respects_capacity() {
+        let mut first = VisualizableQueue::with_capacity(3);
+        first.enqueue(1).unwrap();
+        first.enqueue(3).unwrap();
+        let mut second = queue_of(&[2, 4]);
+
+        assert!(first.interleave_with_steps(&mut second).is_err());
+        assert_eq!(first.len(), 2);
+        assert_eq!(second.len(), 2);
+    }
+
+    #[test]
+    fn test_queue_interleave_halves() {
+        let mut queue = queue_of(&[1, 2, 3, 4, 5]);
+        queue.execute(Operation::Interleave).unwrap();
+        assert_eq!(queue.pretty_print(), "FRONT -> 1, 4, 2, 5, 3 <- BACK");
+    }
+
+    #[test]
+    fn test_queue_empty_placeholder() {
+        let state = VisualizableQueue::new().render_state();
+        assert!(state.is_empty_placeholder());
+        assert!(state.connections.is_empty());
+    }
 }
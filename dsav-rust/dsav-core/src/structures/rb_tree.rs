@@ -5,6 +5,7 @@
 
 use crate::error::{DsavError, Result};
 use crate::state::{RenderElement, RenderState, ElementState};
+use crate::instrument::traced;
 use crate::traits::{Operation, Step, Visualizable};
 use std::rc::Rc;
 use std::cell::RefCell;
@@ -15,6 +16,11 @@ pub enum Color {
     Black,
 }
 
+type Link = Option<Rc<RefCell<Node>>>;
+
+/// `(value, render index, depth)` of a node placed by a bulk build.
+type Placed = (i32, usize, usize);
+
 #[derive(Debug, Clone)]
 struct Node {
     value: i32,
@@ -40,16 +46,38 @@ impl Node {
             .map(|n| n.borrow().color == Color::Red)
             .unwrap_or(false)
     }
+}
 
-    fn is_black(node: &Option<Rc<RefCell<Node>>>) -> bool {
-        !Self::is_red(node)
-    }
+/// Measured height of a Red-Black tree next to its theoretical bound.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HeightAnalysis {
+    /// Nodes on the longest root-to-leaf path.
+    pub height: usize,
+    /// Black nodes on a root-to-leaf path, not counting NIL leaves.
+    pub black_height: usize,
+    pub node_count: usize,
+    /// `2 * log2(n + 1)`, the tallest a valid tree of `n` nodes can grow.
+    pub max_height: f64,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct VisualizableRBTree {
     root: Option<Rc<RefCell<Node>>>,
     size: usize,
+    /// Last computed render state, cleared whenever the tree changes.
+    render_cache: RefCell<Option<RenderState>>,
+}
+
+impl Clone for VisualizableRBTree {
+    /// Copy every node. Nodes sit behind `Rc`, so copying the root pointer
+    /// would leave both trees sharing, and changing, the same nodes.
+    fn clone(&self) -> Self {
+        Self {
+            root: Self::clone_subtree(&self.root, None),
+            size: self.size,
+            render_cache: self.render_cache.clone(),
+        }
+    }
 }
 
 impl VisualizableRBTree {
@@ -57,6 +85,7 @@ impl VisualizableRBTree {
         Self {
             root: None,
             size: 0,
+            render_cache: RefCell::new(None),
         }
     }
 
@@ -71,29 +100,394 @@ impl VisualizableRBTree {
     pub fn clear(&mut self) {
         self.root = None;
         self.size = 0;
+        self.invalidate_render_cache();
+    }
+
+    fn invalidate_render_cache(&mut self) {
+        *self.render_cache.get_mut() = None;
+    }
+
+    /// Build a valid tree from strictly ascending `values` in O(n), without
+    /// rotations: the middle value becomes the root of a balanced tree, every
+    /// level is black, and only the deepest level is red.
+    pub fn from_sorted(values: &[i32]) -> Result<Self> {
+        Ok(Self::build_sorted(values)?.0)
+    }
+
+    /// The tree from [`Self::from_sorted`] plus every placed node in level order.
+    fn build_sorted(values: &[i32]) -> Result<(Self, Vec<Placed>)> {
+        if let Some(pair) = values.windows(2).find(|pair| pair[0] >= pair[1]) {
+            return Err(DsavError::InvalidState {
+                reason: format!("Values must be strictly ascending, found {} before {}", pair[0], pair[1]),
+            });
+        }
+
+        let mut placed = Vec::new();
+        let root = Self::build_balanced(values, 0, 0, None, &mut placed);
+        placed.sort_by_key(|&(_, idx, _)| idx);
+
+        // Every root-to-NIL path crosses the same black levels, so the deepest
+        // level can be red; a lone root stays black
+        let deepest = placed.iter().map(|&(_, _, depth)| depth).max().unwrap_or(0);
+        if deepest > 0 {
+            Self::color_level(&root, 0, deepest);
+        }
+
+        let tree = Self {
+            root,
+            size: values.len(),
+            render_cache: RefCell::new(None),
+        };
+        Ok((tree, placed))
+    }
+
+    fn build_balanced(
+        values: &[i32],
+        idx: usize,
+        depth: usize,
+        parent: Link,
+        placed: &mut Vec<Placed>,
+    ) -> Link {
+        if values.is_empty() {
+            return None;
+        }
+
+        let mid = values.len() / 2;
+        let node = Node::new(values[mid]);
+        {
+            let mut n = node.borrow_mut();
+            n.color = Color::Black;
+            n.parent = parent;
+        }
+        placed.push((values[mid], idx, depth));
+
+        let left = Self::build_balanced(&values[..mid], idx * 2 + 1, depth + 1, Some(node.clone()), placed);
+        let right = Self::build_balanced(&values[mid + 1..], idx * 2 + 2, depth + 1, Some(node.clone()), placed);
+        {
+            let mut n = node.borrow_mut();
+            n.left = left;
+            n.right = right;
+        }
+
+        Some(node)
+    }
+
+    fn color_level(node: &Link, depth: usize, target: usize) {
+        if let Some(n) = node {
+            let mut n = n.borrow_mut();
+            if depth == target {
+                n.color = Color::Red;
+            } else {
+                Self::color_level(&n.left, depth + 1, target);
+                Self::color_level(&n.right, depth + 1, target);
+            }
+        }
+    }
+
+    /// Replace the tree with one built from `values` by [`Self::from_sorted`],
+    /// animating the construction level by level and then the coloring.
+    pub fn bulk_build_with_steps(&mut self, values: &[i32]) -> Result<Vec<Step>> {
+        let (tree, placed) = Self::build_sorted(values)?;
+        *self = tree;
+
+        let mut steps = vec![Step {
+            description: format!("Building a balanced tree from {} sorted value(s)", values.len()),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": "bulk_build",
+                "values": values.to_vec()
+            }),
+            detail: None,
+        }];
+
+        let deepest = placed.iter().map(|&(_, _, depth)| depth).max().unwrap_or(0);
+        let mut built = Vec::new();
+        for depth in 0..=deepest {
+            let level: Vec<&Placed> = placed.iter().filter(|&&(_, _, d)| d == depth).collect();
+            if level.is_empty() {
+                continue;
+            }
+            built.extend(level.iter().map(|&&(_, idx, _)| idx));
+
+            steps.push(Step {
+                description: format!(
+                    "Level {}: placing {:?} as the middles of their ranges",
+                    depth,
+                    level.iter().map(|&&(value, _, _)| value).collect::<Vec<_>>()
+                ),
+                highlight_indices: level.iter().map(|&&(_, idx, _)| idx).collect(),
+                active_indices: built.clone(),
+                metadata: serde_json::json!({
+                    "operation": "place_level",
+                    "level": depth
+                }),
+                detail: None,
+            });
+        }
+
+        let red: Vec<usize> = if deepest > 0 {
+            placed.iter().filter(|&&(_, _, d)| d == deepest).map(|&(_, idx, _)| idx).collect()
+        } else {
+            Vec::new()
+        };
+
+        steps.push(Step {
+            description: if red.is_empty() {
+                "All nodes are black".to_string()
+            } else {
+                format!("Coloring the deepest level ({}) red, all other nodes black", deepest)
+            },
+            highlight_indices: red.clone(),
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": "color",
+                "red_level": (deepest > 0).then_some(deepest)
+            }),
+            detail: Some(
+                "Every path from the root to a NIL leaf passes the same black levels, \
+                 so all black heights match, and red nodes on the deepest level have no children."
+                    .to_string(),
+            ),
+        });
+
+        steps.push(Step {
+            description: format!("Built a valid Red-Black Tree with {} node(s), no rotations needed", self.size),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "valid": self.is_valid()
+            }),
+            detail: None,
+        });
+
+        Ok(steps)
+    }
+
+    /// Whether the tree satisfies every Red-Black property: ordered keys, a
+    /// black root, no red node with a red child, and equal black heights.
+    pub fn is_valid(&self) -> bool {
+        fn check(node: &Link, min: Option<i32>, max: Option<i32>) -> Option<usize> {
+            let Some(n) = node else {
+                return Some(1);
+            };
+            let n = n.borrow();
+
+            if min.is_some_and(|min| n.value <= min) || max.is_some_and(|max| n.value >= max) {
+                return None;
+            }
+            if n.color == Color::Red && (Node::is_red(&n.left) || Node::is_red(&n.right)) {
+                return None;
+            }
+
+            let left = check(&n.left, min, Some(n.value))?;
+            let right = check(&n.right, Some(n.value), max)?;
+            (left == right).then_some(left + usize::from(n.color == Color::Black))
+        }
+
+        !Node::is_red(&self.root) && check(&self.root, None, None).is_some()
+    }
+
+    /// Compare the tree's actual height with the `2 * log2(n + 1)` bound
+    /// every Red-Black tree stays within.
+    pub fn height_analysis(&self) -> HeightAnalysis {
+        fn height(node: &Link) -> usize {
+            node.as_ref().map_or(0, |n| {
+                let n = n.borrow();
+                1 + height(&n.left).max(height(&n.right))
+            })
+        }
+
+        // Every path has the same black height, so the left spine will do
+        let mut black_height = 0;
+        let mut current = self.root.clone();
+        while let Some(node) = current {
+            let node = node.borrow();
+            black_height += usize::from(node.color == Color::Black);
+            current = node.left.clone();
+        }
+
+        HeightAnalysis {
+            height: height(&self.root),
+            black_height,
+            node_count: self.size,
+            max_height: 2.0 * ((self.size + 1) as f64).log2(),
+        }
+    }
+
+    fn analyze_height_with_steps(&self) -> Vec<Step> {
+        let analysis = self.height_analysis();
+        let verdict = if analysis.height as f64 <= analysis.max_height {
+            "within"
+        } else {
+            "above"
+        };
+
+        vec![Step {
+            description: format!(
+                "Height {} with {} nodes is {} the bound 2·log2(n+1) = {:.2} (black height {})",
+                analysis.height, analysis.node_count, verdict, analysis.max_height, analysis.black_height
+            ),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": "analyze_height",
+                "height": analysis.height,
+                "black_height": analysis.black_height,
+                "node_count": analysis.node_count,
+                "max_height": analysis.max_height
+            }),
+            detail: Some(
+                "A Red-Black tree's longest path is at most twice its shortest, which keeps the height logarithmic."
+                    .to_string(),
+            ),
+        }]
+    }
+
+    /// Walk from the node holding `value` up to the root through `parent`
+    /// pointers. Each step highlights the next ancestor; the last step's
+    /// `ancestors` metadata lists them from the parent up to the root.
+    fn path_to_root_with_steps(&self, value: i32) -> Result<Vec<Step>> {
+        let node = self.find_node(&self.root, value).ok_or(DsavError::NotFound { value })?;
+        let start_idx = self.find_node_index(&node);
+
+        let mut steps = Vec::new();
+        steps.push(Step {
+            description: format!("Walking from {} up to the root via parent pointers", value),
+            highlight_indices: vec![start_idx],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": "path_to_root",
+                "value": value,
+                "path": [value]
+            }),
+            detail: None,
+        });
+
+        let mut path = vec![value];
+        let mut visited = vec![start_idx];
+        let mut current = node.borrow().parent.clone();
+
+        while let Some(parent) = current {
+            let parent_value = parent.borrow().value;
+            let parent_idx = self.find_node_index(&parent);
+            path.push(parent_value);
+
+            steps.push(Step {
+                description: format!("Parent of {} is {}", path[path.len() - 2], parent_value),
+                highlight_indices: vec![parent_idx],
+                active_indices: visited.clone(),
+                metadata: serde_json::json!({
+                    "operation": "ascend",
+                    "value": parent_value,
+                    "path": path
+                }),
+                detail: None,
+            });
+
+            visited.push(parent_idx);
+            current = parent.borrow().parent.clone();
+        }
+
+        steps.push(Step {
+            description: format!(
+                "Reached the root: {}",
+                path.iter().map(i32::to_string).collect::<Vec<_>>().join(" → ")
+            ),
+            highlight_indices: vec![],
+            active_indices: visited,
+            metadata: serde_json::json!({
+                "path": path,
+                "ancestors": path[1..]
+            }),
+            detail: None,
+        });
+
+        Ok(steps)
+    }
+
+    /// Format the tree sideways like `VisualizableBST::pretty_print`, tagging
+    /// each node with its color as `(R)` or `(B)`.
+    pub fn pretty_print(&self) -> String {
+        if self.root.is_none() {
+            return "(empty)".to_string();
+        }
+
+        let mut lines = Vec::new();
+        Self::pretty_print_helper(&self.root, 0, &mut lines);
+        lines.join("\n")
+    }
+
+    fn pretty_print_helper(node: &Link, depth: usize, lines: &mut Vec<String>) {
+        if let Some(n) = node {
+            let n = n.borrow();
+            let color = match n.color {
+                Color::Red => "R",
+                Color::Black => "B",
+            };
+
+            Self::pretty_print_helper(&n.right, depth + 1, lines);
+            lines.push(format!("{}{} ({})", "    ".repeat(depth), n.value, color));
+            Self::pretty_print_helper(&n.left, depth + 1, lines);
+        }
+    }
+
+    /// Fresh copy of the subtree at `node`, with parent links pointing into the copy.
+    fn clone_subtree(node: &Link, parent: Option<&Rc<RefCell<Node>>>) -> Link {
+        let node = node.as_ref()?.borrow();
+        let copy = Rc::new(RefCell::new(Node {
+            value: node.value,
+            color: node.color,
+            left: None,
+            right: None,
+            parent: parent.cloned(),
+        }));
+
+        let left = Self::clone_subtree(&node.left, Some(&copy));
+        let right = Self::clone_subtree(&node.right, Some(&copy));
+        {
+            let mut copy = copy.borrow_mut();
+            copy.left = left;
+            copy.right = right;
+        }
+
+        Some(copy)
     }
 
     /// Insert a value into the tree (non-visualized)
     pub fn insert(&mut self, value: i32) {
+        self.insert_node(value);
+    }
+
+    /// Insert a value, returning whether it was added and the render index
+    /// (`idx*2+1` / `idx*2+2` layout) it ends up at once the tree is
+    /// rebalanced. A duplicate returns `(false, None)`.
+    pub fn insert_returning_index(&mut self, value: i32) -> (bool, Option<usize>) {
+        match self.insert_node(value) {
+            Some(node) => (true, Some(self.find_node_index(&node))),
+            None => (false, None),
+        }
+    }
+
+    /// Insert and rebalance, returning the new node, or `None` for a duplicate.
+    fn insert_node(&mut self, value: i32) -> Option<Rc<RefCell<Node>>> {
+        self.invalidate_render_cache();
+
         if self.root.is_none() {
             let node = Node::new(value);
             node.borrow_mut().color = Color::Black;
-            self.root = Some(node);
+            self.root = Some(node.clone());
             self.size += 1;
-            return;
+            return Some(node);
         }
 
-        // Standard BST insert
-        let new_node = self.insert_bst(value);
-        if new_node.is_none() {
-            return; // Duplicate
-        }
-
-        let new_node = new_node.unwrap();
+        // Standard BST insert, None for a duplicate
+        let new_node = self.insert_bst(value)?;
         self.size += 1;
 
         // Fix RB properties
-        self.insert_fixup(new_node);
+        self.insert_fixup(new_node.clone());
+        Some(new_node)
     }
 
     /// BST-style insertion, returns the new node
@@ -317,6 +711,8 @@ impl VisualizableRBTree {
 
     /// Delete a value from the RB tree
     pub fn delete(&mut self, value: i32) -> bool {
+        self.invalidate_render_cache();
+
         // Find the node to delete
         let node_to_delete = match self.find_node(&self.root, value) {
             Some(node) => node,
@@ -351,7 +747,7 @@ impl VisualizableRBTree {
         let mut y_original_color = y.borrow().color;
 
         // Find node to splice out and its replacement
-        let (x, x_parent): (Option<Rc<RefCell<Node>>>, Option<Rc<RefCell<Node>>>);
+        let (x, x_parent): (Link, Link);
 
         {
             let z_borrow = z.borrow();
@@ -455,12 +851,12 @@ impl VisualizableRBTree {
 
     /// RB delete fixup - restore RB properties after deletion
     fn delete_fixup(&mut self, mut x: Option<Rc<RefCell<Node>>>, mut x_parent: Option<Rc<RefCell<Node>>>) {
-        while x.as_ref().map_or(true, |node| !Rc::ptr_eq(node, self.root.as_ref().unwrap()))
-              && x.as_ref().map_or(true, |node| node.borrow().color == Color::Black) {
+        while x.as_ref().is_none_or(|node| !Rc::ptr_eq(node, self.root.as_ref().unwrap()))
+              && x.as_ref().is_none_or(|node| node.borrow().color == Color::Black) {
 
             let x_is_left = if let Some(parent) = &x_parent {
                 parent.borrow().left.as_ref()
-                    .map(|l| x.as_ref().map_or(false, |x_node| Rc::ptr_eq(l, x_node)))
+                    .map(|l| x.as_ref().is_some_and(|x_node| Rc::ptr_eq(l, x_node)))
                     .unwrap_or(true) // If parent.left is None, x is considered left
             } else {
                 break;
@@ -482,9 +878,9 @@ impl VisualizableRBTree {
 
                 if let Some(w_node) = &w {
                     let left_is_black = w_node.borrow().left.as_ref()
-                        .map_or(true, |l| l.borrow().color == Color::Black);
+                        .is_none_or(|l| l.borrow().color == Color::Black);
                     let right_is_black = w_node.borrow().right.as_ref()
-                        .map_or(true, |r| r.borrow().color == Color::Black);
+                        .is_none_or(|r| r.borrow().color == Color::Black);
 
                     if left_is_black && right_is_black {
                         // Case 2: Sibling and its children are black
@@ -533,9 +929,9 @@ impl VisualizableRBTree {
 
                 if let Some(w_node) = &w {
                     let left_is_black = w_node.borrow().left.as_ref()
-                        .map_or(true, |l| l.borrow().color == Color::Black);
+                        .is_none_or(|l| l.borrow().color == Color::Black);
                     let right_is_black = w_node.borrow().right.as_ref()
-                        .map_or(true, |r| r.borrow().color == Color::Black);
+                        .is_none_or(|r| r.borrow().color == Color::Black);
 
                     if left_is_black && right_is_black {
                         // Case 2: Sibling and its children are black
@@ -614,6 +1010,34 @@ impl VisualizableRBTree {
         }
     }
 
+    /// Number of nodes in the subtree rooted at render index `index`, or
+    /// `None` if no node sits there.
+    pub fn subtree_size(&self, index: usize) -> Option<usize> {
+        fn count(node: &Link) -> usize {
+            node.as_ref().map_or(0, |n| {
+                let n = n.borrow();
+                1 + count(&n.left) + count(&n.right)
+            })
+        }
+
+        self.node_at_index(index).map(|node| count(&Some(node)))
+    }
+
+    /// Node at render index `index`, found by walking down from its parent.
+    fn node_at_index(&self, index: usize) -> Link {
+        if index == 0 {
+            return self.root.clone();
+        }
+
+        let parent = self.node_at_index((index - 1) / 2)?;
+        let parent = parent.borrow();
+        if index % 2 == 1 {
+            parent.left.clone()
+        } else {
+            parent.right.clone()
+        }
+    }
+
     /// Convert tree to array representation for rendering
     fn tree_to_array(&self) -> Vec<Option<(i32, Color)>> {
         let mut result = vec![None; 128]; // Max nodes for visualization
@@ -645,7 +1069,7 @@ impl Default for VisualizableRBTree {
 
 impl Visualizable for VisualizableRBTree {
     fn execute_with_steps(&mut self, operation: Operation) -> Result<Vec<Step>> {
-        match operation {
+        traced("rb_tree", operation, || match operation {
             Operation::Insert(_, value) => {
                 self.insert_with_steps(value)
             }
@@ -661,15 +1085,20 @@ impl Visualizable for VisualizableRBTree {
                         "operation": "search",
                         "target": target
                     }),
+                    detail: None,
                 });
 
                 let mut current = self.root.clone();
                 let mut idx = 0;
                 let mut found = false;
+                // Every node compared, root first, for reviewing the whole path afterwards
+                let mut path = Vec::new();
 
                 while let Some(node_rc) = current {
                     let node = node_rc.borrow();
 
+                    path.push(idx);
+
                     steps.push(Step {
                         description: format!("Checking {} node with value {}",
                             if node.color == Color::Red { "RED" } else { "BLACK" },
@@ -677,19 +1106,24 @@ impl Visualizable for VisualizableRBTree {
                         highlight_indices: vec![idx],
                         active_indices: vec![],
                         metadata: serde_json::json!({
+                            "operation": "compare",
+                            "index": idx,
                             "node_color": if node.color == Color::Red { "red" } else { "black" }
                         }),
+                        detail: None,
                     });
 
                     if target == node.value {
                         steps.push(Step {
                             description: format!("Found {} at node", target),
-                            highlight_indices: vec![],
+                            highlight_indices: path[..path.len() - 1].to_vec(),
                             active_indices: vec![idx],
                             metadata: serde_json::json!({
                                 "found": true,
-                                "index": idx
+                                "index": idx,
+                                "path": path
                             }),
+                            detail: None,
                         });
                         found = true;
                         break;
@@ -705,17 +1139,21 @@ impl Visualizable for VisualizableRBTree {
                 if !found {
                     steps.push(Step {
                         description: format!("Value {} not found in tree", target),
-                        highlight_indices: vec![],
+                        highlight_indices: path.clone(),
                         active_indices: vec![],
                         metadata: serde_json::json!({
-                            "found": false
+                            "found": false,
+                            "path": path
                         }),
+                        detail: None,
                     });
                 }
 
                 Ok(steps)
             }
 
+            Operation::Traverse if self.root.is_none() => Ok(vec![Step::empty_traversal()]),
+
             Operation::Traverse => {
                 let mut steps = Vec::new();
 
@@ -726,6 +1164,7 @@ impl Visualizable for VisualizableRBTree {
                     metadata: serde_json::json!({
                         "operation": "traverse"
                     }),
+                    detail: None,
                 });
 
                 Self::inorder_traverse_steps(&self.root, 0, &mut steps);
@@ -735,6 +1174,7 @@ impl Visualizable for VisualizableRBTree {
                     highlight_indices: vec![],
                     active_indices: vec![],
                     metadata: serde_json::json!({}),
+                    detail: None,
                 });
 
                 Ok(steps)
@@ -745,13 +1185,50 @@ impl Visualizable for VisualizableRBTree {
                 self.delete_with_steps(value_as_idx as i32)
             }
 
+            // Like Delete, the payload is interpreted as the node's value
+            Operation::RbRotateLeft(value) => self.rotate_left_with_steps(value),
+            Operation::RbRotateRight(value) => self.rotate_right_with_steps(value),
+
+            Operation::RebalanceAround(value) => self.rebalance_around_with_steps(value),
+
+            Operation::RebuildBalanced => {
+                let values = self.collect_nodes();
+                self.bulk_build_with_steps(&values)
+            }
+
+            Operation::AnalyzeHeight => Ok(self.analyze_height_with_steps()),
+
+            Operation::PathToRoot(value) => self.path_to_root_with_steps(value),
+
             _ => Err(DsavError::Visualization(
                 "Operation not supported for Red-Black Tree".to_string(),
             )),
+        })
+    }
+
+    fn execute(&mut self, operation: Operation) -> Result<()> {
+        match operation {
+            Operation::Insert(_, value) => {
+                self.insert(value);
+                Ok(())
+            }
+            Operation::Delete(value_as_idx) => {
+                self.delete(value_as_idx as i32);
+                Ok(())
+            }
+            _ => self.execute_with_steps(operation).map(|_| ()),
         }
     }
 
     fn render_state(&self) -> RenderState {
+        if self.root.is_none() {
+            return RenderState::empty_placeholder("Red-Black Tree is empty", "Insert a value to add nodes");
+        }
+
+        if let Some(state) = self.render_cache.borrow().as_ref() {
+            return state.clone();
+        }
+
         let mut elements = Vec::new();
         let mut connections = Vec::new();
 
@@ -770,8 +1247,11 @@ impl Visualizable for VisualizableRBTree {
 
                 elements[idx] = RenderElement::new(*value)
                     .with_label(value.to_string())
-                    .with_sublabel(format!("{}", if *color == Color::Red { "R" } else { "B" }))
+                    .with_sublabel((if *color == Color::Red { "R" } else { "B" }).to_string())
                     .with_state(state);
+                if idx == 0 {
+                    elements[idx].badge = Some("root".to_string());
+                }
 
                 // Add connections
                 let left_idx = idx * 2 + 1;
@@ -787,17 +1267,36 @@ impl Visualizable for VisualizableRBTree {
             }
         }
 
-        RenderState {
+        let state = RenderState {
             elements,
             connections,
-        }
+            cols: None,
+        };
+        *self.render_cache.borrow_mut() = Some(state.clone());
+        state
     }
 }
 
+/// Explanations attached as step `detail` for the insert fixup cases.
+const UNCLE_RED_DETAIL: &str = "The grandparent was BLACK with two RED children. Swapping colors \
+(parent and uncle BLACK, grandparent RED) keeps the number of BLACK nodes on every path through \
+the grandparent the same, so black-height is preserved. The grandparent may now be a RED child of \
+a RED parent, so the fixup continues from there.";
+const TRIANGLE_DETAIL: &str = "The new node is an inner grandchild. Rotating at the parent turns \
+the triangle into a line without changing any colors; both rotated nodes are RED, so no path \
+gains or loses a BLACK node.";
+const LINE_DETAIL: &str = "The parent takes the grandparent's place and its BLACK color, and the \
+old grandparent becomes a RED child. Every path that went through the BLACK grandparent now goes \
+through the BLACK parent instead, so black-height is unchanged and no RED node has a RED child.";
+const FIXUP_END_DETAIL: &str = "A RED node under a BLACK parent breaks no rule, and the root is \
+always recolored BLACK, which adds one to every path equally.";
+
 // Step-by-step visualization methods
 impl VisualizableRBTree {
     /// Insert with detailed animation steps
     fn insert_with_steps(&mut self, value: i32) -> Result<Vec<Step>> {
+        self.invalidate_render_cache();
+
         let mut steps = Vec::new();
 
         steps.push(Step {
@@ -808,6 +1307,7 @@ impl VisualizableRBTree {
                 "operation": "insert",
                 "value": value
             }),
+            detail: None,
         });
 
         // Handle empty tree
@@ -820,6 +1320,7 @@ impl VisualizableRBTree {
                     "new_root": value,
                     "color": "black"
                 }),
+                detail: None,
             });
 
             let node = Node::new(value);
@@ -849,6 +1350,7 @@ impl VisualizableRBTree {
                     "comparing": [value, node.value],
                     "node_color": if node.color == Color::Red { "red" } else { "black" }
                 }),
+                detail: None,
             });
 
             parent = Some(node_rc.clone());
@@ -865,6 +1367,7 @@ impl VisualizableRBTree {
                     highlight_indices: path,
                     active_indices: vec![],
                     metadata: serde_json::json!({ "duplicate": true }),
+                    detail: None,
                 });
                 return Ok(steps);
             }
@@ -896,16 +1399,236 @@ impl VisualizableRBTree {
                 "color": "red",
                 "index": insert_idx
             }),
+            detail: None,
         });
 
         // Fixup phase with detailed steps
-        self.insert_fixup_with_steps(new_node, &mut steps)?;
+        self.insert_fixup_with_steps(new_node.clone(), &mut steps)?;
+
+        // Rotations may have moved the new node, so look up where it landed
+        let final_idx = self.find_node_index(&new_node);
 
         steps.push(Step {
-            description: "Red-Black Tree properties restored".to_string(),
+            description: format!("Red-Black Tree properties restored, {} is at index {}", value, final_idx),
             highlight_indices: vec![],
+            active_indices: vec![final_idx],
+            metadata: serde_json::json!({
+                "fixup_complete": true,
+                "index": final_idx
+            }),
+            detail: None,
+        });
+
+        Ok(steps)
+    }
+
+    /// Rotate left around the node holding `value`, without any fixup.
+    ///
+    /// This exists for teaching: the raw rotation keeps BST order but can
+    /// break the red-black invariants (e.g. a red node with a red child or
+    /// unequal black heights), and they are intentionally left broken.
+    pub fn rotate_left_with_steps(&mut self, value: i32) -> Result<Vec<Step>> {
+        self.rotate_with_steps(value, true)
+    }
+
+    /// Rotate right around the node holding `value`, without any fixup.
+    ///
+    /// Like [`Self::rotate_left_with_steps`], this may leave the red-black
+    /// invariants violated.
+    pub fn rotate_right_with_steps(&mut self, value: i32) -> Result<Vec<Step>> {
+        self.rotate_with_steps(value, false)
+    }
+
+    fn rotate_with_steps(&mut self, value: i32, left: bool) -> Result<Vec<Step>> {
+        let direction = if left { "left" } else { "right" };
+        let x = self.find_node(&self.root, value).ok_or(DsavError::NotFound { value })?;
+
+        let pivot = if left { x.borrow().right.clone() } else { x.borrow().left.clone() };
+        let Some(pivot) = pivot else {
+            return Err(DsavError::Visualization(format!(
+                "Cannot rotate {} around {}: it has no {} child",
+                direction,
+                value,
+                if left { "right" } else { "left" }
+            )));
+        };
+
+        let mut steps = Vec::new();
+        let x_idx = self.find_node_index(&x);
+        let pivot_idx = self.find_node_index(&pivot);
+        let pivot_value = pivot.borrow().value;
+
+        steps.push(Step {
+            description: format!("Rotating {} around {}", direction, value),
+            highlight_indices: vec![x_idx],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": "rotate",
+                "direction": direction,
+                "value": value
+            }),
+            detail: None,
+        });
+
+        steps.push(Step {
+            description: format!(
+                "{} moves up into {}'s place, {} becomes its {} child",
+                pivot_value, value, value, direction
+            ),
+            highlight_indices: vec![x_idx, pivot_idx],
             active_indices: vec![],
-            metadata: serde_json::json!({ "fixup_complete": true }),
+            metadata: serde_json::json!({
+                "pivot": pivot_value,
+                "pivot_index": pivot_idx
+            }),
+            detail: None,
+        });
+
+        self.invalidate_render_cache();
+        if left {
+            self.rotate_left(x.clone());
+        } else {
+            self.rotate_right(x.clone());
+        }
+
+        let new_x_idx = self.find_node_index(&x);
+        let new_pivot_idx = self.find_node_index(&pivot);
+
+        steps.push(Step {
+            description: "Rotation complete; no recoloring was done, so Red-Black properties may be violated"
+                .to_string(),
+            highlight_indices: vec![new_x_idx],
+            active_indices: vec![new_pivot_idx],
+            metadata: serde_json::json!({
+                "rotated": value,
+                "pivot": pivot_value,
+                "index": new_pivot_idx
+            }),
+            detail: None,
+        });
+
+        Ok(steps)
+    }
+
+    /// Rotate a red child of the node holding `value` up into its place, then
+    /// repair the tree with recoloring and the insert fixup.
+    ///
+    /// The red child (the right one when both are red) becomes the pivot. It
+    /// takes the node's color and the node turns red, which keeps every black
+    /// height unchanged; the only possible damage is a red-red pair between the
+    /// demoted node and its outer child, which the insert fixup then resolves.
+    /// Rotating a black child up would change black heights, so a node with no
+    /// red child returns `DsavError::InvalidState`.
+    pub fn rebalance_around_with_steps(&mut self, value: i32) -> Result<Vec<Step>> {
+        let x = self.find_node(&self.root, value).ok_or(DsavError::NotFound { value })?;
+
+        let children = (x.borrow().right.clone(), x.borrow().left.clone());
+        let (left, pivot) = match children {
+            (Some(right), _) if right.borrow().color == Color::Red => (true, right),
+            (_, Some(left)) if left.borrow().color == Color::Red => (false, left),
+            _ => {
+                return Err(DsavError::InvalidState {
+                    reason: format!(
+                        "{} has no red child; rotating a black child up would change black heights",
+                        value
+                    ),
+                })
+            }
+        };
+
+        let direction = if left { "left" } else { "right" };
+        let pivot_value = pivot.borrow().value;
+
+        let mut steps = Vec::new();
+        steps.push(Step {
+            description: format!("Rebalancing around {}: rotating its red child {} up", value, pivot_value),
+            highlight_indices: vec![self.find_node_index(&x), self.find_node_index(&pivot)],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": "rebalance_around",
+                "direction": direction,
+                "value": value,
+                "pivot": pivot_value
+            }),
+            detail: None,
+        });
+
+        self.invalidate_render_cache();
+        if left {
+            self.rotate_left(x.clone());
+        } else {
+            self.rotate_right(x.clone());
+        }
+
+        steps.push(Step {
+            description: format!("Rotated {} around {}; {} moved up into its place", direction, value, pivot_value),
+            highlight_indices: vec![self.find_node_index(&x)],
+            active_indices: vec![self.find_node_index(&pivot)],
+            metadata: serde_json::json!({
+                "operation": "rotate",
+                "direction": direction,
+                "rotated": value,
+                "pivot": pivot_value
+            }),
+            detail: None,
+        });
+
+        let x_color = x.borrow().color;
+        pivot.borrow_mut().color = x_color;
+        x.borrow_mut().color = Color::Red;
+
+        steps.push(Step {
+            description: format!(
+                "Recolor: {} takes {}'s color ({}), {} becomes RED, so black heights are unchanged",
+                pivot_value,
+                value,
+                if x_color == Color::Red { "RED" } else { "BLACK" },
+                value
+            ),
+            highlight_indices: vec![],
+            active_indices: vec![self.find_node_index(&pivot), self.find_node_index(&x)],
+            metadata: serde_json::json!({
+                "operation": "recolor",
+                "pivot": pivot_value,
+                "value": value
+            }),
+            detail: None,
+        });
+
+        // The demoted node's outer child is the only one that can now be red under red
+        let outer = if left { x.borrow().left.clone() } else { x.borrow().right.clone() };
+        let z = match outer {
+            Some(child) if child.borrow().color == Color::Red => {
+                steps.push(Step {
+                    description: format!(
+                        "{} and its child {} are both RED; running the insert fixup from {}",
+                        value,
+                        child.borrow().value,
+                        child.borrow().value
+                    ),
+                    highlight_indices: vec![self.find_node_index(&x), self.find_node_index(&child)],
+                    active_indices: vec![],
+                    metadata: serde_json::json!({
+                        "operation": "fixup",
+                        "violation": "red_red"
+                    }),
+                    detail: None,
+                });
+                child
+            }
+            _ => x,
+        };
+        self.insert_fixup_with_steps(z, &mut steps)?;
+
+        debug_assert!(self.is_valid());
+        steps.push(Step {
+            description: "Red-Black properties hold again".to_string(),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "valid": self.is_valid()
+            }),
+            detail: None,
         });
 
         Ok(steps)
@@ -913,6 +1636,8 @@ impl VisualizableRBTree {
 
     /// Delete a value with detailed animation steps
     fn delete_with_steps(&mut self, value: i32) -> Result<Vec<Step>> {
+        self.invalidate_render_cache();
+
         let mut steps = Vec::new();
 
         steps.push(Step {
@@ -923,6 +1648,7 @@ impl VisualizableRBTree {
                 "operation": "delete",
                 "value": value
             }),
+            detail: None,
         });
 
         // Find the node to delete
@@ -937,6 +1663,7 @@ impl VisualizableRBTree {
                         "found": true,
                         "index": idx
                     }),
+                    detail: None,
                 });
                 node
             }
@@ -948,6 +1675,7 @@ impl VisualizableRBTree {
                     metadata: serde_json::json!({
                         "found": false
                     }),
+                    detail: None,
                 });
                 return Ok(steps);
             }
@@ -964,6 +1692,7 @@ impl VisualizableRBTree {
             metadata: serde_json::json!({
                 "complete": true
             }),
+            detail: None,
         });
 
         Ok(steps)
@@ -977,7 +1706,7 @@ impl VisualizableRBTree {
         let mut y = z.clone();
         let mut y_original_color = y.borrow().color;
 
-        let (x, x_parent): (Option<Rc<RefCell<Node>>>, Option<Rc<RefCell<Node>>>);
+        let (x, x_parent): (Link, Link);
 
         {
             let z_borrow = z.borrow();
@@ -994,6 +1723,7 @@ impl VisualizableRBTree {
                         "case": "no_children",
                         "node": z_val
                     }),
+                    detail: None,
                 });
                 x = None;
                 x_parent = z_borrow.parent.clone();
@@ -1011,6 +1741,7 @@ impl VisualizableRBTree {
                         "node": z_val,
                         "replacement": right_val
                     }),
+                    detail: None,
                 });
                 x = z_borrow.right.clone();
                 x_parent = z_borrow.parent.clone();
@@ -1028,6 +1759,7 @@ impl VisualizableRBTree {
                         "node": z_val,
                         "replacement": left_val
                     }),
+                    detail: None,
                 });
                 x = z_borrow.left.clone();
                 x_parent = z_borrow.parent.clone();
@@ -1049,6 +1781,7 @@ impl VisualizableRBTree {
                         "node": z_val,
                         "successor": y_val
                     }),
+                    detail: None,
                 });
 
                 x = y.borrow().right.clone();
@@ -1084,6 +1817,7 @@ impl VisualizableRBTree {
                         "replaced": z_val,
                         "with": y_val
                     }),
+                    detail: None,
                 });
             }
         }
@@ -1098,6 +1832,7 @@ impl VisualizableRBTree {
                     "fixup_needed": true,
                     "deleted_color": "black"
                 }),
+                detail: None,
             });
 
             self.delete_fixup_with_steps(x, x_parent, steps)?;
@@ -1110,6 +1845,7 @@ impl VisualizableRBTree {
                     "fixup_needed": false,
                     "deleted_color": "red"
                 }),
+                detail: None,
             });
         }
 
@@ -1125,14 +1861,14 @@ impl VisualizableRBTree {
     ) -> Result<()> {
         let mut iteration = 0;
 
-        while x.as_ref().map_or(true, |node| self.root.as_ref().map_or(true, |root| !Rc::ptr_eq(node, root)))
-              && x.as_ref().map_or(true, |node| node.borrow().color == Color::Black) {
+        while x.as_ref().is_none_or(|node| self.root.as_ref().is_none_or(|root| !Rc::ptr_eq(node, root)))
+              && x.as_ref().is_none_or(|node| node.borrow().color == Color::Black) {
 
             iteration += 1;
 
             let x_is_left = if let Some(parent) = &x_parent {
                 parent.borrow().left.as_ref()
-                    .map(|l| x.as_ref().map_or(false, |x_node| Rc::ptr_eq(l, x_node)))
+                    .map(|l| x.as_ref().is_some_and(|x_node| Rc::ptr_eq(l, x_node)))
                     .unwrap_or(true)
             } else {
                 break;
@@ -1154,6 +1890,7 @@ impl VisualizableRBTree {
                                 "case": "sibling_red",
                                 "iteration": iteration
                             }),
+                            detail: None,
                         });
 
                         w_node.borrow_mut().color = Color::Black;
@@ -1165,9 +1902,9 @@ impl VisualizableRBTree {
 
                 if let Some(w_node) = &w {
                     let left_is_black = w_node.borrow().left.as_ref()
-                        .map_or(true, |l| l.borrow().color == Color::Black);
+                        .is_none_or(|l| l.borrow().color == Color::Black);
                     let right_is_black = w_node.borrow().right.as_ref()
-                        .map_or(true, |r| r.borrow().color == Color::Black);
+                        .is_none_or(|r| r.borrow().color == Color::Black);
 
                     if left_is_black && right_is_black {
                         // Case 2: Both children black
@@ -1179,6 +1916,7 @@ impl VisualizableRBTree {
                                 "case": "both_children_black",
                                 "iteration": iteration
                             }),
+                            detail: None,
                         });
 
                         w_node.borrow_mut().color = Color::Red;
@@ -1195,6 +1933,7 @@ impl VisualizableRBTree {
                                     "case": "triangle",
                                     "iteration": iteration
                                 }),
+                                detail: None,
                             });
 
                             if let Some(left) = &w_node.borrow().left {
@@ -1214,6 +1953,7 @@ impl VisualizableRBTree {
                                 "case": "line",
                                 "iteration": iteration
                             }),
+                            detail: None,
                         });
 
                         if let Some(w_node) = &w {
@@ -1245,6 +1985,7 @@ impl VisualizableRBTree {
                                 "case": "sibling_red_mirror",
                                 "iteration": iteration
                             }),
+                            detail: None,
                         });
 
                         w_node.borrow_mut().color = Color::Black;
@@ -1256,9 +1997,9 @@ impl VisualizableRBTree {
 
                 if let Some(w_node) = &w {
                     let left_is_black = w_node.borrow().left.as_ref()
-                        .map_or(true, |l| l.borrow().color == Color::Black);
+                        .is_none_or(|l| l.borrow().color == Color::Black);
                     let right_is_black = w_node.borrow().right.as_ref()
-                        .map_or(true, |r| r.borrow().color == Color::Black);
+                        .is_none_or(|r| r.borrow().color == Color::Black);
 
                     if left_is_black && right_is_black {
                         steps.push(Step {
@@ -1269,6 +2010,7 @@ impl VisualizableRBTree {
                                 "case": "both_children_black_mirror",
                                 "iteration": iteration
                             }),
+                            detail: None,
                         });
 
                         w_node.borrow_mut().color = Color::Red;
@@ -1284,6 +2026,7 @@ impl VisualizableRBTree {
                                     "case": "triangle_mirror",
                                     "iteration": iteration
                                 }),
+                                detail: None,
                             });
 
                             if let Some(right) = &w_node.borrow().right {
@@ -1302,6 +2045,7 @@ impl VisualizableRBTree {
                                 "case": "line_mirror",
                                 "iteration": iteration
                             }),
+                            detail: None,
                         });
 
                         if let Some(w_node) = &w {
@@ -1332,6 +2076,7 @@ impl VisualizableRBTree {
             metadata: serde_json::json!({
                 "fixup_complete": true
             }),
+            detail: None,
         });
 
         Ok(())
@@ -1339,6 +2084,10 @@ impl VisualizableRBTree {
 
     /// Render state with NIL leaves shown
     pub fn render_state_with_nil_nodes(&self) -> RenderState {
+        if self.root.is_none() {
+            return self.render_state();
+        }
+
         let mut elements = Vec::new();
         let mut connections = Vec::new();
 
@@ -1358,8 +2107,11 @@ impl VisualizableRBTree {
 
                 elements[idx] = RenderElement::new(*value)
                     .with_label(value.to_string())
-                    .with_sublabel(format!("{}", if *color == Color::Red { "R" } else { "B" }))
+                    .with_sublabel((if *color == Color::Red { "R" } else { "B" }).to_string())
                     .with_state(state);
+                if idx == 0 {
+                    elements[idx].badge = Some("root".to_string());
+                }
 
                 // Add connections to children (including NIL nodes)
                 let left_idx = idx * 2 + 1;
@@ -1369,7 +2121,7 @@ impl VisualizableRBTree {
                 if left_idx < array.len() * 2 { // Allow space for NIL nodes
                     connections.push((idx, left_idx));
                     // If child doesn't exist, we'll add a NIL node
-                    if array.get(left_idx).is_none() || !array[left_idx].is_some() {
+                    if array.get(left_idx).is_none() || array[left_idx].is_none() {
                         while elements.len() <= left_idx {
                             elements.push(RenderElement::new(0).with_label("".to_string()));
                         }
@@ -1383,7 +2135,7 @@ impl VisualizableRBTree {
                 if right_idx < array.len() * 2 {
                     connections.push((idx, right_idx));
                     // If child doesn't exist, we'll add a NIL node
-                    if array.get(right_idx).is_none() || !array[right_idx].is_some() {
+                    if array.get(right_idx).is_none() || array[right_idx].is_none() {
                         while elements.len() <= right_idx {
                             elements.push(RenderElement::new(0).with_label("".to_string()));
                         }
@@ -1399,6 +2151,7 @@ impl VisualizableRBTree {
         RenderState {
             elements,
             connections,
+            cols: None,
         }
     }
 
@@ -1425,6 +2178,7 @@ impl VisualizableRBTree {
                             highlight_indices: vec![],
                             active_indices: vec![],
                             metadata: serde_json::json!({ "fixup_end": true }),
+                            detail: Some(FIXUP_END_DETAIL.to_string()),
                         });
                         break;
                     }
@@ -1483,6 +2237,7 @@ impl VisualizableRBTree {
                         "grandparent": gp_val,
                         "uncle_is_red": Node::is_red(&uncle)
                     }),
+                    detail: None,
                 });
 
                 if Node::is_red(&uncle) {
@@ -1498,6 +2253,7 @@ impl VisualizableRBTree {
                             "case": "uncle_red",
                             "recolor": ["parent", "uncle", "grandparent"]
                         }),
+                        detail: Some(UNCLE_RED_DETAIL.to_string()),
                     });
 
                     parent_rc.borrow_mut().color = Color::Black;
@@ -1527,6 +2283,7 @@ impl VisualizableRBTree {
                                 "rotation": "left",
                                 "pivot": parent_val
                             }),
+                            detail: Some(TRIANGLE_DETAIL.to_string()),
                         });
 
                         current_z = parent_rc.clone();
@@ -1550,6 +2307,7 @@ impl VisualizableRBTree {
                             "rotation": "right",
                             "pivot": grandparent_rc.borrow().value
                         }),
+                        detail: Some(LINE_DETAIL.to_string()),
                     });
 
                     parent_rc.borrow_mut().color = Color::Black;
@@ -1590,6 +2348,7 @@ impl VisualizableRBTree {
                         "grandparent": gp_val,
                         "uncle_is_red": Node::is_red(&uncle)
                     }),
+                    detail: None,
                 });
 
                 if Node::is_red(&uncle) {
@@ -1605,6 +2364,7 @@ impl VisualizableRBTree {
                             "case": "uncle_red_mirror",
                             "recolor": ["parent", "uncle", "grandparent"]
                         }),
+                        detail: Some(UNCLE_RED_DETAIL.to_string()),
                     });
 
                     parent_rc.borrow_mut().color = Color::Black;
@@ -1634,6 +2394,7 @@ impl VisualizableRBTree {
                                 "rotation": "right",
                                 "pivot": parent_val
                             }),
+                            detail: Some(TRIANGLE_DETAIL.to_string()),
                         });
 
                         current_z = parent_rc.clone();
@@ -1657,6 +2418,7 @@ impl VisualizableRBTree {
                             "rotation": "left",
                             "pivot": grandparent_rc.borrow().value
                         }),
+                        detail: Some(LINE_DETAIL.to_string()),
                     });
 
                     parent_rc.borrow_mut().color = Color::Black;
@@ -1681,6 +2443,7 @@ impl VisualizableRBTree {
                     highlight_indices: vec![0],
                     active_indices: vec![],
                     metadata: serde_json::json!({ "root_recolor": true }),
+                    detail: None,
                 });
                 root.borrow_mut().color = Color::Black;
             }
@@ -1709,6 +2472,7 @@ impl VisualizableRBTree {
                     "color": if n.color == Color::Red { "red" } else { "black" },
                     "index": idx
                 }),
+                detail: None,
             });
 
             Self::inorder_traverse_steps(&n.right, idx * 2 + 2, steps);
@@ -1778,6 +2542,33 @@ mod tests {
         assert_eq!(tree.size(), 0);
     }
 
+    #[test]
+    fn test_rb_tree_clone_is_independent() {
+        let mut tree = VisualizableRBTree::new();
+        for value in [10, 20] {
+            tree.insert(value);
+        }
+        let copy = tree.clone();
+        let original = copy.pretty_print();
+
+        // Inserts recolor and rotate the original's nodes
+        for value in [30, 40, 50] {
+            tree.insert(value);
+        }
+        tree.execute_with_steps(Operation::RbRotateLeft(20)).unwrap();
+
+        assert_eq!(copy.pretty_print(), original);
+        assert_eq!(copy.size(), 2);
+        assert_eq!(copy.render_state().elements[0].label, "10");
+        assert_eq!(copy.render_state().elements[2].label, "20");
+
+        // The copy keeps working as a tree of its own
+        let mut copy = copy;
+        copy.insert(5);
+        assert_eq!(copy.size(), 3);
+        assert!(copy.is_valid());
+    }
+
     #[test]
     fn test_rb_tree_clear() {
         let mut tree = VisualizableRBTree::new();
@@ -1823,10 +2614,8 @@ mod tests {
                 let n = n.borrow();
 
                 // Check no red-red parent-child
-                if n.color == Color::Red {
-                    if Node::is_red(&n.left) || Node::is_red(&n.right) {
-                        return (0, false); // Red node with red child
-                    }
+                if n.color == Color::Red && (Node::is_red(&n.left) || Node::is_red(&n.right)) {
+                    return (0, false); // Red node with red child
                 }
 
                 let (left_bh, left_valid) = verify_rb_recursive(&n.left);
@@ -1882,12 +2671,12 @@ mod tests {
 
     #[test]
     fn test_rb_random_insertions() {
-        use rand::Rng;
-        let mut rng = rand::thread_rng();
+        use crate::dataset::{RandomSource, SeededRandom};
+        let mut rng = SeededRandom::new(7);
         let mut tree = VisualizableRBTree::new();
 
         for _ in 0..100 {
-            let val = rng.gen_range(1..1000);
+            let val = rng.next_in_range(1, 999);
             tree.insert(val);
             assert!(verify_rb_properties(&tree.root), "RB properties violated");
         }
@@ -1898,4 +2687,320 @@ mod tests {
             assert!(nodes[i] >= nodes[i - 1], "Tree not sorted");
         }
     }
+
+    #[test]
+    fn test_rb_tree_pretty_print() {
+        let mut tree = VisualizableRBTree::new();
+        assert_eq!(tree.pretty_print(), "(empty)");
+
+        for value in [10, 20, 30] {
+            tree.insert(value);
+        }
+        // Inserting in order triggers a rotation, making 20 the black root
+        assert_eq!(tree.pretty_print(), "    30 (R)\n20 (B)\n    10 (R)");
+    }
+
+    #[test]
+    fn test_rb_tree_render_state_cache() {
+        let mut tree = VisualizableRBTree::new();
+        for value in [10, 20, 30] {
+            tree.insert(value);
+        }
+
+        let first = tree.render_state();
+        assert!(tree.render_cache.borrow().is_some());
+        let second = tree.render_state();
+        assert_eq!(second.connections, first.connections);
+        assert_eq!(second.elements[0].value, 20);
+
+        tree.execute_with_steps(Operation::Insert(0, 40)).unwrap();
+        assert!(tree.render_cache.borrow().is_none());
+        assert_eq!(tree.render_state().elements.len(), 7);
+
+        tree.delete(40);
+        assert!(tree.render_cache.borrow().is_none());
+        assert_eq!(tree.render_state().elements.len(), 3);
+    }
+
+    #[test]
+    fn test_rb_insert_final_step_marks_landed_node() {
+        let mut tree = VisualizableRBTree::new();
+
+        // Ascending inserts force rotations that move the new node
+        for value in [10, 20, 30, 40, 50, 25] {
+            let steps = tree.execute_with_steps(Operation::Insert(0, value)).unwrap();
+            let idx = steps.last().unwrap().active_indices[0];
+
+            let state = tree.render_state();
+            assert_eq!(state.elements[idx].label, value.to_string());
+        }
+    }
+
+    #[test]
+    fn test_rb_insert_returning_index() {
+        let mut tree = VisualizableRBTree::new();
+
+        // Rotations move nodes, so the index reflects the rebalanced tree
+        for value in [10, 20, 30, 40, 50, 25] {
+            let (inserted, idx) = tree.insert_returning_index(value);
+            assert!(inserted);
+
+            let state = tree.render_state();
+            assert_eq!(state.elements[idx.unwrap()].label, value.to_string());
+        }
+        assert_eq!(tree.insert_returning_index(30), (false, None));
+        assert_eq!(tree.size(), 6);
+    }
+
+    #[test]
+    fn test_rb_rotate_left_then_right_restores_shape() {
+        let mut tree = VisualizableRBTree::new();
+        for value in [50, 30, 70, 20, 40, 60, 80] {
+            tree.insert(value);
+        }
+        let original = tree.pretty_print();
+
+        // Rotating left at 30 lifts its right child 40; rotating right at 40 undoes it
+        tree.execute_with_steps(Operation::RbRotateLeft(30)).unwrap();
+        assert_ne!(tree.pretty_print(), original);
+        assert_eq!(tree.render_state().elements[1].label, "40");
+
+        tree.execute_with_steps(Operation::RbRotateRight(40)).unwrap();
+        assert_eq!(tree.pretty_print(), original);
+    }
+
+    #[test]
+    fn test_rb_rotate_at_root() {
+        let mut tree = VisualizableRBTree::new();
+        for value in [50, 30, 70] {
+            tree.insert(value);
+        }
+        let original = tree.pretty_print();
+
+        let steps = tree.rotate_right_with_steps(50).unwrap();
+        assert_eq!(steps.last().unwrap().active_indices, vec![0]);
+        assert_eq!(tree.render_state().elements[0].label, "30");
+
+        tree.rotate_left_with_steps(30).unwrap();
+        assert_eq!(tree.pretty_print(), original);
+    }
+
+    #[test]
+    fn test_rb_rebalance_around_stays_valid() {
+        let mut tree = VisualizableRBTree::new();
+        for value in 1..=10 {
+            tree.insert(value);
+        }
+        let values = tree.collect_nodes();
+
+        // 6 is black with a red right child 8, which moves up and turns black
+        let steps = tree.execute_with_steps(Operation::RebalanceAround(6)).unwrap();
+        assert!(tree.is_valid());
+        assert_eq!(tree.collect_nodes(), values);
+        assert_eq!(tree.render_state().elements[2].label, "8");
+        assert!(steps.iter().any(|s| s.metadata["operation"] == "recolor"));
+
+        // The root 4 now has two black children
+        assert!(matches!(
+            tree.rebalance_around_with_steps(4),
+            Err(DsavError::InvalidState { .. })
+        ));
+    }
+
+    #[test]
+    fn test_rb_rebalance_around_runs_fixup() {
+        let mut tree = VisualizableRBTree::new();
+        for value in [20, 10, 30] {
+            tree.insert(value);
+        }
+
+        // Lifting 30 leaves 20 and 10 both red, which the fixup repairs
+        let steps = tree.rebalance_around_with_steps(20).unwrap();
+        assert!(steps.iter().any(|s| s.metadata["violation"] == "red_red"));
+        assert!(tree.is_valid());
+        assert_eq!(tree.collect_nodes(), vec![10, 20, 30]);
+    }
+
+    #[test]
+    fn test_rb_path_to_root() {
+        let mut tree = VisualizableRBTree::new();
+        for value in [50, 30, 70, 20, 40, 60, 80, 10] {
+            tree.insert(value);
+        }
+
+        let steps = tree.execute_with_steps(Operation::PathToRoot(10)).unwrap();
+        let last = steps.last().unwrap();
+        assert_eq!(last.metadata["ancestors"], serde_json::json!([20, 30, 50]));
+        assert_eq!(last.active_indices, vec![7, 3, 1, 0]);
+
+        let steps = tree.execute_with_steps(Operation::PathToRoot(50)).unwrap();
+        assert_eq!(steps.last().unwrap().metadata["ancestors"], serde_json::json!([]));
+        assert!(tree.execute_with_steps(Operation::PathToRoot(99)).is_err());
+    }
+
+    #[test]
+    fn test_rb_rotate_errors() {
+        let mut tree = VisualizableRBTree::new();
+        tree.insert(50);
+
+        assert!(tree.rotate_left_with_steps(50).is_err());
+        assert!(tree.rotate_right_with_steps(99).is_err());
+
+        // Negative values reach the tree unchanged; -10 is the root here
+        for value in [-10, -20] {
+            tree.insert(value);
+        }
+        tree.execute_with_steps(Operation::RbRotateRight(-10)).unwrap();
+        assert_eq!(tree.render_state().elements[0].label, "-20");
+    }
+
+    #[test]
+    fn test_rb_insert_fixup_steps_have_detail() {
+        let mut tree = VisualizableRBTree::new();
+        for value in [10, 20] {
+            tree.insert(value);
+        }
+
+        // 30 under 20 under 10 is a line case
+        let steps = tree.execute_with_steps(Operation::Insert(0, 30)).unwrap();
+        let line = steps.iter().find(|s| s.metadata["case"] == "line_mirror").unwrap();
+        assert!(line.detail.as_deref().unwrap().contains("black-height"));
+
+        // Recoloring case: 40's parent 30 and uncle 10 are both RED
+        let steps = tree.execute_with_steps(Operation::Insert(0, 40)).unwrap();
+        let recolor = steps.iter().find(|s| s.metadata["case"] == "uncle_red_mirror").unwrap();
+        assert!(recolor.detail.is_some());
+
+        // Detail is omitted from JSON when absent
+        let json = serde_json::to_value(&steps[0]).unwrap();
+        assert!(json.get("detail").is_none());
+    }
+
+    #[test]
+    fn test_rb_empty_placeholder() {
+        let mut tree = VisualizableRBTree::new();
+        assert!(tree.render_state().is_empty_placeholder());
+        assert!(tree.render_state_with_nil_nodes().is_empty_placeholder());
+
+        tree.insert(1);
+        assert!(!tree.render_state().is_empty_placeholder());
+    }
+
+    #[test]
+    fn test_rb_subtree_size() {
+        let mut tree = VisualizableRBTree::new();
+        for value in 1..=7 {
+            tree.insert(value);
+        }
+
+        assert_eq!(tree.subtree_size(0), Some(7));
+        let left = tree.subtree_size(1).unwrap();
+        let right = tree.subtree_size(2).unwrap();
+        assert_eq!(left + right, 6);
+        assert_eq!(tree.subtree_size(200), None);
+    }
+
+    #[test]
+    fn test_rb_search_path() {
+        let mut tree = VisualizableRBTree::new();
+        for value in [50, 30, 70, 20, 40, 60, 80, 10] {
+            tree.insert(value);
+        }
+
+        for target in [10, 65] {
+            let steps = tree.execute_with_steps(Operation::Search(target)).unwrap();
+            let comparisons = steps.iter().filter(|s| s.metadata["operation"] == "compare").count();
+            let path = steps.last().unwrap().metadata["path"].as_array().unwrap().clone();
+
+            assert_eq!(path.len(), comparisons);
+            assert_eq!(path[0], 0);
+        }
+    }
+
+    #[test]
+    fn test_rb_render_states_validate() {
+        let mut tree = VisualizableRBTree::new();
+        for value in [50, 30, 70, 20, 40, 60, 80, 10] {
+            tree.insert(value);
+        }
+
+        assert!(tree.render_state().validate().is_ok());
+        assert!(tree.render_state_with_nil_nodes().validate().is_ok());
+    }
+
+    #[test]
+    fn test_rb_from_sorted_matches_sequential_insert() {
+        for n in [0, 1, 2, 3, 7, 10, 31, 100] {
+            let values: Vec<i32> = (1..=n).map(|v| v * 10).collect();
+            let built = VisualizableRBTree::from_sorted(&values).unwrap();
+
+            let mut inserted = VisualizableRBTree::new();
+            for &value in &values {
+                inserted.insert(value);
+            }
+
+            assert!(built.is_valid(), "n = {}", n);
+            assert!(verify_rb_properties(&built.root), "n = {}", n);
+            assert!(inserted.is_valid());
+            assert_eq!(built.collect_nodes(), inserted.collect_nodes());
+            assert_eq!(built.size(), inserted.size());
+        }
+    }
+
+    #[test]
+    fn test_rb_from_sorted_rejects_unsorted() {
+        assert!(VisualizableRBTree::from_sorted(&[1, 3, 2]).is_err());
+        assert!(VisualizableRBTree::from_sorted(&[1, 1]).is_err());
+    }
+
+    #[test]
+    fn test_rb_bulk_build_steps() {
+        let mut tree = VisualizableRBTree::new();
+        let steps = tree.bulk_build_with_steps(&[1, 2, 3, 4, 5]).unwrap();
+
+        let levels = steps.iter().filter(|s| s.metadata["operation"] == "place_level").count();
+        assert_eq!(levels, 3);
+        assert_eq!(steps.last().unwrap().metadata["valid"], true);
+
+        // Rebuilding keeps the values and the properties
+        for value in [6, 7, 8, 9] {
+            tree.insert(value);
+        }
+        tree.execute_with_steps(Operation::RebuildBalanced).unwrap();
+        assert!(tree.is_valid());
+        assert_eq!(tree.collect_nodes(), (1..=9).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_rb_is_valid_detects_violation() {
+        let tree = VisualizableRBTree::from_sorted(&[1, 2, 3]).unwrap();
+        assert!(tree.is_valid());
+
+        tree.root.as_ref().unwrap().borrow_mut().color = Color::Red;
+        assert!(!tree.is_valid());
+    }
+
+    #[test]
+    fn test_rb_traverse_empty() {
+        let mut tree = VisualizableRBTree::new();
+        let steps = tree.execute_with_steps(Operation::Traverse).unwrap();
+        assert_eq!(steps.len(), 1);
+        assert_eq!(steps[0].metadata["empty"], true);
+    }
+
+    #[test]
+    fn test_rb_height_within_bound() {
+        let mut tree = VisualizableRBTree::new();
+        for value in 0..1000 {
+            tree.insert(value);
+        }
+
+        let analysis = tree.height_analysis();
+        assert_eq!(analysis.node_count, 1000);
+        assert!(analysis.height as f64 <= analysis.max_height);
+        assert!(analysis.black_height >= analysis.height / 2);
+
+        let steps = tree.execute_with_steps(Operation::AnalyzeHeight).unwrap();
+        assert_eq!(steps[0].metadata["height"], analysis.height);
+    }
 }
@@ -0,0 +1,614 @@
+//! Educational skip list implementation with visualization support.
+//!
+//! A skip list keeps a sorted linked list at level 0 and sparser "express
+//! lane" lists above it. Each inserted value is promoted level by level with
+//! coin flips, so searches can skip ahead on the upper levels and drop down
+//! once they would overshoot.
+
+use crate::dataset::{RandomSource, SeededRandom, ThreadRandom};
+use crate::error::{DsavError, Result};
+use crate::state::{ElementState, RenderElement, RenderState};
+use crate::instrument::traced;
+use crate::traits::{Operation, Step, Visualizable};
+
+const DEFAULT_MAX_LEVEL: usize = 4;
+
+/// Index of the head sentinel in the node arena.
+const HEAD: usize = 0;
+
+#[derive(Debug, Clone)]
+struct Node {
+    value: i32,
+    /// Next node on each level this node takes part in.
+    forward: Vec<Option<usize>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct VisualizableSkipList {
+    /// Arena of nodes; `HEAD` is a sentinel linked on every level.
+    nodes: Vec<Node>,
+    /// Arena slots freed by deletion, reused by later inserts.
+    free: Vec<usize>,
+    /// Number of levels currently in use (at least 1).
+    level: usize,
+    max_level: usize,
+    length: usize,
+    rng: SeededRandom,
+}
+
+impl VisualizableSkipList {
+    pub fn new() -> Self {
+        Self::with_max_level(DEFAULT_MAX_LEVEL)
+    }
+
+    pub fn with_max_level(max_level: usize) -> Self {
+        Self::with_seed(max_level, ThreadRandom.next_seed())
+    }
+
+    /// Create a skip list whose coin flips come from a seeded RNG, for
+    /// reproducible level assignment.
+    pub fn with_seed(max_level: usize, seed: u64) -> Self {
+        let max_level = max_level.max(1);
+
+        Self {
+            nodes: vec![Node {
+                value: i32::MIN,
+                forward: vec![None; max_level],
+            }],
+            free: Vec::new(),
+            level: 1,
+            max_level,
+            length: 0,
+            rng: SeededRandom::new(seed),
+        }
+    }
+
+    /// Insert a value. Returns `false` if it was already present.
+    pub fn insert(&mut self, value: i32) -> bool {
+        self.insert_impl(value, None)
+    }
+
+    pub fn search(&self, value: i32) -> bool {
+        let update = self.descend(value, None);
+        self.next(update[0], 0).is_some_and(|next| self.nodes[next].value == value)
+    }
+
+    /// Remove a value. Returns `false` if it wasn't present.
+    pub fn delete(&mut self, value: i32) -> bool {
+        self.delete_impl(value, None)
+    }
+
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    pub fn max_level(&self) -> usize {
+        self.max_level
+    }
+
+    /// Number of levels currently in use.
+    pub fn level(&self) -> usize {
+        self.level
+    }
+
+    pub fn clear(&mut self) {
+        self.nodes.truncate(1);
+        self.nodes[HEAD].forward = vec![None; self.max_level];
+        self.free.clear();
+        self.level = 1;
+        self.length = 0;
+    }
+
+    /// Values on each level, bottom level first.
+    pub fn levels(&self) -> Vec<Vec<i32>> {
+        (0..self.level)
+            .map(|lvl| self.level_nodes(lvl).into_iter().map(|id| self.nodes[id].value).collect())
+            .collect()
+    }
+
+    fn next(&self, node: usize, lvl: usize) -> Option<usize> {
+        self.nodes[node].forward[lvl]
+    }
+
+    /// Arena ids of the nodes on `lvl`, in order.
+    fn level_nodes(&self, lvl: usize) -> Vec<usize> {
+        std::iter::successors(self.next(HEAD, lvl), |&id| self.next(id, lvl)).collect()
+    }
+
+    /// Position of `node` on `lvl` in render order (levels bottom-up, nodes left to right).
+    fn render_index(&self, node: usize, lvl: usize) -> Option<usize> {
+        let offset: usize = (0..lvl).map(|l| self.level_nodes(l).len()).sum();
+        self.level_nodes(lvl).iter().position(|&id| id == node).map(|pos| offset + pos)
+    }
+
+    /// Render indices of `node` on every level it takes part in.
+    fn tower_indices(&self, node: usize) -> Vec<usize> {
+        (0..self.nodes[node].forward.len())
+            .filter_map(|lvl| self.render_index(node, lvl))
+            .collect()
+    }
+
+    /// Walk from the top level down, moving right while the next value is
+    /// smaller than `target`. Returns the last node visited on each level.
+    fn descend(&self, target: i32, mut steps: Option<&mut Vec<Step>>) -> Vec<usize> {
+        let mut update = vec![HEAD; self.max_level];
+        let mut current = HEAD;
+
+        for lvl in (0..self.level).rev() {
+            while let Some(next) = self.next(current, lvl) {
+                let next_value = self.nodes[next].value;
+                if next_value >= target {
+                    break;
+                }
+
+                if let Some(steps) = steps.as_deref_mut() {
+                    steps.push(Step {
+                        description: format!("Level {}: {} < {}, moving right", lvl, next_value, target),
+                        highlight_indices: self.render_index(next, lvl).into_iter().collect(),
+                        active_indices: vec![],
+                        metadata: serde_json::json!({
+                            "operation": "move_right",
+                            "level": lvl,
+                            "value": next_value
+                        }),
+                        detail: None,
+                    });
+                }
+
+                current = next;
+            }
+
+            if let Some(steps) = steps.as_deref_mut() {
+                let reason = match self.next(current, lvl) {
+                    Some(next) => format!("next value {} is not less than {}", self.nodes[next].value, target),
+                    None => "end of level reached".to_string(),
+                };
+
+                steps.push(Step {
+                    description: if lvl > 0 {
+                        format!("Level {}: {}, dropping down to level {}", lvl, reason, lvl - 1)
+                    } else {
+                        format!("Level 0: {}", reason)
+                    },
+                    highlight_indices: self.render_index(current, lvl).into_iter().collect(),
+                    active_indices: vec![],
+                    metadata: serde_json::json!({
+                        "operation": "drop_down",
+                        "level": lvl
+                    }),
+                    detail: None,
+                });
+            }
+
+            update[lvl] = current;
+        }
+
+        update
+    }
+
+    /// Flip coins until tails (or `max_level`), returning the level count and the flips.
+    fn random_level(&mut self) -> (usize, Vec<bool>) {
+        let mut flips = Vec::new();
+        let mut levels = 1;
+
+        while levels < self.max_level {
+            let heads = self.rng.next_in_range(0, 1) == 1;
+            flips.push(heads);
+            if !heads {
+                break;
+            }
+            levels += 1;
+        }
+
+        (levels, flips)
+    }
+
+    fn insert_impl(&mut self, value: i32, mut steps: Option<&mut Vec<Step>>) -> bool {
+        let mut update = self.descend(value, steps.as_deref_mut());
+
+        if self.next(update[0], 0).is_some_and(|next| self.nodes[next].value == value) {
+            if let Some(steps) = steps {
+                steps.push(Step {
+                    description: format!("{} is already in the skip list", value),
+                    highlight_indices: vec![],
+                    active_indices: vec![],
+                    metadata: serde_json::json!({ "duplicate": true }),
+                    detail: None,
+                });
+            }
+            return false;
+        }
+
+        let (levels, flips) = self.random_level();
+
+        if let Some(steps) = steps.as_deref_mut() {
+            for (i, &heads) in flips.iter().enumerate() {
+                steps.push(Step {
+                    description: if heads {
+                        format!("Coin flip {}: heads, promoting {} to level {}", i + 1, value, i + 1)
+                    } else {
+                        format!("Coin flip {}: tails, {} stays at {} level(s)", i + 1, value, levels)
+                    },
+                    highlight_indices: vec![],
+                    active_indices: vec![],
+                    metadata: serde_json::json!({
+                        "operation": "coin_flip",
+                        "heads": heads,
+                        "levels": i + 1 + heads as usize
+                    }),
+                    detail: None,
+                });
+            }
+        }
+
+        if levels > self.level {
+            // New levels start from the head
+            for slot in update.iter_mut().take(levels).skip(self.level) {
+                *slot = HEAD;
+            }
+            self.level = levels;
+        }
+
+        let node = Node {
+            value,
+            forward: vec![None; levels],
+        };
+        let id = match self.free.pop() {
+            Some(id) => {
+                self.nodes[id] = node;
+                id
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        };
+
+        for (lvl, &prev) in update.iter().enumerate().take(levels) {
+            self.nodes[id].forward[lvl] = self.nodes[prev].forward[lvl];
+            self.nodes[prev].forward[lvl] = Some(id);
+        }
+
+        self.length += 1;
+
+        if let Some(steps) = steps {
+            steps.push(Step {
+                description: format!("Inserted {} on {} level(s)", value, levels),
+                highlight_indices: vec![],
+                active_indices: self.tower_indices(id),
+                metadata: serde_json::json!({
+                    "inserted": value,
+                    "levels": levels,
+                    "flips": flips
+                }),
+                detail: None,
+            });
+        }
+
+        true
+    }
+
+    fn delete_impl(&mut self, value: i32, mut steps: Option<&mut Vec<Step>>) -> bool {
+        let update = self.descend(value, steps.as_deref_mut());
+
+        let target = match self.next(update[0], 0) {
+            Some(next) if self.nodes[next].value == value => next,
+            _ => {
+                if let Some(steps) = steps {
+                    steps.push(Step {
+                        description: format!("{} is not in the skip list", value),
+                        highlight_indices: vec![],
+                        active_indices: vec![],
+                        metadata: serde_json::json!({ "found": false }),
+                        detail: None,
+                    });
+                }
+                return false;
+            }
+        };
+
+        let levels = self.nodes[target].forward.len();
+
+        if let Some(steps) = steps.as_deref_mut() {
+            steps.push(Step {
+                description: format!("Unlinking {} from {} level(s)", value, levels),
+                highlight_indices: self.tower_indices(target),
+                active_indices: vec![],
+                metadata: serde_json::json!({
+                    "operation": "unlink",
+                    "levels": levels
+                }),
+                detail: None,
+            });
+        }
+
+        for (lvl, &prev) in update.iter().enumerate().take(levels) {
+            self.nodes[prev].forward[lvl] = self.nodes[target].forward[lvl];
+        }
+        self.nodes[target].forward.clear();
+        self.free.push(target);
+        self.length -= 1;
+
+        // Drop levels that became empty
+        while self.level > 1 && self.next(HEAD, self.level - 1).is_none() {
+            self.level -= 1;
+        }
+
+        if let Some(steps) = steps {
+            steps.push(Step {
+                description: format!("Deleted {}, {} level(s) in use", value, self.level),
+                highlight_indices: vec![],
+                active_indices: vec![],
+                metadata: serde_json::json!({
+                    "deleted": value,
+                    "level": self.level
+                }),
+                detail: None,
+            });
+        }
+
+        true
+    }
+
+    fn search_with_steps(&self, value: i32) -> Vec<Step> {
+        let mut steps = vec![Step {
+            description: format!("Searching for {}, starting on level {}", value, self.level - 1),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": "search",
+                "target": value
+            }),
+            detail: None,
+        }];
+
+        let update = self.descend(value, Some(&mut steps));
+
+        let final_step = match self.next(update[0], 0) {
+            Some(next) if self.nodes[next].value == value => Step {
+                description: format!("Found {}", value),
+                highlight_indices: vec![],
+                active_indices: self.render_index(next, 0).into_iter().collect(),
+                metadata: serde_json::json!({ "found": true }),
+                detail: None,
+            },
+            _ => Step {
+                description: format!("{} not found", value),
+                highlight_indices: vec![],
+                active_indices: vec![],
+                metadata: serde_json::json!({ "found": false }),
+                detail: None,
+            },
+        };
+        steps.push(final_step);
+
+        steps
+    }
+}
+
+impl Default for VisualizableSkipList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Visualizable for VisualizableSkipList {
+    fn execute_with_steps(&mut self, operation: Operation) -> Result<Vec<Step>> {
+        traced("skip_list", operation, || match operation {
+            Operation::Insert(_, value) => {
+                let mut steps = vec![Step {
+                    description: format!("Inserting {} into skip list", value),
+                    highlight_indices: vec![],
+                    active_indices: vec![],
+                    metadata: serde_json::json!({
+                        "operation": "insert",
+                        "value": value
+                    }),
+                    detail: None,
+                }];
+                self.insert_impl(value, Some(&mut steps));
+                Ok(steps)
+            }
+
+            Operation::Search(value) => Ok(self.search_with_steps(value)),
+
+            Operation::Delete(value_as_idx) => {
+                // Like the trees, the payload is interpreted as the value to delete
+                let value = value_as_idx as i32;
+                let mut steps = vec![Step {
+                    description: format!("Deleting {} from skip list", value),
+                    highlight_indices: vec![],
+                    active_indices: vec![],
+                    metadata: serde_json::json!({
+                        "operation": "delete",
+                        "value": value
+                    }),
+                    detail: None,
+                }];
+                self.delete_impl(value, Some(&mut steps));
+                Ok(steps)
+            }
+
+            _ => Err(DsavError::Visualization(
+                "Operation not supported for skip lists".to_string(),
+            )),
+        })
+    }
+
+    fn execute(&mut self, operation: Operation) -> Result<()> {
+        match operation {
+            Operation::Insert(_, value) => {
+                self.insert(value);
+                Ok(())
+            }
+            Operation::Delete(value_as_idx) => {
+                self.delete(value_as_idx as i32);
+                Ok(())
+            }
+            _ => self.execute_with_steps(operation).map(|_| ()),
+        }
+    }
+
+    /// Elements are laid out level by level from the bottom, each level left
+    /// to right, with sublabel `L<level>`. Connections link neighbours on a
+    /// level and each node to its copy on the level below.
+    fn render_state(&self) -> RenderState {
+        if self.is_empty() {
+            return RenderState::empty_placeholder("Skip list is empty", "Insert a value to add nodes");
+        }
+
+        let mut elements = Vec::new();
+        let mut connections = Vec::new();
+        // Render index of each node on the level below, for vertical links
+        let mut below: Vec<Option<usize>> = vec![None; self.nodes.len()];
+
+        for lvl in 0..self.level {
+            let mut previous = None;
+            let mut current_level = vec![None; self.nodes.len()];
+
+            for id in self.level_nodes(lvl) {
+                let idx = elements.len();
+                let value = self.nodes[id].value;
+
+                elements.push(
+                    RenderElement::new(value)
+                        .with_label(value.to_string())
+                        .with_sublabel(format!("L{}", lvl))
+                        .with_state(if lvl > 0 { ElementState::Active } else { ElementState::Normal }),
+                );
+
+                if let Some(prev) = previous {
+                    connections.push((prev, idx));
+                }
+                if let Some(lower) = below[id] {
+                    connections.push((idx, lower));
+                }
+
+                previous = Some(idx);
+                current_level[id] = Some(idx);
+            }
+
+            below = current_level;
+        }
+
+        RenderState {
+            elements,
+            connections,
+            cols: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seeded(values: &[i32]) -> VisualizableSkipList {
+        let mut list = VisualizableSkipList::with_seed(4, 7);
+        for &value in values {
+            list.insert(value);
+        }
+        list
+    }
+
+    #[test]
+    fn test_skip_list_search() {
+        let list = seeded(&[30, 10, 50, 20, 40]);
+
+        for value in [10, 20, 30, 40, 50] {
+            assert!(list.search(value));
+        }
+        assert!(!list.search(25));
+        assert!(!list.search(99));
+        assert_eq!(list.levels()[0], vec![10, 20, 30, 40, 50]);
+    }
+
+    #[test]
+    fn test_skip_list_levels_respect_max() {
+        for max_level in 1..=4 {
+            let mut list = VisualizableSkipList::with_seed(max_level, 42);
+            for value in 0..200 {
+                list.insert(value);
+            }
+
+            assert!(list.level() <= max_level);
+            assert!(list.levels().len() <= max_level);
+            // Every upper level is a sorted subset of the one below
+            for pair in list.levels().windows(2) {
+                assert!(pair[1].iter().all(|v| pair[0].contains(v)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_skip_list_seed_is_reproducible() {
+        let values = [5, 3, 8, 1, 9, 2, 7];
+        assert_eq!(seeded(&values).levels(), seeded(&values).levels());
+    }
+
+    #[test]
+    fn test_skip_list_delete() {
+        let mut list = seeded(&[10, 20, 30]);
+
+        assert!(list.delete(20));
+        assert!(!list.delete(20));
+        assert!(!list.search(20));
+        assert_eq!(list.len(), 2);
+        assert!(list.levels().iter().all(|level| !level.contains(&20)));
+
+        list.insert(20);
+        assert_eq!(list.levels()[0], vec![10, 20, 30]);
+    }
+
+    #[test]
+    fn test_skip_list_insert_steps() {
+        let mut list = seeded(&[10, 30]);
+
+        let steps = list.execute_with_steps(Operation::Insert(0, 20)).unwrap();
+        let last = steps.last().unwrap();
+        let levels = last.metadata["levels"].as_u64().unwrap() as usize;
+
+        assert_eq!(last.active_indices.len(), levels);
+        assert!(steps.iter().any(|s| s.metadata["operation"] == "drop_down"));
+
+        let state = list.render_state();
+        for &idx in &last.active_indices {
+            assert_eq!(state.elements[idx].value, 20);
+        }
+
+        let duplicate = list.execute_with_steps(Operation::Insert(0, 20)).unwrap();
+        assert_eq!(duplicate.last().unwrap().metadata["duplicate"], true);
+    }
+
+    #[test]
+    fn test_skip_list_search_steps() {
+        let mut list = seeded(&[10, 20, 30, 40, 50]);
+
+        let steps = list.execute_with_steps(Operation::Search(40)).unwrap();
+        let last = steps.last().unwrap();
+        assert_eq!(last.metadata["found"], true);
+        assert_eq!(list.render_state().elements[last.active_indices[0]].value, 40);
+
+        // One drop-down step per level
+        let drops = steps.iter().filter(|s| s.metadata["operation"] == "drop_down").count();
+        assert_eq!(drops, list.level());
+
+        let steps = list.execute_with_steps(Operation::Search(45)).unwrap();
+        assert_eq!(steps.last().unwrap().metadata["found"], false);
+    }
+
+    #[test]
+    fn test_skip_list_render_state() {
+        let list = seeded(&[10, 20, 30]);
+        let state = list.render_state();
+
+        let total: usize = list.levels().iter().map(Vec::len).sum();
+        assert_eq!(state.elements.len(), total);
+        assert_eq!(state.elements[0].sublabel, "L0");
+        assert!(VisualizableSkipList::new().render_state().is_empty_placeholder());
+    }
+}
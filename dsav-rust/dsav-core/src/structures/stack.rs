@@ -5,6 +5,7 @@
 
 use crate::error::{DsavError, Result};
 use crate::state::{ElementState, RenderElement, RenderState};
+use crate::instrument::traced;
 use crate::traits::{Operation, Step, Visualizable};
 
 const DEFAULT_CAPACITY: usize = 16;
@@ -69,6 +70,90 @@ impl VisualizableStack {
     pub fn clear(&mut self) {
         self.data.clear();
     }
+
+    /// The elements from bottom to top.
+    pub fn to_vec(&self) -> Vec<i32> {
+        self.data.clone()
+    }
+
+    /// Push each value in order, animating every insertion followed by a
+    /// separator step. Stops with a "full" step instead of an error once
+    /// capacity is reached, leaving the remaining values out.
+    pub fn push_many_with_steps(&mut self, values: &[i32]) -> Vec<Step> {
+        let mut steps = Vec::new();
+
+        steps.push(Step {
+            description: format!("Pushing {} value(s) onto the stack", values.len()),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": "push_many",
+                "values": values.to_vec()
+            }),
+            detail: None,
+        });
+
+        for (i, &value) in values.iter().enumerate() {
+            if self.is_full() {
+                steps.push(Step {
+                    description: format!(
+                        "Stack is full (capacity {}), {} value(s) not added",
+                        self.capacity,
+                        values.len() - i
+                    ),
+                    highlight_indices: vec![],
+                    active_indices: vec![],
+                    metadata: serde_json::json!({
+                        "operation": "full",
+                        "capacity": self.capacity,
+                        "remaining": values[i..].to_vec()
+                    }),
+                    detail: None,
+                });
+                break;
+            }
+
+            // Capacity was checked above, so the single-value operation succeeds
+            if let Ok(value_steps) = self.execute_with_steps(Operation::Push(value)) {
+                steps.extend(value_steps);
+            }
+
+            steps.push(Step {
+                description: format!("Added {} of {}", i + 1, values.len()),
+                highlight_indices: vec![],
+                active_indices: vec![],
+                metadata: serde_json::json!({
+                    "operation": "separator",
+                    "added": i + 1,
+                    "stack_state": self.data.clone()
+                }),
+                detail: None,
+            });
+        }
+
+        steps
+    }
+
+    /// Format the stack top-down, one element per line, marking the top.
+    pub fn pretty_print(&self) -> String {
+        if self.data.is_empty() {
+            return "(empty)".to_string();
+        }
+
+        self.data
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(i, value)| {
+                if i == 0 {
+                    format!("{} <- TOP", value)
+                } else {
+                    value.to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
 }
 
 impl Default for VisualizableStack {
@@ -79,7 +164,7 @@ impl Default for VisualizableStack {
 
 impl Visualizable for VisualizableStack {
     fn execute_with_steps(&mut self, operation: Operation) -> Result<Vec<Step>> {
-        match operation {
+        traced("stack", operation, || match operation {
             Operation::Push(value) => {
                 let mut steps = Vec::new();
 
@@ -91,6 +176,7 @@ impl Visualizable for VisualizableStack {
                         "operation": "push",
                         "value": value
                     }),
+                    detail: None,
                 });
 
                 self.push(value)?;
@@ -103,6 +189,7 @@ impl Visualizable for VisualizableStack {
                     metadata: serde_json::json!({
                         "top_index": top_index
                     }),
+                    detail: None,
                 });
 
                 Ok(steps)
@@ -121,6 +208,7 @@ impl Visualizable for VisualizableStack {
                     metadata: serde_json::json!({
                         "value": value
                     }),
+                    detail: None,
                 });
 
                 self.pop()?;
@@ -130,6 +218,7 @@ impl Visualizable for VisualizableStack {
                     highlight_indices: vec![],
                     active_indices: vec![],
                     metadata: serde_json::json!({}),
+                    detail: None,
                 });
 
                 Ok(steps)
@@ -138,10 +227,22 @@ impl Visualizable for VisualizableStack {
             _ => Err(DsavError::InvalidState {
                 reason: "Operation not supported for stacks".to_string(),
             }),
+        })
+    }
+
+    fn execute(&mut self, operation: Operation) -> Result<()> {
+        match operation {
+            Operation::Push(value) => self.push(value),
+            Operation::Pop => self.pop().map(|_| ()),
+            _ => self.execute_with_steps(operation).map(|_| ()),
         }
     }
 
     fn render_state(&self) -> RenderState {
+        if self.data.is_empty() {
+            return RenderState::empty_placeholder("Stack is empty", "Push a value to add elements");
+        }
+
         RenderState {
             elements: self
                 .data
@@ -164,6 +265,7 @@ impl Visualizable for VisualizableStack {
                 })
                 .collect(),
             connections: Vec::new(),
+            cols: None,
         }
     }
 }
@@ -209,4 +311,48 @@ mod tests {
         let mut stack = VisualizableStack::new();
         assert!(stack.pop().is_err());
     }
+
+    #[test]
+    fn test_stack_pretty_print() {
+        let mut stack = VisualizableStack::new();
+        assert_eq!(stack.pretty_print(), "(empty)");
+
+        stack.push(10).unwrap();
+        stack.push(20).unwrap();
+        stack.push(30).unwrap();
+        assert_eq!(stack.pretty_print(), "30 <- TOP\n20\n10");
+    }
+
+    #[test]
+    fn test_stack_push_many() {
+        let mut stack = VisualizableStack::new();
+        let steps = stack.push_many_with_steps(&[1, 2, 3]);
+
+        assert_eq!(stack.pop().unwrap(), 3);
+        assert_eq!(stack.peek().unwrap(), 2);
+
+        // Start step, then two push steps and a separator per value
+        assert_eq!(steps.len(), 1 + 3 * 3);
+        let separators: Vec<&Step> = steps.iter().filter(|s| s.metadata["operation"] == "separator").collect();
+        assert_eq!(separators.len(), 3);
+        assert_eq!(separators[2].metadata["stack_state"], serde_json::json!([1, 2, 3]));
+    }
+
+    #[test]
+    fn test_stack_push_many_stops_when_full() {
+        let mut stack = VisualizableStack::with_capacity(2);
+        let steps = stack.push_many_with_steps(&[1, 2, 3]);
+
+        assert_eq!(stack.len(), 2);
+        let last = steps.last().unwrap();
+        assert_eq!(last.metadata["operation"], "full");
+        assert_eq!(last.metadata["remaining"], serde_json::json!([3]));
+    }
+
+    #[test]
+    fn test_stack_empty_placeholder() {
+        let state = VisualizableStack::new().render_state();
+        assert!(state.is_empty_placeholder());
+        assert!(state.connections.is_empty());
+    }
 }
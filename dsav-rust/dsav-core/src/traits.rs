@@ -10,31 +10,110 @@ pub struct Step {
     pub highlight_indices: Vec<usize>,
     pub active_indices: Vec<usize>,
     pub metadata: serde_json::Value,
+    /// Longer explanation shown alongside the one-line `description`,
+    /// such as the invariant a step maintains.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
 }
 
-#[derive(Debug, Clone, Copy)]
+impl Step {
+    /// The only step a traversal records when there is nothing to visit.
+    pub fn empty_traversal() -> Self {
+        Self {
+            description: "Structure is empty, nothing to traverse".to_string(),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({
+                "operation": "traverse",
+                "empty": true
+            }),
+            detail: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Operation {
     Insert(usize, i32),
     Delete(usize),
     Update(usize, i32),
+    Append(i32),
+    Replace(i32, i32),
+    GetRange(usize, usize),
+    FillRange(usize, usize, i32),
+    Clamp(i32, i32),
+    Scale(i32),
+    RemoveDuplicates,
+    RemoveValue(i32),
+    ReverseArray,
+    PartitionByParity,
+    DutchFlag(i32),
     Search(i32),
+    SearchWithBudget(i32, usize),
     BinarySearch(i32),
+    SearchRange(i32),
+    TwoSum(i32),
+    InsertBalanced(i32),
     Traverse,
     PreOrderTraverse,
     PostOrderTraverse,
     LevelOrderTraverse,
+    ZigzagTraverse,
+    MorrisInOrder,
+    MirrorTree,
+    IsBalanced,
+    IsSymmetric,
+    ShowLevelWidths,
+    Rank(i32),
+    Select(usize),
+    PathToRoot(i32),
+    FindClosest(i32),
+    InOrderPredecessorSuccessorPair(i32),
+    RangeQuery(i32, i32),
     Push(i32),
     Pop,
     Enqueue(i32),
     Dequeue,
+    Interleave,
+    SplitAt(usize),
+    RotateLeft(usize),
+    RbRotateLeft(i32),
+    RbRotateRight(i32),
+    RebalanceAround(i32),
+    RebuildBalanced,
+    AnalyzeHeight,
     BubbleSort,
     InsertionSort,
     SelectionSort,
+    StableInsertionSort,
+    UnstableSelectionSort,
     MergeSort,
     QuickSort,
+    BucketSort,
+    Heapify,
+    MaxSubarray,
+    LongestIncreasingSubsequence,
+    Sum,
+    Average,
+    Count,
+    CountInversions,
+    Compress,
+    IsValidBstArray,
+    FindDuplicate,
+    Median,
+    Shuffle,
+    Transpose,
 }
 
 pub trait Visualizable {
     fn execute_with_steps(&mut self, operation: Operation) -> Result<Vec<Step>>;
     fn render_state(&self) -> RenderState;
+
+    /// Apply an operation without keeping its animation steps.
+    ///
+    /// The default runs `execute_with_steps` and discards the result. Structures
+    /// override this to skip building steps for their basic mutations.
+    fn execute(&mut self, operation: Operation) -> Result<()> {
+        self.execute_with_steps(operation).map(|_| ())
+    }
 }
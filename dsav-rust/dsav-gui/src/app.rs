@@ -2,6 +2,7 @@
 
 use dsav_core::{
     structures::VisualizableArray,
+    structures::VisualizableMatrix,
     structures::VisualizableStack,
     structures::VisualizableQueue,
     structures::VisualizableLinkedList,
@@ -11,31 +12,117 @@ use dsav_core::{
     Visualizable,
     Step
 };
-use crate::colors::{Colors, Theme, ColorPalette};
+use crate::colors::{Theme, ColorPalette};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum DataStructure {
     Array,
+    Matrix,
     Stack,
     Queue,
     LinkedList,
-    BST,
+    Bst,
     RBTree,
 }
 
+/// Changes highlighted briefly after new data replaces a structure's contents.
+struct LoadDiff {
+    structure: DataStructure,
+    diff: dsav_core::RenderDiff,
+    remaining: f32,
+}
+
+/// A copy of one structure, taken when macro recording starts so a replay
+/// begins from the same contents.
+enum StructureSnapshot {
+    Array(VisualizableArray),
+    Matrix(VisualizableMatrix),
+    Stack(VisualizableStack),
+    Queue(VisualizableQueue),
+    LinkedList(VisualizableLinkedList),
+    Bst(VisualizableBST),
+    RBTree(VisualizableRBTree),
+}
+
+/// Operations recorded on one structure, for replaying a demo.
+#[derive(Debug, Default)]
+struct OperationMacro {
+    recording: bool,
+    structure: Option<DataStructure>,
+    operations: Vec<Operation>,
+}
+
+impl OperationMacro {
+    /// Start a new recording on `structure`, dropping any earlier one.
+    fn start(&mut self, structure: DataStructure) {
+        self.recording = true;
+        self.structure = Some(structure);
+        self.operations.clear();
+    }
+
+    fn stop(&mut self) {
+        self.recording = false;
+    }
+
+    /// Keep `operation` if recording and it ran on the recorded structure.
+    fn record(&mut self, structure: DataStructure, operation: Operation) {
+        if self.recording && self.structure == Some(structure) {
+            self.operations.push(operation);
+        }
+    }
+
+    fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// The recorded operations in order, as a queue for the replay driver.
+    fn replay(&self) -> std::collections::VecDeque<Operation> {
+        self.operations.iter().copied().collect()
+    }
+}
+
+/// Font family for element labels in every view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LabelFont {
+    Proportional,
+    Monospace,
+}
+
+/// How the array is drawn: indexed boxes, or a bar chart of the values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArrayViewMode {
+    Boxes,
+    Bars,
+}
+
 pub struct DsavApp {
     selected_structure: DataStructure,
     array: VisualizableArray,
+    matrix: VisualizableMatrix,
     stack: VisualizableStack,
     queue: VisualizableQueue,
     linked_list: VisualizableLinkedList,
+    /// Nodes cut off by the last split, kept so they can be reattached.
+    split_tail: Option<VisualizableLinkedList>,
     bst: VisualizableBST,
     rb_tree: VisualizableRBTree,
 
     input_value: i32,
     input_index: usize,
     search_value: i32,
+    /// Comparisons allowed before a budgeted linear search gives up.
+    search_budget: usize,
+    /// Inclusive bounds for BST range queries.
+    range_low: i32,
+    range_high: i32,
+    /// Text whose brackets the stack view checks.
+    bracket_input: String,
     randomize_size: usize,
+    /// Shape used when randomizing the matrix.
+    matrix_rows: usize,
+    matrix_cols: usize,
+    use_seed: bool,
+    seed: u64,
 
     status_message: String,
     current_steps: Vec<Step>,
@@ -43,13 +130,76 @@ pub struct DsavApp {
     playing: bool,
     animation_speed: f32,
     time_since_last_step: f32,
+    /// Duration multipliers for steps whose `operation` is a comparison, or
+    /// a swap/placement.
+    compare_delay: f32,
+    swap_delay: f32,
 
     current_theme: Theme,
     show_settings: bool,
     show_nil_nodes: bool,
     tree_zoom: f32,
+    max_steps: usize,
+    array_element_size: egui::Vec2,
+    array_font_size: f32,
+    array_auto_width: bool,
+    /// Wrap long arrays onto several rows instead of one scrolling row.
+    array_wrap: bool,
+    /// Number array, queue and list index labels from 1. Only the labels
+    /// change; step indices stay 0-based.
+    one_based_indices: bool,
+    array_view_mode: ArrayViewMode,
+    /// Family and size multiplier for element labels, shared by all views.
+    label_font: LabelFont,
+    label_font_scale: f32,
+    /// Highlight what changed when randomizing replaces the data.
+    show_load_diff: bool,
+    load_diff: Option<LoadDiff>,
+    /// Render index of the tree node clicked in the BST or RB tree view.
+    selected_node: Option<usize>,
+    /// Tree scroll offset requested by a minimap click, applied next frame.
+    tree_scroll_target: Option<egui::Vec2>,
+    /// Hide tree nodes deeper than `max_tree_depth` (the root is depth 0).
+    limit_tree_depth: bool,
+    max_tree_depth: usize,
+    /// Render indices of tree nodes whose whole subtree is shown despite
+    /// the depth limit.
+    expanded_subtrees: std::collections::HashSet<usize>,
+    custom_palette: ColorPalette,
+    operation_macro: OperationMacro,
+    /// Selected structure as it was when recording started.
+    macro_snapshot: Option<StructureSnapshot>,
+    /// Recorded operations still to run; the next one starts once the
+    /// current animation finishes.
+    replay_queue: std::collections::VecDeque<Operation>,
+    /// Step count and generation time of the last operation run.
+    last_generation: Option<(usize, std::time::Duration)>,
 }
 
+/// Default cap on animation steps so large sorts don't exhaust memory.
+const DEFAULT_MAX_STEPS: usize = 5000;
+
+/// Horizontal space between array boxes.
+const ARRAY_BOX_GAP: f32 = 8.0;
+
+/// Seconds each step is shown at 1x speed, before per-kind multipliers.
+const BASE_STEP_SECONDS: f32 = 0.5;
+
+/// Smallest label font size, so zoomed-out trees stay legible.
+const MIN_LABEL_FONT_SIZE: f32 = 8.0;
+
+/// Gap between a collapsed node and its "hidden" indicator.
+const COLLAPSE_INDICATOR_OFFSET: f32 = 30.0;
+
+/// Largest width or height of the tree minimap.
+const MINIMAP_MAX_SIZE: f32 = 160.0;
+
+/// Seconds the load diff stays highlighted.
+const LOAD_DIFF_SECONDS: f32 = 1.5;
+
+/// Quick-pick animation speeds shown next to the speed slider.
+const SPEED_PRESETS: [f32; 4] = [0.5, 1.0, 2.0, 4.0];
+
 impl DsavApp {
     pub fn new() -> Self {
         let mut array = VisualizableArray::new(16);
@@ -79,36 +229,89 @@ impl DsavApp {
         rb_tree.insert(60);
         rb_tree.insert(80);
 
+        dsav_core::set_max_steps(Some(DEFAULT_MAX_STEPS));
+
         Self {
             selected_structure: DataStructure::Array,
             array,
+            matrix: VisualizableMatrix::from_rows(&[vec![1, 2, 3], vec![4, 5, 6]])
+                .unwrap_or_else(|_| VisualizableMatrix::new(2, 3)),
             stack: VisualizableStack::with_capacity(16),
             queue: VisualizableQueue::with_capacity(16),
             linked_list,
+            split_tail: None,
             bst,
             rb_tree,
             input_value: 42,
             input_index: 0,
             search_value: 30,
+            search_budget: 3,
+            range_low: 25,
+            range_high: 55,
+            bracket_input: "([]{})".to_string(),
             randomize_size: 8,
+            matrix_rows: 3,
+            matrix_cols: 4,
+            use_seed: false,
+            seed: 42,
             status_message: "Ready. Select an operation to visualize.".to_string(),
             current_steps: Vec::new(),
             current_step_index: 0,
             playing: false,
             animation_speed: 1.0,
             time_since_last_step: 0.0,
+            compare_delay: 1.0,
+            swap_delay: 1.0,
             current_theme: Theme::Vibrant,
             show_settings: false,
             show_nil_nodes: false,
             tree_zoom: 1.0,
+            max_steps: DEFAULT_MAX_STEPS,
+            array_element_size: egui::vec2(60.0, 60.0),
+            array_font_size: 20.0,
+            array_auto_width: true,
+            array_wrap: false,
+            one_based_indices: false,
+            array_view_mode: ArrayViewMode::Boxes,
+            label_font: LabelFont::Monospace,
+            label_font_scale: 1.0,
+            show_load_diff: false,
+            load_diff: None,
+            selected_node: None,
+            tree_scroll_target: None,
+            limit_tree_depth: false,
+            max_tree_depth: 4,
+            expanded_subtrees: std::collections::HashSet::new(),
+            custom_palette: crate::colors::saved_custom_palette().unwrap_or_else(ColorPalette::vibrant),
+            operation_macro: OperationMacro::default(),
+            macro_snapshot: None,
+            replay_queue: std::collections::VecDeque::new(),
+            last_generation: None,
         }
     }
 
     pub fn update(&mut self, delta_time: f32) {
+        if let Some(load_diff) = &mut self.load_diff {
+            load_diff.remaining -= delta_time;
+            if load_diff.remaining <= 0.0 {
+                self.load_diff = None;
+            }
+        }
+
+        // Pausing partway through an animation holds the replay there
+        let animation_done = self.current_step_index + 1 >= self.current_steps.len();
+        if !self.playing && animation_done && !self.replay_queue.is_empty() {
+            self.replay_next();
+        }
+
         if self.playing && !self.current_steps.is_empty() {
             self.time_since_last_step += delta_time * self.animation_speed;
 
-            let step_duration = 0.5;
+            let step_duration = BASE_STEP_SECONDS
+                * self
+                    .current_steps
+                    .get(self.current_step_index)
+                    .map_or(1.0, |step| step_delay_multiplier(step, self.compare_delay, self.swap_delay));
             if self.time_since_last_step >= step_duration {
                 self.time_since_last_step = 0.0;
 
@@ -131,7 +334,7 @@ impl DsavApp {
 
         self.update(ctx.input(|i| i.stable_dt));
 
-        if self.playing {
+        if self.playing || self.load_diff.is_some() || !self.replay_queue.is_empty() {
             ctx.request_repaint();
         }
 
@@ -163,6 +366,7 @@ impl DsavApp {
                 .auto_shrink([false, false])
                 .show(ui, |ui| {
                     ui.label("Data Structure:");
+                    let previous_structure = self.selected_structure;
                     ui.horizontal(|ui| {
                         ui.selectable_value(&mut self.selected_structure, DataStructure::Array, "📊 Array");
                         ui.selectable_value(&mut self.selected_structure, DataStructure::Stack, "📚 Stack");
@@ -172,21 +376,33 @@ impl DsavApp {
                         ui.selectable_value(&mut self.selected_structure, DataStructure::LinkedList, "🔗 List");
                     });
                     ui.horizontal(|ui| {
-                        ui.selectable_value(&mut self.selected_structure, DataStructure::BST, "🌲 BST");
+                        ui.selectable_value(&mut self.selected_structure, DataStructure::Bst, "🌲 BST");
                         ui.selectable_value(&mut self.selected_structure, DataStructure::RBTree, "🔴⚫ RB-Tree");
                     });
+                    ui.horizontal(|ui| {
+                        ui.selectable_value(&mut self.selected_structure, DataStructure::Matrix, "▦ Matrix");
+                    });
+                    if self.selected_structure != previous_structure {
+                        self.selected_node = None;
+                        self.expanded_subtrees.clear();
+                    }
 
                     ui.add_space(16.0);
 
                     match self.selected_structure {
                         DataStructure::Array => self.array_controls(ui),
+                        DataStructure::Matrix => self.matrix_controls(ui),
                         DataStructure::Stack => self.stack_controls(ui),
                         DataStructure::Queue => self.queue_controls(ui),
                         DataStructure::LinkedList => self.linked_list_controls(ui),
-                        DataStructure::BST => self.bst_controls(ui),
+                        DataStructure::Bst => self.bst_controls(ui),
                         DataStructure::RBTree => self.rb_tree_controls(ui),
                     }
 
+                    ui.add_space(16.0);
+                    ui.separator();
+                    self.macro_controls(ui);
+
                     ui.add_space(16.0);
                     ui.separator();
 
@@ -197,6 +413,10 @@ impl DsavApp {
                             ui.label(format!("Utilization: {:.1}%",
                                 (self.array.len() as f32 / self.array.capacity() as f32) * 100.0));
                         }
+                        DataStructure::Matrix => {
+                            ui.label(format!("Shape: {} × {}", self.matrix.rows(), self.matrix.cols()));
+                            ui.label(format!("Elements: {}", self.matrix.rows() * self.matrix.cols()));
+                        }
                         DataStructure::Stack => {
                             ui.label(format!("Size: {} / {}", self.stack.len(), self.stack.capacity()));
                             ui.label(format!("Utilization: {:.1}%",
@@ -215,7 +435,7 @@ impl DsavApp {
                                 format!("Head: {}", self.linked_list.get(0).unwrap_or(0))
                             });
                         }
-                        DataStructure::BST => {
+                        DataStructure::Bst => {
                             ui.label(format!("Nodes: {}", self.bst.size()));
                             ui.label(if self.bst.is_empty() {
                                 "Status: Empty".to_string()
@@ -237,6 +457,12 @@ impl DsavApp {
                         ui.add_space(16.0);
                         ui.separator();
                         self.render_animation_controls(ui);
+
+                        ui.add_space(8.0);
+                        self.render_recursion_stack(ui);
+
+                        ui.add_space(8.0);
+                        self.render_step_details(ui);
                     }
                 });
         });
@@ -246,6 +472,18 @@ impl DsavApp {
             ui.horizontal(|ui| {
                 ui.label("Status:");
                 ui.label(&self.status_message);
+
+                if !self.current_steps.is_empty() {
+                    let comparisons = count_comparisons_up_to(&self.current_steps, self.current_step_index);
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.label(format!("Comparisons: {}", comparisons));
+                        if let Some((step_count, elapsed)) = self.last_generation {
+                            ui.separator();
+                            ui.label(format!("Generated {} steps in {}", step_count, format_duration(elapsed)))
+                                .on_hover_text("Time spent building the steps, not playing them");
+                        }
+                    });
+                }
             });
             ui.add_space(4.0);
         });
@@ -256,10 +494,11 @@ impl DsavApp {
             ui.vertical_centered(|ui| {
                 ui.heading(match self.selected_structure {
                     DataStructure::Array => "📊 Array Visualization",
+                    DataStructure::Matrix => "▦ Matrix Visualization",
                     DataStructure::Stack => "📚 Stack Visualization (LIFO)",
                     DataStructure::Queue => "🎯 Queue Visualization (FIFO)",
                     DataStructure::LinkedList => "🔗 Linked List Visualization",
-                    DataStructure::BST => "🌲 Binary Search Tree Visualization",
+                    DataStructure::Bst => "🌲 Binary Search Tree Visualization",
                     DataStructure::RBTree => "🔴⚫ Red-Black Tree Visualization",
                 });
             });
@@ -278,10 +517,11 @@ impl DsavApp {
 
                     match self.selected_structure {
                         DataStructure::Array => self.render_array(ui),
+                        DataStructure::Matrix => self.render_matrix(ui),
                         DataStructure::Stack => self.render_stack(ui),
                         DataStructure::Queue => self.render_queue(ui),
                         DataStructure::LinkedList => self.render_linked_list(ui),
-                        DataStructure::BST => self.render_bst(ui),
+                        DataStructure::Bst => self.render_bst(ui),
                         DataStructure::RBTree => self.render_rb_tree(ui),
                     }
                 },
@@ -290,6 +530,14 @@ impl DsavApp {
     }
 
     fn array_controls(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("View:");
+            ui.selectable_value(&mut self.array_view_mode, ArrayViewMode::Boxes, "▦ Boxes");
+            ui.selectable_value(&mut self.array_view_mode, ArrayViewMode::Bars, "📊 Bars");
+        });
+
+        ui.add_space(8.0);
+
         ui.group(|ui| {
             ui.label("Insert / Delete / Update:");
 
@@ -308,6 +556,10 @@ impl DsavApp {
                     self.execute_array_operation(Operation::Insert(self.input_index, self.input_value));
                 }
 
+                if ui.button("➕ Append").clicked() {
+                    self.execute_array_operation(Operation::Append(self.input_value));
+                }
+
                 if ui.button("🗑 Delete").clicked() {
                     self.execute_array_operation(Operation::Delete(self.input_index));
                 }
@@ -334,8 +586,13 @@ impl DsavApp {
                 }
 
                 if ui.button("⚡ Binary Search").clicked() {
-                    // For binary search, sort the array instantly without animation
-                    match self.array.execute_with_steps(Operation::QuickSort) {
+                    // For binary search, sort the array instantly without animation.
+                    // Lift the step limit so a truncated sort can't leave it unsorted.
+                    dsav_core::set_max_steps(None);
+                    let sorted = self.array.execute_with_steps(Operation::QuickSort);
+                    dsav_core::set_max_steps(Some(self.max_steps));
+
+                    match sorted {
                         Ok(_) => {
                             // Array is now sorted, clear steps to skip animation
                             self.current_steps.clear();
@@ -350,6 +607,59 @@ impl DsavApp {
                     }
                 }
             });
+
+            ui.horizontal(|ui| {
+                ui.label("Budget:");
+                ui.add(egui::DragValue::new(&mut self.search_budget).speed(1.0));
+
+                if ui
+                    .button(format!("⏱ Search with {} Comparison(s)", self.search_budget))
+                    .on_hover_text("Linear search that gives up once the comparison budget runs out")
+                    .clicked()
+                {
+                    self.execute_array_operation(Operation::SearchWithBudget(self.search_value, self.search_budget));
+                }
+            });
+
+            if ui
+                .button(format!("👉👈 Two Sum = {}", self.search_value))
+                .on_hover_text("Two-pointer search for a pair summing to the value (array should be sorted)")
+                .clicked()
+            {
+                self.execute_array_operation(Operation::TwoSum(self.search_value));
+            }
+
+            if ui
+                .button(format!("↔ Range of {}", self.search_value))
+                .on_hover_text("Binary search for the first and last index of the value (array should be sorted)")
+                .clicked()
+            {
+                self.execute_array_operation(Operation::SearchRange(self.search_value));
+            }
+
+            if ui
+                .button(format!("🔁 Replace {} → {}", self.search_value, self.input_value))
+                .clicked()
+            {
+                self.execute_array_operation(Operation::Replace(self.search_value, self.input_value));
+            }
+
+            let (low, high) = (self.search_value.min(self.input_value), self.search_value.max(self.input_value));
+            if ui
+                .button(format!("📏 Clamp to [{}, {}]", low, high))
+                .on_hover_text("Clamp between the search and input values")
+                .clicked()
+            {
+                self.execute_array_operation(Operation::Clamp(low, high));
+            }
+
+            if ui
+                .button(format!("✖ Scale by {}", self.input_value))
+                .on_hover_text("Multiply every element by the input value; fails on overflow")
+                .clicked()
+            {
+                self.execute_array_operation(Operation::Scale(self.input_value));
+            }
         });
 
         ui.add_space(8.0);
@@ -376,6 +686,135 @@ impl DsavApp {
             if ui.button("⚡ Quick Sort").clicked() {
                 self.execute_array_operation(Operation::QuickSort);
             }
+
+            if ui.button("🪣 Bucket Sort").clicked() {
+                self.execute_array_operation(Operation::BucketSort);
+            }
+
+            if ui.button("⛰ Heapify (Max-Heap)")
+                .on_hover_text("Sift down each internal node, last to first, to build a max-heap in O(n)")
+                .clicked()
+            {
+                self.execute_array_operation(Operation::Heapify);
+            }
+        });
+
+        ui.add_space(8.0);
+
+        ui.group(|ui| {
+            ui.label("Sort Stability:")
+                .on_hover_text("Equal values are tagged (3a, 3b) to show whether their order survives the sort");
+
+            ui.horizontal(|ui| {
+                if ui.button("Stable (Insertion)").clicked() {
+                    self.execute_array_operation(Operation::StableInsertionSort);
+                }
+
+                if ui.button("Unstable (Selection)").clicked() {
+                    self.execute_array_operation(Operation::UnstableSelectionSort);
+                }
+            });
+        });
+
+        ui.add_space(8.0);
+
+        ui.group(|ui| {
+            ui.label("Algorithms:");
+
+            if ui.button("📈 Max Subarray (Kadane)").clicked() {
+                self.execute_array_operation(Operation::MaxSubarray);
+            }
+
+            if ui.button("📶 Longest Increasing Subsequence")
+                .on_hover_text("Fill the DP table of LIS lengths, then trace one LIS back")
+                .clicked()
+            {
+                self.execute_array_operation(Operation::LongestIncreasingSubsequence);
+            }
+
+            ui.horizontal(|ui| {
+                if ui.button("➕ Sum").clicked() {
+                    self.execute_array_operation(Operation::Sum);
+                }
+
+                if ui.button("➗ Average").clicked() {
+                    self.execute_array_operation(Operation::Average);
+                }
+
+                if ui.button("🧮 Count").clicked() {
+                    self.execute_array_operation(Operation::Count);
+                }
+            });
+
+            if ui.button("🎯 Median (Median of Medians)")
+                .on_hover_text("Deterministic selection; returns the lower median for even lengths")
+                .clicked()
+            {
+                self.execute_array_operation(Operation::Median);
+            }
+
+            if ui.button("🔢 Count Inversions")
+                .on_hover_text("Merge sort on a copy, counting out-of-order pairs")
+                .clicked()
+            {
+                self.execute_array_operation(Operation::CountInversions);
+            }
+
+            if ui.button("🗜 Compress (RLE)")
+                .on_hover_text("Run-length encode the array into (value, count) pairs")
+                .clicked()
+            {
+                self.execute_array_operation(Operation::Compress);
+            }
+
+            if ui.button("🌲 Is BST (Level Order)")
+                .on_hover_text("Read the array as a level-order tree and check each node's BST bounds")
+                .clicked()
+            {
+                self.execute_array_operation(Operation::IsValidBstArray);
+            }
+
+            if ui.button("🐢🐇 Find Duplicate (Floyd)")
+                .on_hover_text("Needs n + 1 values in 1..=n; values are followed as next indices")
+                .clicked()
+            {
+                self.execute_array_operation(Operation::FindDuplicate);
+            }
+
+            if ui.button("⚖ Evens Before Odds")
+                .on_hover_text("Two-pointer partition; order within each group is not kept")
+                .clicked()
+            {
+                self.execute_array_operation(Operation::PartitionByParity);
+            }
+
+            if ui
+                .button(format!("🚦 Dutch Flag around {}", self.search_value))
+                .on_hover_text("Three-way partition: less than, equal to, then greater than the search value")
+                .clicked()
+            {
+                self.execute_array_operation(Operation::DutchFlag(self.search_value));
+            }
+
+            if ui.button("🔃 Reverse").clicked() {
+                self.execute_array_operation(Operation::ReverseArray);
+            }
+
+            if ui.button("🔀 Shuffle (Fisher-Yates)").clicked() {
+                self.execute_array_operation(Operation::Shuffle);
+            }
+
+            if ui.button("🧹 Remove Duplicates").clicked() {
+                self.execute_array_operation(Operation::RemoveDuplicates);
+            }
+
+            if ui
+                .button(format!("✂ Remove All {}", self.search_value))
+                .on_hover_text("Compact the array in place, dropping every copy of the search value")
+                .clicked()
+            {
+                self.execute_array_operation(Operation::RemoveValue(self.search_value));
+            }
         });
 
         ui.add_space(8.0);
@@ -385,21 +824,21 @@ impl DsavApp {
 
             ui.horizontal(|ui| {
                 ui.label("Size:");
-                ui.add(egui::DragValue::new(&mut self.randomize_size).clamp_range(1..=16).speed(0.1));
+                ui.add(egui::DragValue::new(&mut self.randomize_size).range(1..=16).speed(0.1));
             });
 
-            if ui.button("🎲 Randomize").clicked() {
-                use rand::Rng;
-                let mut rng = rand::thread_rng();
+            self.seed_controls(ui);
 
+            if ui.button("🎲 Randomize").clicked() {
+                let before = self.structure_render_state();
                 self.array = dsav_core::structures::VisualizableArray::new(16);
-                for i in 0..self.randomize_size {
-                    let random_value = rng.gen_range(1..=100);
-                    let _ = self.array.insert(i, random_value);
+                for (i, value) in self.random_values().into_iter().enumerate() {
+                    let _ = self.array.insert(i, value);
                 }
 
                 self.current_steps.clear();
-                self.status_message = format!("Generated {} random elements", self.randomize_size);
+                self.status_message = self.randomized_message();
+                self.start_load_diff(&before);
             }
         });
 
@@ -416,6 +855,64 @@ impl DsavApp {
         });
     }
 
+    fn matrix_controls(&mut self, ui: &mut egui::Ui) {
+        ui.group(|ui| {
+            ui.label("Matrix Operations:");
+
+            if ui
+                .button("↔ Transpose")
+                .on_hover_text("Swap rows and columns, mirroring elements across the diagonal")
+                .clicked()
+            {
+                self.execute_matrix_operation(Operation::Transpose);
+            }
+
+            ui.horizontal(|ui| {
+                ui.label("Factor:");
+                ui.add(egui::DragValue::new(&mut self.input_value).speed(1.0));
+
+                if ui
+                    .button("✖ Scale")
+                    .on_hover_text("Multiply every element by the factor; fails on overflow")
+                    .clicked()
+                {
+                    self.execute_matrix_operation(Operation::Scale(self.input_value));
+                }
+            });
+        });
+
+        ui.add_space(8.0);
+
+        ui.group(|ui| {
+            ui.label("Randomize:");
+
+            ui.horizontal(|ui| {
+                ui.label("Rows:");
+                ui.add(egui::DragValue::new(&mut self.matrix_rows).range(1..=8).speed(0.1));
+                ui.label("Cols:");
+                ui.add(egui::DragValue::new(&mut self.matrix_cols).range(1..=8).speed(0.1));
+            });
+
+            self.seed_controls(ui);
+
+            if ui.button("🎲 Randomize").clicked() {
+                let before = self.structure_render_state();
+                let values = dsav_core::dataset::random_values(
+                    self.matrix_rows * self.matrix_cols,
+                    self.use_seed.then_some(self.seed),
+                );
+                let rows: Vec<Vec<i32>> = values.chunks(self.matrix_cols).map(<[i32]>::to_vec).collect();
+                if let Ok(matrix) = VisualizableMatrix::from_rows(&rows) {
+                    self.matrix = matrix;
+                }
+
+                self.current_steps.clear();
+                self.status_message = format!("Generated a {} × {} matrix", self.matrix_rows, self.matrix_cols);
+                self.start_load_diff(&before);
+            }
+        });
+    }
+
     fn stack_controls(&mut self, ui: &mut egui::Ui) {
         ui.group(|ui| {
             ui.label("Stack Operations:");
@@ -449,26 +946,44 @@ impl DsavApp {
 
         ui.add_space(8.0);
 
+        ui.group(|ui| {
+            ui.label("Bracket Matching:");
+
+            ui.horizontal(|ui| {
+                ui.label("Input:");
+                ui.text_edit_singleline(&mut self.bracket_input);
+            });
+
+            if ui.button("🧮 Check Brackets")
+                .on_hover_text("Push each opening bracket and pop it at its closing partner; supports () [] {}")
+                .clicked()
+            {
+                self.check_brackets();
+            }
+        });
+
+        ui.add_space(8.0);
+
         ui.group(|ui| {
             ui.label("Randomize:");
 
             ui.horizontal(|ui| {
                 ui.label("Size:");
-                ui.add(egui::DragValue::new(&mut self.randomize_size).clamp_range(1..=16).speed(0.1));
+                ui.add(egui::DragValue::new(&mut self.randomize_size).range(1..=16).speed(0.1));
             });
 
-            if ui.button("🎲 Randomize").clicked() {
-                use rand::Rng;
-                let mut rng = rand::thread_rng();
+            self.seed_controls(ui);
 
+            if ui.button("🎲 Randomize").clicked() {
+                let before = self.structure_render_state();
                 self.stack = dsav_core::structures::VisualizableStack::with_capacity(16);
-                for _ in 0..self.randomize_size {
-                    let random_value = rng.gen_range(1..=100);
-                    let _ = self.stack.push(random_value);
+                for value in self.random_values() {
+                    let _ = self.stack.push(value);
                 }
 
                 self.current_steps.clear();
-                self.status_message = format!("Generated {} random elements", self.randomize_size);
+                self.status_message = self.randomized_message();
+                self.start_load_diff(&before);
             }
         });
 
@@ -514,6 +1029,13 @@ impl DsavApp {
                     }
                 }
             }
+
+            if ui.button("🔀 Interleave Halves")
+                .on_hover_text("Alternate elements from the front and back halves")
+                .clicked()
+            {
+                self.execute_queue_operation(Operation::Interleave);
+            }
         });
 
         ui.add_space(8.0);
@@ -523,21 +1045,21 @@ impl DsavApp {
 
             ui.horizontal(|ui| {
                 ui.label("Size:");
-                ui.add(egui::DragValue::new(&mut self.randomize_size).clamp_range(1..=16).speed(0.1));
+                ui.add(egui::DragValue::new(&mut self.randomize_size).range(1..=16).speed(0.1));
             });
 
-            if ui.button("🎲 Randomize").clicked() {
-                use rand::Rng;
-                let mut rng = rand::thread_rng();
+            self.seed_controls(ui);
 
+            if ui.button("🎲 Randomize").clicked() {
+                let before = self.structure_render_state();
                 self.queue = dsav_core::structures::VisualizableQueue::with_capacity(16);
-                for _ in 0..self.randomize_size {
-                    let random_value = rng.gen_range(1..=100);
-                    let _ = self.queue.enqueue(random_value);
+                for value in self.random_values() {
+                    let _ = self.queue.enqueue(value);
                 }
 
                 self.current_steps.clear();
-                self.status_message = format!("Generated {} random elements", self.randomize_size);
+                self.status_message = self.randomized_message();
+                self.start_load_diff(&before);
             }
         });
 
@@ -580,6 +1102,14 @@ impl DsavApp {
                 if ui.button("✏ Update").clicked() {
                     self.execute_linked_list_operation(Operation::Update(self.input_index, self.input_value));
                 }
+
+                if ui.button("✂ Split").clicked() {
+                    self.execute_linked_list_operation(Operation::SplitAt(self.input_index));
+                }
+
+                if ui.button("🔄 Rotate").clicked() {
+                    self.execute_linked_list_operation(Operation::RotateLeft(self.input_index));
+                }
             });
         });
 
@@ -601,9 +1131,51 @@ impl DsavApp {
                 if ui.button("🚶 Traverse").clicked() {
                     self.execute_linked_list_operation(Operation::Traverse);
                 }
+
+                if ui.button("🔀 Merge Sort").clicked() {
+                    self.execute_linked_list_operation(Operation::MergeSort);
+                }
             });
+
+            if ui
+                .button(format!("🔁 Replace {} → {}", self.search_value, self.input_value))
+                .clicked()
+            {
+                self.execute_linked_list_operation(Operation::Replace(self.search_value, self.input_value));
+            }
         });
 
+        if let Some(tail) = &self.split_tail {
+            let values = tail.to_vec();
+            ui.add_space(8.0);
+
+            ui.group(|ui| {
+                ui.label("Detached Tail:");
+                ui.label(if values.is_empty() {
+                    "(empty)".to_string()
+                } else {
+                    values.iter().map(i32::to_string).collect::<Vec<_>>().join(" → ")
+                });
+
+                ui.horizontal(|ui| {
+                    if ui.button("🔗 Reattach").clicked() {
+                        let before = self.structure_render_state();
+                        if let Some(tail) = self.split_tail.take() {
+                            self.linked_list.append(tail);
+                        }
+                        self.current_steps.clear();
+                        self.status_message = format!("Reattached {} node(s)", values.len());
+                        self.start_load_diff(&before);
+                    }
+
+                    if ui.button("🗑 Discard").clicked() {
+                        self.split_tail = None;
+                        self.status_message = format!("Discarded {} detached node(s)", values.len());
+                    }
+                });
+            });
+        }
+
         ui.add_space(8.0);
 
         ui.group(|ui| {
@@ -611,21 +1183,21 @@ impl DsavApp {
 
             ui.horizontal(|ui| {
                 ui.label("Size:");
-                ui.add(egui::DragValue::new(&mut self.randomize_size).clamp_range(1..=16).speed(0.1));
+                ui.add(egui::DragValue::new(&mut self.randomize_size).range(1..=16).speed(0.1));
             });
 
-            if ui.button("🎲 Randomize").clicked() {
-                use rand::Rng;
-                let mut rng = rand::thread_rng();
+            self.seed_controls(ui);
 
+            if ui.button("🎲 Randomize").clicked() {
+                let before = self.structure_render_state();
                 self.linked_list = dsav_core::structures::VisualizableLinkedList::new();
-                for _ in 0..self.randomize_size {
-                    let random_value = rng.gen_range(1..=100);
-                    self.linked_list.insert_back(random_value);
+                for value in self.random_values() {
+                    self.linked_list.insert_back(value);
                 }
 
                 self.current_steps.clear();
-                self.status_message = format!("Generated {} random elements", self.randomize_size);
+                self.status_message = self.randomized_message();
+                self.start_load_diff(&before);
             }
         });
 
@@ -663,6 +1235,82 @@ impl DsavApp {
                 if ui.button("🔍 Search").clicked() {
                     self.execute_bst_operation(Operation::Search(self.input_value));
                 }
+
+                if ui
+                    .button("🧲 Closest")
+                    .on_hover_text("Find the value nearest to the value; ties go to the smaller")
+                    .clicked()
+                {
+                    self.execute_bst_operation(Operation::FindClosest(self.input_value));
+                }
+
+                if ui
+                    .button("↔ Neighbors")
+                    .on_hover_text("Find the in-order predecessor and successor of the value")
+                    .clicked()
+                {
+                    self.execute_bst_operation(Operation::InOrderPredecessorSuccessorPair(self.input_value));
+                }
+            });
+
+            ui.horizontal(|ui| {
+                if ui
+                    .button("📐 Rank")
+                    .on_hover_text("Count the values less than the value")
+                    .clicked()
+                {
+                    self.execute_bst_operation(Operation::Rank(self.input_value));
+                }
+
+                if ui
+                    .button("🎯 Select")
+                    .on_hover_text("Find the k-th smallest value, using the value as k")
+                    .clicked()
+                {
+                    self.execute_bst_operation(Operation::Select(self.input_value.max(0) as usize));
+                }
+
+                if ui
+                    .button("⬆ Path to Root")
+                    .on_hover_text("Highlight each ancestor of the value, up to the root")
+                    .clicked()
+                {
+                    self.execute_bst_operation(Operation::PathToRoot(self.input_value));
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Range:");
+                ui.add(egui::DragValue::new(&mut self.range_low).speed(1.0));
+                ui.label("to");
+                ui.add(egui::DragValue::new(&mut self.range_high).speed(1.0));
+
+                if ui
+                    .button("📏 Range Query")
+                    .on_hover_text("Collect every value in the range, skipping subtrees that lie outside it")
+                    .clicked()
+                {
+                    self.execute_bst_operation(Operation::RangeQuery(self.range_low, self.range_high));
+                }
+            });
+
+            ui.horizontal(|ui| {
+                if ui
+                    .button("⚖ Insert Balanced")
+                    .on_hover_text("Insert, then rebuild the scapegoat subtree if the new node is too deep")
+                    .clicked()
+                {
+                    self.execute_bst_operation(Operation::InsertBalanced(self.input_value));
+                }
+
+                let mut alpha = self.bst.balance_alpha();
+                if ui
+                    .add(egui::Slider::new(&mut alpha, 0.55..=0.95).text("α"))
+                    .on_hover_text("Lower keeps the tree tighter but rebuilds more often")
+                    .changed()
+                {
+                    let _ = self.bst.set_balance_alpha(alpha);
+                }
             });
         });
 
@@ -690,6 +1338,26 @@ impl DsavApp {
                     self.execute_bst_operation(Operation::LevelOrderTraverse);
                 }
             });
+
+            if ui.button("🔀 Zigzag Level-Order").clicked() {
+                self.execute_bst_operation(Operation::ZigzagTraverse);
+            }
+
+            if ui.button("🧵 Morris In-Order").clicked() {
+                self.execute_bst_operation(Operation::MorrisInOrder);
+            }
+
+            if ui.button("🪞 Mirror Tree").clicked() {
+                self.execute_bst_operation(Operation::MirrorTree);
+            }
+
+            if ui.button("⚖ Check Balance").clicked() {
+                self.execute_bst_operation(Operation::IsBalanced);
+            }
+
+            if ui.button("📊 Level Widths").clicked() {
+                self.execute_bst_operation(Operation::ShowLevelWidths);
+            }
         });
 
         ui.add_space(8.0);
@@ -699,21 +1367,21 @@ impl DsavApp {
 
             ui.horizontal(|ui| {
                 ui.label("Size:");
-                ui.add(egui::DragValue::new(&mut self.randomize_size).clamp_range(1..=16).speed(0.1));
+                ui.add(egui::DragValue::new(&mut self.randomize_size).range(1..=16).speed(0.1));
             });
 
-            if ui.button("🎲 Randomize").clicked() {
-                use rand::Rng;
-                let mut rng = rand::thread_rng();
+            self.seed_controls(ui);
 
+            if ui.button("🎲 Randomize").clicked() {
+                let before = self.structure_render_state();
                 self.bst.clear();
-                for _ in 0..self.randomize_size {
-                    let random_value = rng.gen_range(1..=100);
-                    self.bst.insert(random_value);
+                for value in self.random_values() {
+                    self.bst.insert(value);
                 }
 
                 self.current_steps.clear();
-                self.status_message = format!("Generated {} random elements", self.randomize_size);
+                self.status_message = self.randomized_message();
+                self.start_load_diff(&before);
             }
         });
 
@@ -751,7 +1419,51 @@ impl DsavApp {
                 if ui.button("🔍 Search").clicked() {
                     self.execute_rb_tree_operation(Operation::Search(self.input_value));
                 }
+
+                if ui
+                    .button("⬆ Path to Root")
+                    .on_hover_text("Follow parent pointers from the value up to the root")
+                    .clicked()
+                {
+                    self.execute_rb_tree_operation(Operation::PathToRoot(self.input_value));
+                }
+            });
+
+            ui.horizontal(|ui| {
+                let hint = "Raw rotation without fixup; may break Red-Black properties";
+
+                if ui.button("↺ Rotate Left").on_hover_text(hint).clicked() {
+                    self.execute_rb_tree_operation(Operation::RbRotateLeft(self.input_value));
+                }
+
+                if ui.button("↻ Rotate Right").on_hover_text(hint).clicked() {
+                    self.execute_rb_tree_operation(Operation::RbRotateRight(self.input_value));
+                }
             });
+
+            if ui
+                .button("🩹 Rotate + Fixup")
+                .on_hover_text("Lift a red child of the value, then recolor and run the fixup so the tree stays valid")
+                .clicked()
+            {
+                self.execute_rb_tree_operation(Operation::RebalanceAround(self.input_value));
+            }
+
+            if ui
+                .button("🏗 Rebuild Balanced")
+                .on_hover_text("Rebuild from the sorted values in linear time, without rotations")
+                .clicked()
+            {
+                self.execute_rb_tree_operation(Operation::RebuildBalanced);
+            }
+
+            if ui
+                .button("📏 Analyze Height")
+                .on_hover_text("Compare the height with the 2·log2(n+1) bound")
+                .clicked()
+            {
+                self.execute_rb_tree_operation(Operation::AnalyzeHeight);
+            }
         });
 
         ui.add_space(8.0);
@@ -792,21 +1504,21 @@ impl DsavApp {
 
             ui.horizontal(|ui| {
                 ui.label("Size:");
-                ui.add(egui::DragValue::new(&mut self.randomize_size).clamp_range(1..=16).speed(0.1));
+                ui.add(egui::DragValue::new(&mut self.randomize_size).range(1..=16).speed(0.1));
             });
 
-            if ui.button("🎲 Randomize").clicked() {
-                use rand::Rng;
-                let mut rng = rand::thread_rng();
+            self.seed_controls(ui);
 
+            if ui.button("🎲 Randomize").clicked() {
+                let before = self.structure_render_state();
                 self.rb_tree.clear();
-                for _ in 0..self.randomize_size {
-                    let random_value = rng.gen_range(1..=100);
-                    self.rb_tree.insert(random_value);
+                for value in self.random_values() {
+                    self.rb_tree.insert(value);
                 }
 
                 self.current_steps.clear();
-                self.status_message = format!("Generated {} random elements", self.randomize_size);
+                self.status_message = self.randomized_message();
+                self.start_load_diff(&before);
             }
         });
 
@@ -823,16 +1535,261 @@ impl DsavApp {
         });
     }
 
-    fn execute_array_operation(&mut self, operation: Operation) {
-        match self.array.execute_with_steps(operation) {
-            Ok(steps) => {
-                if !steps.is_empty() {
-                    self.current_steps = steps;
-                    self.current_step_index = 0;
-                    self.playing = true;
-                    self.time_since_last_step = 0.0;
-                    if let Some(step) = self.current_steps.first() {
-                        self.status_message = step.description.clone();
+    /// Seed checkbox and value shown in every Randomize group.
+    fn seed_controls(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.use_seed, "Use seed");
+            ui.add_enabled(self.use_seed, egui::DragValue::new(&mut self.seed).speed(1.0));
+        });
+    }
+
+    fn random_values(&self) -> Vec<i32> {
+        dsav_core::dataset::random_values(self.randomize_size, self.use_seed.then_some(self.seed))
+    }
+
+    fn randomized_message(&self) -> String {
+        if self.use_seed {
+            format!("Generated {} random elements (seed {})", self.randomize_size, self.seed)
+        } else {
+            format!("Generated {} random elements", self.randomize_size)
+        }
+    }
+
+    /// Render state of the selected structure, as the load diff compares it.
+    fn structure_render_state(&self) -> dsav_core::RenderState {
+        match self.selected_structure {
+            DataStructure::Array => self.array.render_state(),
+            DataStructure::Matrix => self.matrix.render_state(),
+            DataStructure::Stack => self.stack.render_state(),
+            DataStructure::Queue => self.queue.render_state(),
+            DataStructure::LinkedList => self.linked_list.render_state(),
+            DataStructure::Bst => self.bst.render_state(),
+            DataStructure::RBTree => self.rb_tree.render_state(),
+        }
+    }
+
+    /// Start highlighting how the selected structure differs from `before`,
+    /// if enabled in settings, and summarize the change in the status bar.
+    fn start_load_diff(&mut self, before: &dsav_core::RenderState) {
+        self.load_diff = None;
+        if !self.show_load_diff {
+            return;
+        }
+
+        let diff = before.diff(&self.structure_render_state());
+        self.status_message = format!(
+            "{} ({} changed, {} added, {} removed)",
+            self.status_message,
+            diff.changed.len(),
+            diff.added.len(),
+            diff.removed.len()
+        );
+        if !diff.is_empty() {
+            self.load_diff = Some(LoadDiff {
+                structure: self.selected_structure,
+                diff,
+                remaining: LOAD_DIFF_SECONDS,
+            });
+        }
+    }
+
+    /// Mark changed and added elements while a load diff is showing. Step
+    /// highlights take precedence, so an operation started meanwhile is
+    /// drawn as usual.
+    fn apply_load_diff(&self, state: &mut dsav_core::RenderState) {
+        let Some(load_diff) = &self.load_diff else {
+            return;
+        };
+        if load_diff.structure != self.selected_structure || !self.current_steps.is_empty() {
+            return;
+        }
+
+        for &idx in &load_diff.diff.changed {
+            if let Some(elem) = state.elements.get_mut(idx) {
+                elem.state = dsav_core::state::ElementState::Highlighted;
+            }
+        }
+        for &idx in &load_diff.diff.added {
+            if let Some(elem) = state.elements.get_mut(idx) {
+                elem.state = dsav_core::state::ElementState::Active;
+            }
+        }
+    }
+
+    /// Record, stop and replay buttons for operation macros.
+    fn macro_controls(&mut self, ui: &mut egui::Ui) {
+        ui.label("Macro:");
+
+        let replaying = !self.replay_queue.is_empty();
+        ui.horizontal(|ui| {
+            if self.operation_macro.recording {
+                if ui.button("⏹ Stop").clicked() {
+                    self.operation_macro.stop();
+                    self.status_message = format!("Recorded {} operation(s)", self.operation_macro.operations.len());
+                }
+            } else if ui
+                .add_enabled(!replaying, egui::Button::new("⏺ Start Recording"))
+                .on_hover_text("Capture each operation run on this structure")
+                .clicked()
+            {
+                self.operation_macro.start(self.selected_structure);
+                self.macro_snapshot = Some(self.snapshot_structure());
+                self.status_message = "Recording operations...".to_string();
+            }
+
+            let can_replay = !self.operation_macro.recording
+                && !replaying
+                && !self.operation_macro.operations.is_empty();
+            if ui
+                .add_enabled(can_replay, egui::Button::new("🔁 Replay"))
+                .on_hover_text("Restore the structure as it was when recording started and run the operations again")
+                .clicked()
+            {
+                self.start_replay();
+            }
+
+            if replaying {
+                if ui.button("✖ Cancel").clicked() {
+                    self.replay_queue.clear();
+                    self.status_message = "Replay cancelled".to_string();
+                }
+            } else if self.operation_macro.structure.is_some() && ui.button("🗑 Clear").clicked() {
+                self.operation_macro.clear();
+                self.macro_snapshot = None;
+            }
+        });
+
+        if let Some(structure) = self.operation_macro.structure {
+            let state = if self.operation_macro.recording { "recording" } else { "recorded" };
+            ui.label(format!(
+                "{} operation(s) {} on {:?}",
+                self.operation_macro.operations.len(),
+                state,
+                structure
+            ));
+        }
+    }
+
+    fn snapshot_structure(&self) -> StructureSnapshot {
+        match self.selected_structure {
+            DataStructure::Array => StructureSnapshot::Array(self.array.clone()),
+            DataStructure::Matrix => StructureSnapshot::Matrix(self.matrix.clone()),
+            DataStructure::Stack => StructureSnapshot::Stack(self.stack.clone()),
+            DataStructure::Queue => StructureSnapshot::Queue(self.queue.clone()),
+            DataStructure::LinkedList => StructureSnapshot::LinkedList(self.linked_list.clone()),
+            DataStructure::Bst => StructureSnapshot::Bst(self.bst.clone()),
+            DataStructure::RBTree => StructureSnapshot::RBTree(self.rb_tree.clone()),
+        }
+    }
+
+    /// Put back the structure recorded in `snapshot` and select it.
+    fn restore_snapshot(&mut self, snapshot: &StructureSnapshot) {
+        self.selected_structure = match snapshot {
+            StructureSnapshot::Array(array) => {
+                self.array = array.clone();
+                DataStructure::Array
+            }
+            StructureSnapshot::Matrix(matrix) => {
+                self.matrix = matrix.clone();
+                DataStructure::Matrix
+            }
+            StructureSnapshot::Stack(stack) => {
+                self.stack = stack.clone();
+                DataStructure::Stack
+            }
+            StructureSnapshot::Queue(queue) => {
+                self.queue = queue.clone();
+                DataStructure::Queue
+            }
+            StructureSnapshot::LinkedList(linked_list) => {
+                self.linked_list = linked_list.clone();
+                DataStructure::LinkedList
+            }
+            StructureSnapshot::Bst(bst) => {
+                self.bst = bst.clone();
+                DataStructure::Bst
+            }
+            StructureSnapshot::RBTree(rb_tree) => {
+                self.rb_tree = rb_tree.clone();
+                DataStructure::RBTree
+            }
+        };
+        self.selected_node = None;
+    }
+
+    fn start_replay(&mut self) {
+        if let Some(snapshot) = self.macro_snapshot.take() {
+            self.restore_snapshot(&snapshot);
+            self.macro_snapshot = Some(snapshot);
+        }
+        self.current_steps.clear();
+        self.playing = false;
+        self.replay_queue = self.operation_macro.replay();
+        self.status_message = format!("Replaying {} operation(s)", self.replay_queue.len());
+    }
+
+    /// Run the next queued operation. A failing operation ends the replay.
+    fn replay_next(&mut self) {
+        let Some(operation) = self.replay_queue.pop_front() else {
+            return;
+        };
+        let Some(structure) = self.operation_macro.structure else {
+            self.replay_queue.clear();
+            return;
+        };
+
+        self.selected_structure = structure;
+        match structure {
+            DataStructure::Array => self.execute_array_operation(operation),
+            DataStructure::Matrix => self.execute_matrix_operation(operation),
+            DataStructure::Stack => self.execute_stack_operation(operation),
+            DataStructure::Queue => self.execute_queue_operation(operation),
+            DataStructure::LinkedList => self.execute_linked_list_operation(operation),
+            DataStructure::Bst => self.execute_bst_operation(operation),
+            DataStructure::RBTree => self.execute_rb_tree_operation(operation),
+        }
+
+        if !self.playing && self.current_steps.is_empty() {
+            self.replay_queue.clear();
+            self.status_message = format!("Replay stopped: {}", self.status_message);
+        }
+    }
+
+    fn execute_array_operation(&mut self, operation: Operation) {
+        match dsav_core::time_operation(&mut self.array, operation) {
+            Ok((steps, elapsed)) => {
+                self.last_generation = Some((steps.len(), elapsed));
+                self.operation_macro.record(DataStructure::Array, operation);
+                if !steps.is_empty() {
+                    self.current_steps = steps;
+                    self.current_step_index = 0;
+                    self.playing = true;
+                    self.time_since_last_step = 0.0;
+                    if let Some(step) = self.current_steps.first() {
+                        self.status_message = step.description.clone();
+                    }
+                }
+            }
+            Err(e) => {
+                self.status_message = format!("Error: {}", e);
+                self.current_steps.clear();
+                self.playing = false;
+            }
+        }
+    }
+
+    fn execute_matrix_operation(&mut self, operation: Operation) {
+        match dsav_core::time_operation(&mut self.matrix, operation) {
+            Ok((steps, elapsed)) => {
+                self.last_generation = Some((steps.len(), elapsed));
+                self.operation_macro.record(DataStructure::Matrix, operation);
+                if !steps.is_empty() {
+                    self.current_steps = steps;
+                    self.current_step_index = 0;
+                    self.playing = true;
+                    self.time_since_last_step = 0.0;
+                    if let Some(step) = self.current_steps.first() {
+                        self.status_message = step.description.clone();
                     }
                 }
             }
@@ -845,8 +1802,10 @@ impl DsavApp {
     }
 
     fn execute_stack_operation(&mut self, operation: Operation) {
-        match self.stack.execute_with_steps(operation) {
-            Ok(steps) => {
+        match dsav_core::time_operation(&mut self.stack, operation) {
+            Ok((steps, elapsed)) => {
+                self.last_generation = Some((steps.len(), elapsed));
+                self.operation_macro.record(DataStructure::Stack, operation);
                 if !steps.is_empty() {
                     self.current_steps = steps;
                     self.current_step_index = 0;
@@ -866,8 +1825,10 @@ impl DsavApp {
     }
 
     fn execute_queue_operation(&mut self, operation: Operation) {
-        match self.queue.execute_with_steps(operation) {
-            Ok(steps) => {
+        match dsav_core::time_operation(&mut self.queue, operation) {
+            Ok((steps, elapsed)) => {
+                self.last_generation = Some((steps.len(), elapsed));
+                self.operation_macro.record(DataStructure::Queue, operation);
                 if !steps.is_empty() {
                     self.current_steps = steps;
                     self.current_step_index = 0;
@@ -887,8 +1848,25 @@ impl DsavApp {
     }
 
     fn execute_linked_list_operation(&mut self, operation: Operation) {
-        match self.linked_list.execute_with_steps(operation) {
-            Ok(steps) => {
+        let result = match operation {
+            // Keep the cut-off nodes instead of dropping them with the steps
+            Operation::SplitAt(index) => {
+                let start = std::time::Instant::now();
+                self.linked_list.split_at_with_steps(index).map(|(mut tail, steps)| {
+                    if let Some(previous) = self.split_tail.take() {
+                        tail.append(previous);
+                    }
+                    self.split_tail = Some(tail);
+                    (steps, start.elapsed())
+                })
+            }
+            _ => dsav_core::time_operation(&mut self.linked_list, operation),
+        };
+
+        match result {
+            Ok((steps, elapsed)) => {
+                self.last_generation = Some((steps.len(), elapsed));
+                self.operation_macro.record(DataStructure::LinkedList, operation);
                 if !steps.is_empty() {
                     self.current_steps = steps;
                     self.current_step_index = 0;
@@ -908,8 +1886,10 @@ impl DsavApp {
     }
 
     fn execute_bst_operation(&mut self, operation: Operation) {
-        match self.bst.execute_with_steps(operation) {
-            Ok(steps) => {
+        match dsav_core::time_operation(&mut self.bst, operation) {
+            Ok((steps, elapsed)) => {
+                self.last_generation = Some((steps.len(), elapsed));
+                self.operation_macro.record(DataStructure::Bst, operation);
                 if !steps.is_empty() {
                     self.current_steps = steps;
                     self.current_step_index = 0;
@@ -929,8 +1909,10 @@ impl DsavApp {
     }
 
     fn execute_rb_tree_operation(&mut self, operation: Operation) {
-        match self.rb_tree.execute_with_steps(operation) {
-            Ok(steps) => {
+        match dsav_core::time_operation(&mut self.rb_tree, operation) {
+            Ok((steps, elapsed)) => {
+                self.last_generation = Some((steps.len(), elapsed));
+                self.operation_macro.record(DataStructure::RBTree, operation);
                 if !steps.is_empty() {
                     self.current_steps = steps;
                     self.current_step_index = 0;
@@ -953,6 +1935,11 @@ impl DsavApp {
         let palette = self.current_theme.colors();
         let mut state = self.array.render_state();
 
+        if state.is_empty_placeholder() {
+            self.render_empty_placeholder(ui, &state);
+            return;
+        }
+
         // Check if we have array state from current step (for sorting animations)
         if !self.current_steps.is_empty() && self.current_step_index < self.current_steps.len() {
             let current_step = &self.current_steps[self.current_step_index];
@@ -960,14 +1947,44 @@ impl DsavApp {
             // If step contains array_state in metadata, use that instead
             if let Some(array_state) = current_step.metadata.get("array_state") {
                 if let Some(arr) = array_state.as_array() {
+                    let tags = current_step.metadata.get("tags").and_then(|t| t.as_array());
+                    let pivot = current_step.metadata.get("pivot_index").and_then(|p| p.as_u64());
                     state.elements.clear();
                     for (i, val) in arr.iter().enumerate() {
                         if let Some(num) = val.as_i64() {
-                            state.elements.push(
-                                dsav_core::state::RenderElement::new(num as i32)
-                                    .with_label(num.to_string())
-                                    .with_sublabel(format!("[{}]", i))
-                            );
+                            let mut elem = dsav_core::state::RenderElement::new(num as i32)
+                                .with_label(num.to_string())
+                                .with_sublabel(format!("[{}]", display_index(i, self.one_based_indices)));
+                            let tag = tags
+                                .and_then(|t| t.get(i))
+                                .and_then(|t| t.as_str())
+                                .and_then(|t| t.chars().next());
+                            if let Some(tag) = tag {
+                                elem = elem.with_tag(tag);
+                            }
+                            if pivot == Some(i as u64) {
+                                elem = elem.with_badge("pivot");
+                            }
+                            state.elements.push(elem);
+                        }
+                    }
+                }
+            }
+
+            // Three-way partitions color each settled region before the pointers are highlighted
+            if let Some(regions) = current_step.metadata.get("regions") {
+                use dsav_core::state::ElementState;
+                for (name, region_state) in [
+                    ("less", ElementState::Sorted),
+                    ("equal", ElementState::Comparing),
+                    ("greater", ElementState::Swapping),
+                ] {
+                    let bounds = regions.get(name).and_then(|r| r.as_array());
+                    if let Some([start, end]) = bounds.map(|b| b.as_slice()) {
+                        let start = start.as_u64().unwrap_or(0) as usize;
+                        let end = (end.as_u64().unwrap_or(0) as usize).min(state.elements.len());
+                        for elem in state.elements.iter_mut().take(end).skip(start) {
+                            elem.state = region_state;
                         }
                     }
                 }
@@ -987,13 +2004,62 @@ impl DsavApp {
             }
         }
 
+        self.apply_load_diff(&mut state);
+
+        if self.array_view_mode == ArrayViewMode::Bars {
+            self.render_array_bars(ui, &state);
+            return;
+        }
+
+        let font = self.label_font(self.array_font_size);
+        let sizes: Vec<egui::Vec2> = state
+            .elements
+            .iter()
+            .map(|elem| {
+                let mut size = self.array_element_size;
+                if self.array_auto_width {
+                    // Widen the box so long labels aren't clipped
+                    let galley = ui.painter().layout_no_wrap(elem.label.clone(), font.clone(), palette.text);
+                    size.x = size.x.max(galley.size().x + 16.0);
+                }
+                size
+            })
+            .collect();
+
+        let per_row = if self.array_wrap {
+            let widest = sizes.iter().map(|size| size.x).fold(0.0, f32::max);
+            elements_per_row(ui.available_width() - 32.0, widest + ARRAY_BOX_GAP)
+        } else {
+            state.elements.len()
+        };
+
+        for (row, chunk) in state.elements.chunks(per_row.max(1)).enumerate() {
+            if row > 0 {
+                // Leave room for the index labels under the previous row
+                ui.add_space(28.0);
+            }
+            let first = row * per_row;
+            self.render_array_row(ui, chunk, &sizes[first..first + chunk.len()], first);
+        }
+    }
+
+    /// Draw one row of array boxes; `first` is the array index of `elements[0]`
+    /// so index labels stay correct when the array wraps.
+    fn render_array_row(
+        &self,
+        ui: &mut egui::Ui,
+        elements: &[dsav_core::state::RenderElement],
+        sizes: &[egui::Vec2],
+        first: usize,
+    ) {
+        let palette = self.current_theme.colors();
+        let font = self.label_font(self.array_font_size);
+
         ui.horizontal(|ui| {
             ui.add_space(16.0);
 
-            for (i, elem) in state.elements.iter().enumerate() {
+            for (offset, (elem, &size)) in elements.iter().zip(sizes).enumerate() {
                 let (bg_color, border_color) = self.get_element_colors(elem.state);
-
-                let size = egui::vec2(60.0, 60.0);
                 let (rect, _response) = ui.allocate_exact_size(size, egui::Sense::hover());
 
                 ui.painter().rect(
@@ -1007,26 +2073,247 @@ impl DsavApp {
                     rect.center(),
                     egui::Align2::CENTER_CENTER,
                     &elem.label,
-                    egui::FontId::proportional(20.0),
+                    font.clone(),
                     palette.text,
                 );
 
+                if let Some(badge) = &elem.badge {
+                    self.draw_badge(ui.painter(), rect.right_top(), badge);
+                }
+
                 ui.painter().text(
                     egui::pos2(rect.center().x, rect.bottom() + 8.0),
                     egui::Align2::CENTER_TOP,
-                    format!("[{}]", i),
-                    egui::FontId::proportional(14.0),
+                    format!("[{}]", display_index(first + offset, self.one_based_indices)),
+                    self.label_font(14.0),
                     palette.subtext,
                 );
 
-                ui.add_space(8.0);
+                ui.add_space(ARRAY_BOX_GAP);
+            }
+        });
+    }
+
+    /// Font for element labels drawn at `base_size` before the label size
+    /// setting is applied. Every renderer takes its label font from here.
+    fn label_font(&self, base_size: f32) -> egui::FontId {
+        let size = (base_size * self.label_font_scale).max(MIN_LABEL_FONT_SIZE);
+        match self.label_font {
+            LabelFont::Proportional => egui::FontId::proportional(size),
+            LabelFont::Monospace => egui::FontId::monospace(size),
+        }
+    }
+
+    /// Small rounded chip centered on `anchor` marking an element's role.
+    fn draw_badge(&self, painter: &egui::Painter, anchor: egui::Pos2, text: &str) {
+        let palette = self.current_theme.colors();
+        let galley = painter.layout_no_wrap(text.to_string(), egui::FontId::proportional(11.0), palette.background);
+        let rect = egui::Rect::from_center_size(anchor, galley.size() + egui::vec2(8.0, 2.0));
+
+        painter.rect_filled(rect, 6.0, palette.mauve);
+        painter.galley(rect.center() - galley.size() / 2.0, galley, palette.background);
+    }
+
+    /// Draw the array as a bar chart, one bar per element with height
+    /// proportional to its value. Negative values hang below the baseline.
+    fn render_array_bars(&self, ui: &mut egui::Ui, state: &dsav_core::state::RenderState) {
+        let palette = self.current_theme.colors();
+        let chart_height = 240.0;
+        let gap = 4.0;
+
+        // Shrink bars to fit large arrays instead of scrolling
+        let count = state.elements.len() as f32;
+        let bar_width = ((ui.available_width() - 32.0 - gap * count) / count).clamp(4.0, self.array_element_size.x);
+        let show_labels = bar_width >= 24.0;
+
+        let values: Vec<i32> = state.elements.iter().map(|elem| elem.value).collect();
+        let low = values.iter().copied().min().unwrap_or(0).min(0);
+        let high = values.iter().copied().max().unwrap_or(0).max(0);
+
+        ui.horizontal(|ui| {
+            ui.add_space(16.0);
+
+            for elem in &state.elements {
+                let (bg_color, border_color) = self.get_element_colors(elem.state);
+                let (column, _response) = ui.allocate_exact_size(egui::vec2(bar_width, chart_height), egui::Sense::hover());
+
+                let (top, bottom) = bar_extent(elem.value, low, high);
+                let rect = egui::Rect::from_min_max(
+                    egui::pos2(column.left(), column.top() + top * chart_height),
+                    egui::pos2(column.right(), column.top() + bottom * chart_height),
+                );
+
+                ui.painter().rect(rect, 2.0, bg_color, egui::Stroke::new(2.0, border_color));
+
+                if show_labels {
+                    ui.painter().text(
+                        egui::pos2(column.center().x, column.bottom() + 8.0),
+                        egui::Align2::CENTER_TOP,
+                        &elem.label,
+                        self.label_font(14.0),
+                        palette.subtext,
+                    );
+                }
+
+                ui.add_space(gap);
             }
         });
+
+        ui.add_space(24.0);
+    }
+
+    /// Stats for the selected tree node: value, index, depth, color (RB tree
+    /// only) and subtree size. Clears the selection if the node is gone.
+    fn render_node_details(&mut self, ui: &mut egui::Ui, state: &dsav_core::state::RenderState, subtree_size: Option<usize>) {
+        let Some(idx) = self.selected_node else {
+            return;
+        };
+        let (Some(elem), Some(size)) = (state.elements.get(idx), subtree_size) else {
+            self.selected_node = None;
+            return;
+        };
+
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.strong(format!("Node {}", elem.value));
+                ui.separator();
+                ui.label(format!("Index: {}", idx));
+                ui.separator();
+                ui.label(format!("Depth: {}", (idx + 1).ilog2()));
+                if self.selected_structure == DataStructure::RBTree {
+                    ui.separator();
+                    ui.label(format!("Color: {}", if elem.sublabel == "R" { "Red" } else { "Black" }));
+                }
+                ui.separator();
+                ui.label(format!("Subtree size: {}", size));
+
+                if ui.small_button("✖").on_hover_text("Deselect").clicked() {
+                    self.selected_node = None;
+                }
+            });
+        });
+
+        ui.add_space(8.0);
+    }
+
+    /// Centered message for a structure whose render state is the empty placeholder.
+    fn render_empty_placeholder(&self, ui: &mut egui::Ui, state: &dsav_core::state::RenderState) {
+        let palette = self.current_theme.colors();
+        let Some(elem) = state.elements.first() else {
+            return;
+        };
+
+        ui.vertical_centered(|ui| {
+            ui.add_space(50.0);
+            ui.label(egui::RichText::new(&elem.label).size(18.0));
+            ui.label(egui::RichText::new(&elem.sublabel).color(palette.subtext));
+            ui.add_space(50.0);
+        });
+    }
+
+    /// Draw the matrix as a grid, `state.cols` boxes per row. Steps that
+    /// carry a `matrix_state` and `cols` are drawn from those instead, since a
+    /// transpose can change the shape mid-animation.
+    fn render_matrix(&self, ui: &mut egui::Ui) {
+        let palette = self.current_theme.colors();
+        let mut state = self.matrix.render_state();
+
+        if state.is_empty_placeholder() {
+            self.render_empty_placeholder(ui, &state);
+            return;
+        }
+
+        if let Some(current_step) = self.current_steps.get(self.current_step_index) {
+            let matrix_state = current_step.metadata.get("matrix_state").and_then(|m| m.as_array());
+            let cols = current_step.metadata.get("cols").and_then(|c| c.as_u64());
+            if let (Some(values), Some(cols)) = (matrix_state, cols) {
+                let cols = (cols as usize).max(1);
+                state.cols = Some(cols);
+                state.elements = values
+                    .iter()
+                    .enumerate()
+                    .map(|(i, value)| {
+                        let value = value.as_i64().unwrap_or(0) as i32;
+                        dsav_core::state::RenderElement::new(value)
+                            .with_label(value.to_string())
+                            .with_sublabel(format!("({}, {})", i / cols, i % cols))
+                    })
+                    .collect();
+            }
+
+            for &idx in &current_step.active_indices {
+                if let Some(elem) = state.elements.get_mut(idx) {
+                    elem.state = dsav_core::state::ElementState::Active;
+                }
+            }
+
+            for &idx in &current_step.highlight_indices {
+                if let Some(elem) = state.elements.get_mut(idx) {
+                    elem.state = dsav_core::state::ElementState::Highlighted;
+                }
+            }
+        }
+
+        self.apply_load_diff(&mut state);
+
+        let cols = state.cols.unwrap_or(state.elements.len()).max(1);
+        let size = egui::vec2(60.0, 60.0);
+
+        for row in state.elements.chunks(cols) {
+            ui.horizontal(|ui| {
+                ui.add_space(16.0);
+
+                for elem in row {
+                    let (bg_color, border_color) = self.get_element_colors(elem.state);
+                    let (rect, response) = ui.allocate_exact_size(size, egui::Sense::hover());
+
+                    ui.painter().rect(rect, 4.0, bg_color, egui::Stroke::new(2.0, border_color));
+                    ui.painter().text(
+                        rect.center(),
+                        egui::Align2::CENTER_CENTER,
+                        &elem.label,
+                        self.label_font(20.0),
+                        palette.text,
+                    );
+                    response.on_hover_text(format!("Row, column: {}", elem.sublabel));
+
+                    ui.add_space(ARRAY_BOX_GAP);
+                }
+            });
+            ui.add_space(ARRAY_BOX_GAP);
+        }
+    }
+
+    /// Animate a bracket check in the stack view. The stack structure itself
+    /// is left alone; each step carries the open brackets to draw.
+    fn check_brackets(&mut self) {
+        use dsav_core::algorithms::check_balanced_with_steps;
+
+        let start = std::time::Instant::now();
+        let (_, steps) = check_balanced_with_steps(&self.bracket_input);
+        self.last_generation = Some((steps.len(), start.elapsed()));
+
+        self.current_steps = steps;
+        self.current_step_index = 0;
+        self.playing = true;
+        self.time_since_last_step = 0.0;
+        if let Some(step) = self.current_steps.first() {
+            self.status_message = step.description.clone();
+        }
     }
 
     fn render_stack(&self, ui: &mut egui::Ui) {
         let palette = self.current_theme.colors();
-        let mut state = self.stack.render_state();
+        let mut state = self
+            .current_steps
+            .get(self.current_step_index)
+            .and_then(bracket_stack_state)
+            .unwrap_or_else(|| self.stack.render_state());
+
+        if state.is_empty_placeholder() {
+            self.render_empty_placeholder(ui, &state);
+            return;
+        }
 
         // Apply current step highlights
         if !self.current_steps.is_empty() && self.current_step_index < self.current_steps.len() {
@@ -1045,6 +2332,8 @@ impl DsavApp {
             }
         }
 
+        self.apply_load_diff(&mut state);
+
         // Add scrollable area with fixed height
         egui::ScrollArea::vertical()
             .max_height(500.0)
@@ -1053,44 +2342,38 @@ impl DsavApp {
                 ui.vertical(|ui| {
                     ui.add_space(16.0);
 
-                    if state.elements.is_empty() {
-                        ui.vertical_centered(|ui| {
-                            ui.label("Stack is empty");
-                        });
-                    } else {
-                        for (i, elem) in state.elements.iter().enumerate().rev() {
-                            ui.horizontal(|ui| {
-                                ui.add_space(16.0);
-
-                                let (bg_color, border_color) = self.get_element_colors(elem.state);
-
-                                let size = egui::vec2(200.0, 50.0);
-                                let (rect, _response) = ui.allocate_exact_size(size, egui::Sense::hover());
-
-                                ui.painter().rect(
-                                    rect,
-                                    4.0,
-                                    bg_color,
-                                    egui::Stroke::new(2.0, border_color),
-                                );
-
-                                ui.painter().text(
-                                    rect.center(),
-                                    egui::Align2::CENTER_CENTER,
-                                    &elem.label,
-                                    egui::FontId::proportional(18.0),
-                                    palette.text,
-                                );
-
-                                ui.label(if i == state.elements.len() - 1 {
-                                    "← TOP"
-                                } else {
-                                    ""
-                                });
+                    for (i, elem) in state.elements.iter().enumerate().rev() {
+                        ui.horizontal(|ui| {
+                            ui.add_space(16.0);
+
+                            let (bg_color, border_color) = self.get_element_colors(elem.state);
+
+                            let size = egui::vec2(200.0, 50.0);
+                            let (rect, _response) = ui.allocate_exact_size(size, egui::Sense::hover());
+
+                            ui.painter().rect(
+                                rect,
+                                4.0,
+                                bg_color,
+                                egui::Stroke::new(2.0, border_color),
+                            );
+
+                            ui.painter().text(
+                                rect.center(),
+                                egui::Align2::CENTER_CENTER,
+                                &elem.label,
+                                self.label_font(18.0),
+                                palette.text,
+                            );
+
+                            ui.label(if i == state.elements.len() - 1 {
+                                "← TOP"
+                            } else {
+                                ""
                             });
+                        });
 
-                            ui.add_space(4.0);
-                        }
+                        ui.add_space(4.0);
                     }
                 });
             });
@@ -1100,6 +2383,11 @@ impl DsavApp {
         let palette = self.current_theme.colors();
         let mut state = self.queue.render_state();
 
+        if state.is_empty_placeholder() {
+            self.render_empty_placeholder(ui, &state);
+            return;
+        }
+
         // Apply current step highlights
         if !self.current_steps.is_empty() && self.current_step_index < self.current_steps.len() {
             let current_step = &self.current_steps[self.current_step_index];
@@ -1117,14 +2405,7 @@ impl DsavApp {
             }
         }
 
-        if state.elements.is_empty() {
-            ui.vertical_centered(|ui| {
-                ui.add_space(50.0);
-                ui.label("Queue is empty. Click 'Enqueue' to add elements.");
-                ui.add_space(50.0);
-            });
-            return;
-        }
+        self.apply_load_diff(&mut state);
 
         ui.add_space(20.0);
 
@@ -1157,12 +2438,12 @@ impl DsavApp {
                                 rect.center(),
                                 egui::Align2::CENTER_CENTER,
                                 elem.value.to_string(),
-                                egui::FontId::monospace(24.0),
+                                self.label_font(24.0),
                                 palette.text,
                             );
 
                             ui.add_space(8.0);
-                            ui.label(format!("Index {}", i));
+                            ui.label(format!("Index {}", display_index(i, self.one_based_indices)));
                         });
 
                         if i < state.elements.len() - 1 {
@@ -1182,13 +2463,8 @@ impl DsavApp {
         let palette = self.current_theme.colors();
         let mut state = self.linked_list.render_state();
 
-        // Early return if empty
-        if state.elements.is_empty() {
-            ui.vertical_centered(|ui| {
-                ui.add_space(50.0);
-                ui.label("Linked list is empty. Click 'Insert Front' or 'Insert Back' to add nodes.");
-                ui.add_space(50.0);
-            });
+        if state.is_empty_placeholder() {
+            self.render_empty_placeholder(ui, &state);
             return;
         }
 
@@ -1209,6 +2485,8 @@ impl DsavApp {
             }
         }
 
+        self.apply_load_diff(&mut state);
+
         ui.add_space(20.0);
 
         // Add horizontal scrolling for linked list
@@ -1244,18 +2522,17 @@ impl DsavApp {
                                 egui::Stroke::new(3.0, border_color),
                             );
 
-                            // Draw value using monospace font
                             ui.painter().text(
                                 rect.center(),
                                 egui::Align2::CENTER_CENTER,
                                 elem.value.to_string(),
-                                egui::FontId::monospace(26.0),
+                                self.label_font(26.0),
                                 palette.text,
                             );
 
                             // Draw node index below
                             ui.add_space(8.0);
-                            ui.label(format!("Node {}", i));
+                            ui.label(format!("Node {}", display_index(i, self.one_based_indices)));
                         });
 
                         // Draw enhanced arrow to next node
@@ -1288,13 +2565,13 @@ impl DsavApp {
         let palette = self.current_theme.colors();
         let mut state = self.bst.render_state();
 
-        // Early return if empty
-        if state.elements.is_empty() {
-            ui.vertical_centered(|ui| {
-                ui.add_space(50.0);
-                ui.label("Binary Search Tree is empty. Click 'Insert' to add nodes.");
-                ui.add_space(50.0);
-            });
+        if state.is_empty_placeholder() {
+            self.render_empty_placeholder(ui, &state);
+            return;
+        }
+
+        if let Err(e) = state.validate() {
+            ui.colored_label(palette.red, format!("Cannot draw tree: {}", e));
             return;
         }
 
@@ -1315,12 +2592,14 @@ impl DsavApp {
             }
         }
 
+        self.apply_load_diff(&mut state);
+        let collapsed = self.apply_depth_limit(&mut state);
+
         // Calculate tree layout positions
         let node_radius = 25.0;
         let level_height = 100.0;
 
-        // Find tree depth and calculate required width
-        let max_depth = self.calculate_tree_depth(&state);
+        // Calculate required width
         let tree_width = self.calculate_subtree_width(0, &state);
         let initial_width = tree_width.max(800.0); // Minimum width of 800
 
@@ -1348,9 +2627,16 @@ impl DsavApp {
         let zoomed_width = (max_x + 100.0).max(600.0) * self.tree_zoom;
         let zoomed_height = (max_y + 100.0).max(400.0) * self.tree_zoom;
 
+        let subtree_size = self.selected_node.and_then(|idx| self.bst.subtree_size(idx));
+        self.render_node_details(ui, &state, subtree_size);
+
         // Create scrollable area for the tree
-        egui::ScrollArea::both()
-            .auto_shrink([false, false])
+        let mut scroll_area = egui::ScrollArea::both().auto_shrink([false, false]);
+        if let Some(offset) = self.tree_scroll_target.take() {
+            scroll_area = scroll_area.scroll_offset(offset);
+        }
+
+        let scroll_output = scroll_area
             .show(ui, |ui| {
                 let (response, painter) = ui.allocate_painter(
                     egui::vec2(zoomed_width, zoomed_height),
@@ -1366,6 +2652,22 @@ impl DsavApp {
                     }
                 }
 
+                let indicators = collapse_indicator_positions(&collapsed, &positions, node_radius);
+
+                // Clicking a "hidden" indicator expands that subtree, clicking
+                // a node selects it, clicking empty space deselects
+                if response.clicked() {
+                    if let Some(pointer) = response.interact_pointer_pos() {
+                        let local = (pointer - response.rect.min) / self.tree_zoom;
+                        match node_at(&indicators, (local.x, local.y), node_radius * 0.6) {
+                            Some(idx) => {
+                                self.expanded_subtrees.insert(idx);
+                            }
+                            None => self.selected_node = node_at(&positions, (local.x, local.y), node_radius),
+                        }
+                    }
+                }
+
                 let to_screen = |pos: egui::Pos2| {
                     let zoomed_pos = egui::pos2(pos.x * self.tree_zoom, pos.y * self.tree_zoom);
                     response.rect.min + zoomed_pos.to_vec2()
@@ -1403,17 +2705,39 @@ impl DsavApp {
                             egui::Stroke::new(3.0 * self.tree_zoom, border_color),
                         );
 
+                        if self.selected_node == Some(i) {
+                            painter.circle_stroke(
+                                center,
+                                (node_radius + 6.0) * self.tree_zoom,
+                                egui::Stroke::new(2.0 * self.tree_zoom, palette.mauve),
+                            );
+                        }
+
                         // Draw value (scaled font)
                         painter.text(
                             center,
                             egui::Align2::CENTER_CENTER,
                             elem.value.to_string(),
-                            egui::FontId::monospace((18.0 * self.tree_zoom).max(8.0)),
+                            self.label_font(18.0 * self.tree_zoom),
                             palette.text,
                         );
+
+                        if let Some(badge) = &elem.badge {
+                            let corner = node_radius * self.tree_zoom * std::f32::consts::FRAC_1_SQRT_2;
+                            self.draw_badge(&painter, center + egui::vec2(corner, -corner), badge);
+                        }
                     }
                 }
+
+                self.draw_collapse_indicators(&painter, &collapsed, &indicators, to_screen);
             });
+
+        let target = self.render_tree_minimap(ui, &scroll_output, &state, &positions, |elem| {
+            self.get_element_colors(elem.state).1
+        });
+        if target.is_some() {
+            self.tree_scroll_target = target;
+        }
     }
 
     fn render_rb_tree(&mut self, ui: &mut egui::Ui) {
@@ -1424,13 +2748,13 @@ impl DsavApp {
             self.rb_tree.render_state()
         };
 
-        // Early return if empty
-        if state.elements.is_empty() {
-            ui.vertical_centered(|ui| {
-                ui.add_space(50.0);
-                ui.label("Red-Black Tree is empty. Click 'Insert' to add nodes.");
-                ui.add_space(50.0);
-            });
+        if state.is_empty_placeholder() {
+            self.render_empty_placeholder(ui, &state);
+            return;
+        }
+
+        if let Err(e) = state.validate() {
+            ui.colored_label(palette.red, format!("Cannot draw tree: {}", e));
             return;
         }
 
@@ -1454,12 +2778,14 @@ impl DsavApp {
             }
         }
 
+        self.apply_load_diff(&mut state);
+        let collapsed = self.apply_depth_limit(&mut state);
+
         // Calculate tree layout positions
         let node_radius = 25.0;
         let level_height = 100.0;
 
-        // Find tree depth and calculate required width
-        let max_depth = self.calculate_tree_depth(&state);
+        // Calculate required width
         let tree_width = self.calculate_subtree_width(0, &state);
         let initial_width = tree_width.max(800.0);
 
@@ -1487,9 +2813,16 @@ impl DsavApp {
         let zoomed_width = (max_x + 100.0).max(600.0) * self.tree_zoom;
         let zoomed_height = (max_y + 100.0).max(400.0) * self.tree_zoom;
 
+        let subtree_size = self.selected_node.and_then(|idx| self.rb_tree.subtree_size(idx));
+        self.render_node_details(ui, &state, subtree_size);
+
         // Create scrollable area for the tree
-        egui::ScrollArea::both()
-            .auto_shrink([false, false])
+        let mut scroll_area = egui::ScrollArea::both().auto_shrink([false, false]);
+        if let Some(offset) = self.tree_scroll_target.take() {
+            scroll_area = scroll_area.scroll_offset(offset);
+        }
+
+        let scroll_output = scroll_area
             .show(ui, |ui| {
                 let (response, painter) = ui.allocate_painter(
                     egui::vec2(zoomed_width, zoomed_height),
@@ -1505,6 +2838,22 @@ impl DsavApp {
                     }
                 }
 
+                let indicators = collapse_indicator_positions(&collapsed, &positions, node_radius);
+
+                // Clicking a "hidden" indicator expands that subtree, clicking
+                // a node selects it, clicking empty space deselects
+                if response.clicked() {
+                    if let Some(pointer) = response.interact_pointer_pos() {
+                        let local = (pointer - response.rect.min) / self.tree_zoom;
+                        match node_at(&indicators, (local.x, local.y), node_radius * 0.6) {
+                            Some(idx) => {
+                                self.expanded_subtrees.insert(idx);
+                            }
+                            None => self.selected_node = node_at(&positions, (local.x, local.y), node_radius),
+                        }
+                    }
+                }
+
                 let to_screen = |pos: egui::Pos2| {
                     let zoomed_pos = egui::pos2(pos.x * self.tree_zoom, pos.y * self.tree_zoom);
                     response.rect.min + zoomed_pos.to_vec2()
@@ -1561,41 +2910,161 @@ impl DsavApp {
                             egui::Stroke::new(3.0 * self.tree_zoom, border_color),
                         );
 
+                        if self.selected_node == Some(i) {
+                            painter.circle_stroke(
+                                center,
+                                (node_radius + 6.0) * self.tree_zoom,
+                                egui::Stroke::new(2.0 * self.tree_zoom, palette.mauve),
+                            );
+                        }
+
                         // Draw value (scaled font)
                         painter.text(
                             center,
                             egui::Align2::CENTER_CENTER,
                             elem.value.to_string(),
-                            egui::FontId::monospace((18.0 * self.tree_zoom).max(8.0)),
+                            self.label_font(18.0 * self.tree_zoom),
                             palette.text,
                         );
 
+                        if let Some(badge) = &elem.badge {
+                            let corner = node_radius * self.tree_zoom * std::f32::consts::FRAC_1_SQRT_2;
+                            self.draw_badge(&painter, center + egui::vec2(corner, -corner), badge);
+                        }
+
                         // Draw R/B indicator below node (scaled)
                         painter.text(
                             to_screen(egui::pos2(x, y + node_radius + 12.0)),
                             egui::Align2::CENTER_TOP,
                             &elem.sublabel,
-                            egui::FontId::monospace((14.0 * self.tree_zoom).max(8.0)),
+                            self.label_font(14.0 * self.tree_zoom),
                             if is_red_node { palette.red } else { palette.text.gamma_multiply(0.8) },
                         );
                     }
                 }
+
+                self.draw_collapse_indicators(&painter, &collapsed, &indicators, to_screen);
             });
+
+        let target = self.render_tree_minimap(ui, &scroll_output, &state, &positions, |elem| {
+            if elem.state == dsav_core::state::ElementState::Comparing {
+                palette.red
+            } else {
+                palette.text.gamma_multiply(0.6)
+            }
+        });
+        if target.is_some() {
+            self.tree_scroll_target = target;
+        }
     }
 
-    // Calculate maximum depth of the tree
-    fn calculate_tree_depth(&self, state: &dsav_core::state::RenderState) -> usize {
-        let mut max_depth = 0;
-        for i in 0..state.elements.len() {
-            if !state.elements[i].label.is_empty() {
-                let depth = (i as f32 + 1.0).log2().floor() as usize;
-                max_depth = max_depth.max(depth);
+    /// Overlay a scaled-down copy of the whole tree in the top-right corner of
+    /// the tree view, with a rectangle marking the visible region.
+    ///
+    /// Only shown when the tree doesn't fit the view. Returns the scroll
+    /// offset to apply when the minimap is clicked or dragged.
+    fn render_tree_minimap(
+        &self,
+        ui: &egui::Ui,
+        scroll_output: &egui::scroll_area::ScrollAreaOutput<()>,
+        state: &dsav_core::state::RenderState,
+        positions: &std::collections::HashMap<usize, (f32, f32)>,
+        node_color: impl Fn(&dsav_core::state::RenderElement) -> egui::Color32,
+    ) -> Option<egui::Vec2> {
+        let palette = self.current_theme.colors();
+        let viewport = scroll_output.inner_rect;
+        let content = scroll_output.content_size;
+        if content.x <= viewport.width() && content.y <= viewport.height() {
+            return None;
+        }
+
+        let transform = MinimapTransform::fit(content, egui::vec2(MINIMAP_MAX_SIZE, MINIMAP_MAX_SIZE));
+        let rect = egui::Rect::from_min_size(
+            viewport.right_top() + egui::vec2(-transform.size().x - 8.0, 8.0),
+            transform.size(),
+        );
+        let response = ui.interact(rect, ui.id().with("tree_minimap"), egui::Sense::click_and_drag());
+
+        let painter = ui.painter();
+        painter.rect_filled(rect, 4.0, palette.background.gamma_multiply(0.9));
+        painter.rect_stroke(rect, 4.0, egui::Stroke::new(1.0, palette.overlay));
+
+        // Node positions are in unzoomed layout coordinates, the canvas is zoomed
+        let to_minimap = |(x, y): (f32, f32)| {
+            rect.min + transform.to_minimap(egui::pos2(x * self.tree_zoom, y * self.tree_zoom)).to_vec2()
+        };
+
+        for &(parent_idx, child_idx) in &state.connections {
+            if let (Some(&parent), Some(&child)) = (positions.get(&parent_idx), positions.get(&child_idx)) {
+                painter.line_segment([to_minimap(parent), to_minimap(child)], egui::Stroke::new(1.0, palette.overlay));
+            }
+        }
+
+        let dot_radius = (25.0 * self.tree_zoom * transform.scale).max(1.5);
+        for (i, elem) in state.elements.iter().enumerate() {
+            if let (false, Some(&pos)) = (elem.label.is_empty(), positions.get(&i)) {
+                painter.circle_filled(to_minimap(pos), dot_radius, node_color(elem));
+            }
+        }
+
+        let visible = transform
+            .viewport_rect(scroll_output.state.offset, viewport.size())
+            .translate(rect.min.to_vec2());
+        painter.rect_stroke(visible, 2.0, egui::Stroke::new(1.5, palette.mauve));
+
+        if response.clicked() || response.dragged() {
+            if let Some(pointer) = response.interact_pointer_pos() {
+                ui.ctx().request_repaint();
+                return Some(transform.scroll_offset_for((pointer - rect.min).to_pos2(), viewport.size()));
+            }
+        }
+
+        None
+    }
+
+    /// Blank the labels of tree nodes hidden by the depth limit, so layout
+    /// and drawing skip them like empty slots. Returns the hidden-node count
+    /// under each collapsed node.
+    fn apply_depth_limit(&self, state: &mut dsav_core::RenderState) -> std::collections::BTreeMap<usize, usize> {
+        if !self.limit_tree_depth {
+            return std::collections::BTreeMap::new();
+        }
+
+        let collapsed = collapsed_subtrees(state, self.max_tree_depth, &self.expanded_subtrees);
+        let mut pending: Vec<usize> = collapsed.keys().flat_map(|&idx| [idx * 2 + 1, idx * 2 + 2]).collect();
+        while let Some(idx) = pending.pop() {
+            if let Some(elem) = state.elements.get_mut(idx) {
+                elem.label.clear();
+                pending.extend([idx * 2 + 1, idx * 2 + 2]);
+            }
+        }
+        collapsed
+    }
+
+    /// Draw "▼ N hidden" under each collapsed node.
+    fn draw_collapse_indicators(
+        &self,
+        painter: &egui::Painter,
+        collapsed: &std::collections::BTreeMap<usize, usize>,
+        indicators: &std::collections::HashMap<usize, (f32, f32)>,
+        to_screen: impl Fn(egui::Pos2) -> egui::Pos2,
+    ) {
+        let palette = self.current_theme.colors();
+        for (idx, hidden) in collapsed {
+            if let Some(&(x, y)) = indicators.get(idx) {
+                painter.text(
+                    to_screen(egui::pos2(x, y)),
+                    egui::Align2::CENTER_CENTER,
+                    format!("▼ {} hidden", hidden),
+                    self.label_font(12.0 * self.tree_zoom),
+                    palette.mauve,
+                );
             }
         }
-        max_depth
     }
 
     // Improved tree layout algorithm that prevents overlaps
+    #[allow(clippy::too_many_arguments)]
     fn calculate_node_positions(
         &self,
         idx: usize,
@@ -1611,7 +3080,6 @@ impl DsavApp {
         }
 
         const MIN_NODE_SPACING: f32 = 80.0; // Minimum spacing between nodes
-        const NODE_RADIUS: f32 = 25.0;
 
         // Calculate subtree widths
         let left_child_idx = idx * 2 + 1;
@@ -1620,29 +3088,6 @@ impl DsavApp {
         let has_left = left_child_idx < state.elements.len() && !state.elements[left_child_idx].label.is_empty();
         let has_right = right_child_idx < state.elements.len() && !state.elements[right_child_idx].label.is_empty();
 
-        // Calculate required width for this subtree
-        let left_width = if has_left {
-            self.calculate_subtree_width(left_child_idx, state)
-        } else {
-            MIN_NODE_SPACING / 2.0
-        };
-
-        let right_width = if has_right {
-            self.calculate_subtree_width(right_child_idx, state)
-        } else {
-            MIN_NODE_SPACING / 2.0
-        };
-
-        // Ensure we have enough space
-        let available_width = right_bound - left_bound;
-        let required_width = left_width + right_width + MIN_NODE_SPACING;
-
-        let scale = if required_width > available_width {
-            available_width / required_width
-        } else {
-            1.0
-        };
-
         // Position current node in the center of its space
         let x = (left_bound + right_bound) / 2.0;
         let y = 50.0 + depth as f32 * level_height;
@@ -1650,7 +3095,6 @@ impl DsavApp {
 
         // Calculate child positions with improved spacing
         if has_left {
-            let left_space = (left_width * scale).max(MIN_NODE_SPACING);
             self.calculate_node_positions(
                 left_child_idx,
                 depth + 1,
@@ -1663,7 +3107,6 @@ impl DsavApp {
         }
 
         if has_right {
-            let right_space = (right_width * scale).max(MIN_NODE_SPACING);
             self.calculate_node_positions(
                 right_child_idx,
                 depth + 1,
@@ -1720,20 +3163,16 @@ impl DsavApp {
                 if ui.button("⏸").clicked() {
                     self.playing = false;
                 }
-            } else {
-                if ui.button("▶").clicked() {
-                    self.playing = true;
-                }
+            } else if ui.button("▶").clicked() {
+                self.playing = true;
             }
 
-            if ui.button("⏭").clicked() {
-                if self.current_step_index < self.current_steps.len() - 1 {
-                    self.current_step_index += 1;
-                    self.playing = false;
-                    self.time_since_last_step = 0.0;
-                    if let Some(step) = self.current_steps.get(self.current_step_index) {
-                        self.status_message = step.description.clone();
-                    }
+            if ui.button("⏭").clicked() && self.current_step_index < self.current_steps.len() - 1 {
+                self.current_step_index += 1;
+                self.playing = false;
+                self.time_since_last_step = 0.0;
+                if let Some(step) = self.current_steps.get(self.current_step_index) {
+                    self.status_message = step.description.clone();
                 }
             }
         });
@@ -1741,33 +3180,54 @@ impl DsavApp {
         ui.add_space(8.0);
 
         ui.horizontal(|ui| {
-            if ui.button("⏪ Step Back").clicked() {
-                if self.current_step_index > 0 {
-                    self.current_step_index -= 1;
-                    self.playing = false;
-                    self.time_since_last_step = 0.0;
-                    if let Some(step) = self.current_steps.get(self.current_step_index) {
-                        self.status_message = step.description.clone();
-                    }
+            if ui.button("⏪ Step Back").clicked() && self.current_step_index > 0 {
+                self.current_step_index -= 1;
+                self.playing = false;
+                self.time_since_last_step = 0.0;
+                if let Some(step) = self.current_steps.get(self.current_step_index) {
+                    self.status_message = step.description.clone();
                 }
             }
 
-            if ui.button("⏩ Step Forward").clicked() {
-                if self.current_step_index < self.current_steps.len() - 1 {
-                    self.current_step_index += 1;
-                    self.playing = false;
-                    self.time_since_last_step = 0.0;
-                    if let Some(step) = self.current_steps.get(self.current_step_index) {
-                        self.status_message = step.description.clone();
-                    }
+            if ui.button("⏩ Step Forward").clicked() && self.current_step_index < self.current_steps.len() - 1 {
+                self.current_step_index += 1;
+                self.playing = false;
+                self.time_since_last_step = 0.0;
+                if let Some(step) = self.current_steps.get(self.current_step_index) {
+                    self.status_message = step.description.clone();
                 }
             }
         });
 
+        ui.add_space(4.0);
+
+        ui.horizontal(|ui| {
+            if ui.button("⏭ Skip to End").clicked() {
+                self.skip_to_end();
+            }
+
+            if ui.add_enabled(!self.current_steps.is_empty(), egui::Button::new("📋 Export Markdown"))
+                .on_hover_text("Copy the steps to the clipboard as a Markdown list")
+                .clicked()
+            {
+                ui.ctx().copy_text(steps_to_markdown(&self.current_steps));
+                self.status_message = format!("Copied {} steps as Markdown", self.current_steps.len());
+            }
+        });
+
         ui.add_space(8.0);
 
         ui.horizontal(|ui| {
             ui.label("Speed:");
+
+            // Presets come first so the slider shows a clicked preset in the same frame
+            for preset in SPEED_PRESETS {
+                let is_active = (self.animation_speed - preset).abs() < f32::EPSILON;
+                if ui.selectable_label(is_active, format!("{}x", preset)).clicked() {
+                    self.animation_speed = preset;
+                }
+            }
+
             if ui.add(egui::Slider::new(&mut self.animation_speed, 0.25..=4.0)
                 .text("x")
                 .logarithmic(true)).changed() {
@@ -1794,6 +3254,98 @@ impl DsavApp {
         ui.add(progress_bar);
     }
 
+    /// Jump straight to the final step of the loaded animation.
+    fn skip_to_end(&mut self) {
+        if let Some(last) = self.current_steps.last() {
+            self.current_step_index = self.current_steps.len() - 1;
+            self.playing = false;
+            self.time_since_last_step = 0.0;
+            self.status_message = last.description.clone();
+        }
+    }
+
+    /// Nested range boxes for the recursive calls active at the current step.
+    fn render_recursion_stack(&self, ui: &mut egui::Ui) {
+        let frames = active_frames(&self.current_steps, self.current_step_index);
+        if frames.is_empty() {
+            return;
+        }
+
+        let palette = self.current_theme.colors();
+        let len = self
+            .current_steps
+            .get(self.current_step_index)
+            .and_then(|step| step.metadata.get("array_state"))
+            .and_then(|state| state.as_array())
+            .map_or(self.array.len(), |state| state.len())
+            .max(1);
+
+        egui::CollapsingHeader::new("🪆 Recursion Stack")
+            .default_open(true)
+            .show(ui, |ui| {
+                let row_height = 20.0;
+                let width = ui.available_width();
+                let (rect, _) = ui.allocate_exact_size(
+                    egui::vec2(width, row_height * frames.len() as f32),
+                    egui::Sense::hover(),
+                );
+                let cell = rect.width() / len as f32;
+
+                for (depth, &(low, high)) in frames.iter().enumerate() {
+                    let is_current = depth == frames.len() - 1;
+                    let color = if is_current { palette.yellow } else { palette.blue };
+                    let top = rect.top() + depth as f32 * row_height;
+                    let frame_rect = egui::Rect::from_min_max(
+                        egui::pos2(rect.left() + low as f32 * cell, top + 2.0),
+                        egui::pos2(rect.left() + (high + 1) as f32 * cell, top + row_height - 2.0),
+                    );
+
+                    ui.painter().rect_stroke(frame_rect, 3.0, egui::Stroke::new(1.5, color));
+                    ui.painter().text(
+                        frame_rect.center(),
+                        egui::Align2::CENTER_CENTER,
+                        format!("[{}..{}]", low, high),
+                        egui::FontId::monospace(11.0),
+                        color,
+                    );
+                }
+
+                ui.label(format!("Depth: {}", frames.len() - 1));
+            });
+    }
+
+    fn render_step_details(&self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new("🔎 Step Details")
+            .default_open(false)
+            .show(ui, |ui| {
+                let Some(step) = self.current_steps.get(self.current_step_index) else {
+                    ui.label("No step selected");
+                    return;
+                };
+
+                if let Some(detail) = &step.detail {
+                    ui.label(egui::RichText::new(detail).italics());
+                    ui.add_space(4.0);
+                }
+
+                ui.label(format!("Highlighted: {:?}", step.highlight_indices));
+                ui.label(format!("Active: {:?}", step.active_indices));
+
+                ui.add_space(4.0);
+                ui.label("Metadata:");
+
+                let json = serde_json::to_string_pretty(&step.metadata)
+                    .unwrap_or_else(|e| format!("<invalid metadata: {}>", e));
+
+                egui::ScrollArea::vertical()
+                    .id_salt("step_metadata")
+                    .max_height(200.0)
+                    .show(ui, |ui| {
+                        ui.label(egui::RichText::new(json).monospace());
+                    });
+            });
+    }
+
     fn render_settings(&mut self, ctx: &egui::Context, palette: &ColorPalette) {
         egui::Window::new("⚙ Settings")
             .collapsible(false)
@@ -1810,13 +3362,41 @@ impl DsavApp {
                 ui.add_space(4.0);
 
                 for theme in Theme::all() {
-                    let is_selected = *theme == self.current_theme;
+                    let is_selected = theme == self.current_theme;
 
                     if ui.selectable_label(is_selected, theme.name()).clicked() {
-                        self.current_theme = *theme;
+                        self.current_theme = theme;
                     }
                 }
 
+                ui.add_space(8.0);
+
+                egui::CollapsingHeader::new("🖌 Custom Theme Editor").show(ui, |ui| {
+                    if ui.button("Start from current theme").clicked() {
+                        self.custom_palette = self.current_theme.colors();
+                    }
+
+                    egui::Grid::new("custom_palette_grid").num_columns(2).show(ui, |ui| {
+                        for (name, color) in self.custom_palette.slots_mut() {
+                            ui.label(name);
+                            ui.color_edit_button_srgba(color);
+                            ui.end_row();
+                        }
+                    });
+
+                    if ui.button("💾 Save & Apply").clicked() {
+                        match crate::colors::save_custom_palette(&self.custom_palette) {
+                            Ok(()) => {
+                                self.current_theme = Theme::Custom(self.custom_palette);
+                                self.status_message = "Custom theme saved".to_string();
+                            }
+                            Err(e) => {
+                                self.status_message = format!("Error saving custom theme: {}", e);
+                            }
+                        }
+                    }
+                });
+
                 ui.add_space(16.0);
 
                 ui.label("Preview:");
@@ -1851,6 +3431,107 @@ impl DsavApp {
 
                 ui.add_space(16.0);
 
+                ui.heading("Animation");
+                ui.separator();
+                ui.add_space(8.0);
+
+                ui.horizontal(|ui| {
+                    ui.label("Max steps:");
+                    if ui
+                        .add(egui::DragValue::new(&mut self.max_steps).speed(50.0).range(100..=100_000))
+                        .on_hover_text("Longer animations are truncated at this many steps")
+                        .changed()
+                    {
+                        dsav_core::set_max_steps(Some(self.max_steps));
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("Compare steps:");
+                    ui.add(egui::Slider::new(&mut self.compare_delay, 0.25..=4.0).suffix("x duration"));
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("Swap steps:");
+                    ui.add(egui::Slider::new(&mut self.swap_delay, 0.25..=4.0).suffix("x duration"))
+                        .on_hover_text("Also applies to steps that place a value");
+                });
+
+                ui.add_space(16.0);
+
+                ui.heading("Labels");
+                ui.separator();
+                ui.add_space(8.0);
+
+                ui.horizontal(|ui| {
+                    ui.label("Font:");
+                    ui.selectable_value(&mut self.label_font, LabelFont::Monospace, "Monospace")
+                        .on_hover_text("Digits line up in columns");
+                    ui.selectable_value(&mut self.label_font, LabelFont::Proportional, "Proportional");
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("Size:");
+                    ui.add(egui::Slider::new(&mut self.label_font_scale, 0.5..=2.0).suffix("x"))
+                        .on_hover_text("Scales element labels in every view; array boxes keep their own base size");
+                });
+
+                ui.add_space(16.0);
+
+                ui.heading("Trees");
+                ui.separator();
+                ui.add_space(8.0);
+
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.limit_tree_depth, "Limit visible depth to");
+                    ui.add_enabled(
+                        self.limit_tree_depth,
+                        egui::DragValue::new(&mut self.max_tree_depth).range(1..=20).speed(0.1),
+                    );
+                })
+                .response
+                .on_hover_text("Deeper nodes are hidden; click a node's \"▼ hidden\" marker to expand it");
+
+                if ui
+                    .add_enabled(!self.expanded_subtrees.is_empty(), egui::Button::new("Collapse expanded subtrees"))
+                    .clicked()
+                {
+                    self.expanded_subtrees.clear();
+                }
+
+                ui.add_space(16.0);
+
+                ui.heading("Array Elements");
+                ui.separator();
+                ui.add_space(8.0);
+
+                ui.horizontal(|ui| {
+                    ui.label("Box size:");
+                    ui.add(egui::DragValue::new(&mut self.array_element_size.x).speed(1.0).range(30.0..=200.0).prefix("W "));
+                    ui.add(egui::DragValue::new(&mut self.array_element_size.y).speed(1.0).range(30.0..=200.0).prefix("H "));
+                });
+
+                ui.horizontal(|ui| {
+                    ui.label("Font size:");
+                    ui.add(egui::Slider::new(&mut self.array_font_size, 10.0..=40.0));
+                });
+
+                ui.checkbox(&mut self.array_auto_width, "Widen boxes to fit labels");
+                ui.checkbox(&mut self.array_wrap, "Wrap long arrays onto multiple rows");
+                ui.checkbox(&mut self.one_based_indices, "Number indices from 1")
+                    .on_hover_text("Label array, queue and list positions [1], [2], ... instead of [0], [1], ...");
+
+                ui.add_space(16.0);
+
+                ui.heading("Data Loading");
+                ui.separator();
+                ui.add_space(8.0);
+
+                ui.checkbox(&mut self.show_load_diff, "Highlight changes when randomizing")
+                    .on_hover_text("Briefly mark which elements changed or were added");
+
+                ui.add_space(16.0);
+
                 if ui.button("Close").clicked() {
                     self.show_settings = false;
                 }
@@ -1870,8 +3551,263 @@ impl DsavApp {
             ElementState::Sorted => (palette.green.gamma_multiply(0.3), palette.green),
             ElementState::Comparing => (palette.yellow.gamma_multiply(0.3), palette.yellow),
             ElementState::Swapping => (palette.peach.gamma_multiply(0.3), palette.peach),
+            ElementState::Placeholder => (palette.background, palette.overlay),
+            ElementState::Gap => (palette.background, palette.overlay.gamma_multiply(0.5)),
+        }
+    }
+}
+
+/// The open brackets a bracket-check step holds, as a stack render state.
+///
+/// Bracket checks list their `stack_state` as strings; other stack steps hold
+/// numbers and are drawn from the live stack instead.
+fn bracket_stack_state(step: &Step) -> Option<dsav_core::state::RenderState> {
+    use dsav_core::state::{RenderElement, RenderState};
+
+    let brackets = step
+        .metadata
+        .get("stack_state")?
+        .as_array()?
+        .iter()
+        .map(|entry| entry.as_str())
+        .collect::<Option<Vec<_>>>()?;
+
+    if brackets.is_empty() {
+        return Some(RenderState::empty_placeholder("No open brackets", "Opening brackets are pushed here"));
+    }
+
+    Some(RenderState {
+        elements: brackets
+            .into_iter()
+            .map(|bracket| RenderElement::new(0).with_label(bracket.to_string()))
+            .collect(),
+        connections: Vec::new(),
+        cols: None,
+    })
+}
+
+/// Number of comparison steps among `steps[..=idx]`.
+fn count_comparisons_up_to(steps: &[Step], idx: usize) -> usize {
+    steps
+        .iter()
+        .take(idx.saturating_add(1))
+        .filter(|step| step.metadata.get("operation").and_then(|op| op.as_str()) == Some("compare"))
+        .count()
+}
+
+/// Vertical extent of a bar as `(top, bottom)` fractions of the chart height,
+/// measured from the top, for a chart spanning `low..=high`.
+///
+/// The baseline sits at zero, so positive bars rise above it and negative bars
+/// hang below. Every bar keeps a sliver of height so zeros stay visible.
+fn bar_extent(value: i32, low: i32, high: i32) -> (f32, f32) {
+    let span = (high as f32 - low as f32).max(1.0);
+    let baseline = high as f32 / span;
+    let end = (high as f32 - value as f32) / span;
+
+    let (top, bottom) = if end < baseline { (end, baseline) } else { (baseline, end) };
+    (top, bottom.max(top + 0.01))
+}
+
+/// How many array boxes of `slot_width` (box plus gap) fit in
+/// `available_width`. Always at least one so a narrow window still shows
+/// every element, one per row.
+fn elements_per_row(available_width: f32, slot_width: f32) -> usize {
+    if slot_width <= 0.0 {
+        return 1;
+    }
+    ((available_width / slot_width).floor() as usize).max(1)
+}
+
+/// How much longer than the base duration `step` stays on screen, chosen by
+/// its `operation` metadata. Steps without one keep the base duration.
+fn step_delay_multiplier(step: &Step, compare_delay: f32, swap_delay: f32) -> f32 {
+    match step.metadata.get("operation").and_then(|op| op.as_str()) {
+        Some("compare") => compare_delay,
+        Some("swap" | "place") => swap_delay,
+        _ => 1.0,
+    }
+}
+
+/// Scale-down from the zoomed tree canvas to the minimap, which keeps the
+/// canvas's aspect ratio and never scales up. Minimap points are relative to
+/// its top-left corner.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct MinimapTransform {
+    scale: f32,
+    content: egui::Vec2,
+}
+
+impl MinimapTransform {
+    /// Fit a canvas of size `content` into `bounds`.
+    fn fit(content: egui::Vec2, bounds: egui::Vec2) -> Self {
+        let scale = (bounds.x / content.x.max(1.0)).min(bounds.y / content.y.max(1.0)).min(1.0);
+        Self { scale, content }
+    }
+
+    fn size(self) -> egui::Vec2 {
+        self.content * self.scale
+    }
+
+    fn to_minimap(self, canvas: egui::Pos2) -> egui::Pos2 {
+        (canvas.to_vec2() * self.scale).to_pos2()
+    }
+
+    /// Visible region on the minimap for a scroll `offset` and `viewport` size.
+    fn viewport_rect(self, offset: egui::Vec2, viewport: egui::Vec2) -> egui::Rect {
+        egui::Rect::from_min_size((offset * self.scale).to_pos2(), viewport * self.scale)
+            .intersect(egui::Rect::from_min_size(egui::Pos2::ZERO, self.size()))
+    }
+
+    /// Scroll offset that centers the viewport on a minimap point, clamped so
+    /// the view stays inside the canvas.
+    fn scroll_offset_for(self, point: egui::Pos2, viewport: egui::Vec2) -> egui::Vec2 {
+        let center = point.to_vec2() / self.scale;
+        let max_offset = (self.content - viewport).max(egui::Vec2::ZERO);
+        (center - viewport / 2.0).clamp(egui::Vec2::ZERO, max_offset)
+    }
+}
+
+/// For a heap-indexed tree state (children of `i` at `2i + 1` and `2i + 2`),
+/// the visible nodes at the depth limit that have hidden descendants, mapped
+/// to how many. Nodes deeper than `max_depth` are hidden unless an ancestor,
+/// or the node itself, is in `expanded`, which shows its whole subtree.
+/// Blank slots are not nodes.
+fn collapsed_subtrees(
+    state: &dsav_core::RenderState,
+    max_depth: usize,
+    expanded: &std::collections::HashSet<usize>,
+) -> std::collections::BTreeMap<usize, usize> {
+    let present = |idx: usize| state.elements.get(idx).is_some_and(|elem| !elem.label.is_empty());
+    let count = |root: usize| {
+        let mut pending = vec![root];
+        let mut total = 0;
+        while let Some(idx) = pending.pop() {
+            if present(idx) {
+                total += 1;
+                pending.extend([idx * 2 + 1, idx * 2 + 2]);
+            }
+        }
+        total
+    };
+
+    let mut collapsed = std::collections::BTreeMap::new();
+    // (index, depth, inside an expanded subtree)
+    let mut pending = vec![(0, 0, false)];
+    while let Some((idx, depth, forced)) = pending.pop() {
+        if !present(idx) {
+            continue;
+        }
+
+        let children = [idx * 2 + 1, idx * 2 + 2];
+        let forced = forced || expanded.contains(&idx);
+        if depth < max_depth || forced {
+            pending.extend(children.map(|child| (child, depth + 1, forced)));
+        } else {
+            let hidden: usize = children.into_iter().map(count).sum();
+            if hidden > 0 {
+                collapsed.insert(idx, hidden);
+            }
+        }
+    }
+    collapsed
+}
+
+/// Where each collapsed node's indicator sits, just below the node.
+fn collapse_indicator_positions(
+    collapsed: &std::collections::BTreeMap<usize, usize>,
+    positions: &std::collections::HashMap<usize, (f32, f32)>,
+    node_radius: f32,
+) -> std::collections::HashMap<usize, (f32, f32)> {
+    collapsed
+        .keys()
+        .filter_map(|idx| positions.get(idx).map(|&(x, y)| (*idx, (x, y + node_radius + COLLAPSE_INDICATOR_OFFSET))))
+        .collect()
+}
+
+/// Short human-readable duration: microseconds below a millisecond,
+/// otherwise whole milliseconds.
+fn format_duration(duration: std::time::Duration) -> String {
+    if duration.as_millis() == 0 {
+        format!("{}µs", duration.as_micros())
+    } else {
+        format!("{}ms", duration.as_millis())
+    }
+}
+
+/// Index as shown in array, queue and list labels.
+fn display_index(index: usize, one_based: bool) -> usize {
+    if one_based {
+        index + 1
+    } else {
+        index
+    }
+}
+
+/// Index of the tree node whose circle of `radius` contains `point`, in
+/// unzoomed layout coordinates.
+fn node_at(positions: &std::collections::HashMap<usize, (f32, f32)>, point: (f32, f32), radius: f32) -> Option<usize> {
+    positions
+        .iter()
+        .find(|(_, &(x, y))| (x - point.0).powi(2) + (y - point.1).powi(2) <= radius * radius)
+        .map(|(&idx, _)| idx)
+}
+
+/// Render steps as a numbered Markdown list for lecture notes.
+///
+/// The heading is the first step's `operation` metadata, falling back to its
+/// description. Highlighted and active indices are noted after each step.
+fn steps_to_markdown(steps: &[Step]) -> String {
+    let heading = steps
+        .first()
+        .map(|first| {
+            first
+                .metadata
+                .get("operation")
+                .and_then(|op| op.as_str())
+                .unwrap_or(&first.description)
+                .to_string()
+        })
+        .unwrap_or_else(|| "Steps".to_string());
+
+    let mut markdown = format!("## {}\n\n", heading);
+    for (i, step) in steps.iter().enumerate() {
+        markdown.push_str(&format!("{}. {}", i + 1, step.description));
+        if !step.highlight_indices.is_empty() {
+            markdown.push_str(&format!(" — highlighted: {:?}", step.highlight_indices));
         }
+        if !step.active_indices.is_empty() {
+            markdown.push_str(&format!(" — active: {:?}", step.active_indices));
+        }
+        markdown.push('\n');
+    }
+    markdown
+}
+
+/// Inclusive `(low, high)` ranges of the recursive calls active at `steps[idx]`,
+/// outermost first.
+///
+/// Frames are rebuilt from the `recursion_depth` and `range` metadata that the
+/// recursive sorts attach to their steps; other steps have no active frames.
+fn active_frames(steps: &[Step], idx: usize) -> Vec<(usize, usize)> {
+    let frame = |step: &Step| {
+        let depth = step.metadata.get("recursion_depth")?.as_u64()? as usize;
+        let range = step.metadata.get("range")?.as_array()?;
+        let low = range.first()?.as_u64()? as usize;
+        let high = range.get(1)?.as_u64()? as usize;
+        Some((depth, low, high))
+    };
+
+    if steps.get(idx).and_then(frame).is_none() {
+        return Vec::new();
+    }
+
+    let mut frames = Vec::new();
+    for (depth, low, high) in steps.iter().take(idx + 1).filter_map(frame) {
+        frames.truncate(depth);
+        frames.push((low, high));
     }
+    frames
 }
 
 impl Default for DsavApp {
@@ -1879,3 +3815,325 @@ impl Default for DsavApp {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(operation: &str) -> Step {
+        Step {
+            description: String::new(),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({ "operation": operation }),
+            detail: None,
+        }
+    }
+
+    #[test]
+    fn test_count_comparisons_up_to() {
+        let steps = vec![step("compare"), step("swap"), step("compare"), step("compare")];
+
+        assert_eq!(count_comparisons_up_to(&steps, 0), 1);
+        assert_eq!(count_comparisons_up_to(&steps, 1), 1);
+        assert_eq!(count_comparisons_up_to(&steps, 2), 2);
+        assert_eq!(count_comparisons_up_to(&steps, 10), 3);
+        assert_eq!(count_comparisons_up_to(&[], 0), 0);
+    }
+
+    fn frame_step(depth: usize, low: usize, high: usize) -> Step {
+        Step {
+            description: String::new(),
+            highlight_indices: vec![],
+            active_indices: vec![],
+            metadata: serde_json::json!({ "recursion_depth": depth, "range": [low, high] }),
+            detail: None,
+        }
+    }
+
+    #[test]
+    fn test_bracket_stack_state() {
+        let (_, steps) = dsav_core::algorithms::check_balanced_with_steps("([)]");
+
+        let pushed = bracket_stack_state(&steps[2]).unwrap();
+        let labels: Vec<&str> = pushed.elements.iter().map(|e| e.label.as_str()).collect();
+        assert_eq!(labels, vec!["(", "["]);
+        assert!(bracket_stack_state(&steps[0]).unwrap().is_empty_placeholder());
+
+        // push_many separators record numbers and keep the live stack
+        let mut stack = VisualizableStack::with_capacity(4);
+        let numeric = stack.push_many_with_steps(&[1, 2]);
+        assert!(numeric.iter().all(|step| bracket_stack_state(step).is_none()));
+    }
+
+    #[test]
+    fn test_active_frames() {
+        let steps = vec![
+            step("start"),
+            frame_step(0, 0, 7),
+            frame_step(1, 0, 3),
+            frame_step(2, 0, 1),
+            frame_step(1, 4, 7),
+            step("done"),
+        ];
+
+        assert!(active_frames(&steps, 0).is_empty());
+        assert_eq!(active_frames(&steps, 1), vec![(0, 7)]);
+        assert_eq!(active_frames(&steps, 3), vec![(0, 7), (0, 3), (0, 1)]);
+        assert_eq!(active_frames(&steps, 4), vec![(0, 7), (4, 7)]);
+        assert!(active_frames(&steps, 5).is_empty());
+        assert!(active_frames(&steps, 99).is_empty());
+    }
+
+    #[test]
+    fn test_active_frames_from_merge_sort() {
+        let mut arr = vec![4, 3, 2, 1];
+        let steps = dsav_core::algorithms::sorting::merge_sort_with_steps(&mut arr).unwrap();
+
+        let all: Vec<_> = (0..steps.len()).map(|idx| active_frames(&steps, idx)).collect();
+        assert!(all.contains(&vec![(0, 3), (0, 1)]));
+        assert!(all.contains(&vec![(0, 3), (2, 3)]));
+        assert!(all.iter().all(|frames| frames.len() <= 2));
+    }
+
+    #[test]
+    fn test_skip_to_end_jumps_to_last_step() {
+        let mut app = DsavApp::new();
+        app.execute_array_operation(Operation::BubbleSort);
+        assert!(app.current_steps.len() > 1);
+
+        app.skip_to_end();
+
+        assert_eq!(app.current_step_index, app.current_steps.len() - 1);
+        assert!(!app.playing);
+        assert_eq!(app.status_message, app.current_steps.last().unwrap().description);
+    }
+
+    #[test]
+    fn test_skip_to_end_without_steps_is_noop() {
+        let mut app = DsavApp::new();
+        let status = app.status_message.clone();
+
+        app.skip_to_end();
+
+        assert_eq!(app.current_step_index, 0);
+        assert_eq!(app.status_message, status);
+    }
+
+    #[test]
+    fn test_seeded_randomize_is_reproducible() {
+        let mut app = DsavApp::new();
+        app.use_seed = true;
+        app.seed = 7;
+
+        let first = app.random_values();
+        assert_eq!(first.len(), app.randomize_size);
+        assert_eq!(app.random_values(), first);
+    }
+
+    #[test]
+    fn test_node_at() {
+        let positions = std::collections::HashMap::from([(0, (100.0, 50.0)), (1, (50.0, 150.0))]);
+
+        assert_eq!(node_at(&positions, (105.0, 55.0), 25.0), Some(0));
+        assert_eq!(node_at(&positions, (50.0, 170.0), 25.0), Some(1));
+        assert_eq!(node_at(&positions, (75.0, 100.0), 25.0), None);
+    }
+
+    #[test]
+    fn test_operation_macro() {
+        let mut recorded = OperationMacro::default();
+
+        // Nothing is kept until recording starts
+        recorded.record(DataStructure::Array, Operation::BubbleSort);
+        assert!(recorded.replay().is_empty());
+
+        recorded.start(DataStructure::Array);
+        recorded.record(DataStructure::Array, Operation::Append(5));
+        recorded.record(DataStructure::Bst, Operation::Traverse);
+        recorded.record(DataStructure::Array, Operation::BubbleSort);
+        recorded.stop();
+        recorded.record(DataStructure::Array, Operation::Sum);
+
+        let expected = vec![Operation::Append(5), Operation::BubbleSort];
+        assert_eq!(recorded.replay(), expected);
+        assert_eq!(recorded.replay(), expected, "replaying leaves the recording intact");
+
+        recorded.start(DataStructure::Array);
+        assert!(recorded.replay().is_empty(), "a new recording replaces the old one");
+
+        recorded.clear();
+        assert!(!recorded.recording);
+        assert_eq!(recorded.structure, None);
+    }
+
+    #[test]
+    fn test_split_keeps_detached_tail() {
+        let mut app = DsavApp::new();
+        let original = app.linked_list.to_vec();
+        assert!(original.len() >= 3);
+
+        app.execute_linked_list_operation(Operation::SplitAt(2));
+        app.execute_linked_list_operation(Operation::SplitAt(1));
+        assert_eq!(app.linked_list.to_vec(), original[..1].to_vec());
+
+        // Both cut-off pieces are kept, in list order
+        let tail = app.split_tail.take().unwrap();
+        assert_eq!(tail.to_vec(), original[1..].to_vec());
+        app.linked_list.append(tail);
+        assert_eq!(app.linked_list.to_vec(), original);
+    }
+
+    #[test]
+    fn test_replay_restores_rb_tree_snapshot() {
+        let mut app = DsavApp::new();
+        app.selected_structure = DataStructure::RBTree;
+        app.rb_tree = VisualizableRBTree::new();
+        for value in [10, 20] {
+            app.rb_tree.insert(value);
+        }
+
+        app.operation_macro.start(DataStructure::RBTree);
+        app.macro_snapshot = Some(app.snapshot_structure());
+        for value in [30, 40, 50] {
+            app.execute_rb_tree_operation(Operation::Insert(0, value));
+        }
+        app.operation_macro.stop();
+        let recorded = app.rb_tree.pretty_print();
+
+        // The snapshot must not have picked up the recorded inserts
+        app.start_replay();
+        assert_eq!(app.rb_tree.size(), 2);
+        assert_eq!(app.rb_tree.render_state().elements[2].label, "20");
+
+        while !app.replay_queue.is_empty() {
+            app.replay_next();
+        }
+        assert_eq!(app.rb_tree.pretty_print(), recorded);
+        assert_eq!(app.rb_tree.size(), 5);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        use std::time::Duration;
+
+        assert_eq!(format_duration(Duration::from_micros(250)), "250µs");
+        assert_eq!(format_duration(Duration::from_micros(12_400)), "12ms");
+        assert_eq!(format_duration(Duration::from_secs(2)), "2000ms");
+    }
+
+    #[test]
+    fn test_label_font() {
+        let mut app = DsavApp::new();
+        assert_eq!(app.label_font(20.0), egui::FontId::monospace(20.0));
+
+        app.label_font = LabelFont::Proportional;
+        app.label_font_scale = 1.5;
+        assert_eq!(app.label_font(20.0), egui::FontId::proportional(30.0));
+
+        // Tiny zoomed-out labels are held at the minimum size
+        app.label_font_scale = 0.5;
+        assert_eq!(app.label_font(4.0).size, MIN_LABEL_FONT_SIZE);
+    }
+
+    #[test]
+    fn test_collapsed_subtrees() {
+        use std::collections::HashSet;
+
+        // 50 → 30 → 20 → 10 → 5, plus 70 on the right of the root
+        let mut bst = dsav_core::structures::VisualizableBST::new();
+        for value in [50, 30, 70, 20, 10, 5] {
+            bst.insert(value);
+        }
+        let state = bst.render_state();
+
+        let collapsed = collapsed_subtrees(&state, 1, &HashSet::new());
+        assert_eq!(collapsed.into_iter().collect::<Vec<_>>(), vec![(1, 3)]);
+
+        // 20 is at depth 2, with 10 and 5 below it
+        let collapsed = collapsed_subtrees(&state, 2, &HashSet::new());
+        assert_eq!(collapsed.into_iter().collect::<Vec<_>>(), vec![(3, 2)]);
+
+        // Expanding 30 shows everything under it
+        assert!(collapsed_subtrees(&state, 1, &HashSet::from([1])).is_empty());
+        assert!(collapsed_subtrees(&state, 4, &HashSet::new()).is_empty());
+    }
+
+    #[test]
+    fn test_display_index() {
+        assert_eq!(display_index(0, false), 0);
+        assert_eq!(display_index(0, true), 1);
+        assert_eq!(display_index(4, true), 5);
+    }
+
+    #[test]
+    fn test_minimap_transform() {
+        let transform = MinimapTransform::fit(egui::vec2(1600.0, 400.0), egui::vec2(160.0, 160.0));
+        assert_eq!(transform.scale, 0.1);
+        assert_eq!(transform.size(), egui::vec2(160.0, 40.0));
+        assert_eq!(transform.to_minimap(egui::pos2(800.0, 200.0)), egui::pos2(80.0, 20.0));
+
+        let visible = transform.viewport_rect(egui::vec2(400.0, 0.0), egui::vec2(600.0, 400.0));
+        assert_eq!(visible, egui::Rect::from_min_max(egui::pos2(40.0, 0.0), egui::pos2(100.0, 40.0)));
+
+        // Clicking centers the view there, but never scrolls past the edges
+        assert_eq!(transform.scroll_offset_for(egui::pos2(80.0, 20.0), egui::vec2(600.0, 400.0)), egui::vec2(500.0, 0.0));
+        assert_eq!(transform.scroll_offset_for(egui::pos2(160.0, 40.0), egui::vec2(600.0, 400.0)), egui::vec2(1000.0, 0.0));
+
+        // Small canvases are never scaled up
+        assert_eq!(MinimapTransform::fit(egui::vec2(100.0, 50.0), egui::vec2(160.0, 160.0)).scale, 1.0);
+    }
+
+    #[test]
+    fn test_bar_extent() {
+        // All positive: baseline at the bottom, tallest bar fills the chart
+        assert_eq!(bar_extent(10, 0, 10), (0.0, 1.0));
+        assert_eq!(bar_extent(5, 0, 10), (0.5, 1.0));
+
+        // Mixed signs: baseline between, negative bars hang below it
+        assert_eq!(bar_extent(10, -10, 10), (0.0, 0.5));
+        assert_eq!(bar_extent(-10, -10, 10), (0.5, 1.0));
+
+        let (top, bottom) = bar_extent(0, 0, 10);
+        assert!(bottom > top);
+    }
+
+    #[test]
+    fn test_step_delay_multiplier() {
+        assert_eq!(step_delay_multiplier(&step("compare"), 0.5, 2.0), 0.5);
+        assert_eq!(step_delay_multiplier(&step("swap"), 0.5, 2.0), 2.0);
+        assert_eq!(step_delay_multiplier(&step("place"), 0.5, 2.0), 2.0);
+        assert_eq!(step_delay_multiplier(&step("pivot"), 0.5, 2.0), 1.0);
+
+        let mut plain = step("compare");
+        plain.metadata = serde_json::json!({});
+        assert_eq!(step_delay_multiplier(&plain, 0.5, 2.0), 1.0);
+    }
+
+    #[test]
+    fn test_elements_per_row() {
+        assert_eq!(elements_per_row(680.0, 68.0), 10);
+        assert_eq!(elements_per_row(700.0, 68.0), 10);
+        assert_eq!(elements_per_row(50.0, 68.0), 1);
+        assert_eq!(elements_per_row(-20.0, 68.0), 1);
+        assert_eq!(elements_per_row(500.0, 0.0), 1);
+    }
+
+    #[test]
+    fn test_steps_to_markdown() {
+        let mut first = step("bubble_sort");
+        first.description = "Starting Bubble Sort".to_string();
+        let mut second = step("compare");
+        second.description = "Comparing 3 and 1".to_string();
+        second.highlight_indices = vec![0, 1];
+        let mut third = step("swap");
+        third.description = "Swapped".to_string();
+        third.active_indices = vec![0];
+
+        assert_eq!(
+            steps_to_markdown(&[first, second, third]),
+            "## bubble_sort\n\n1. Starting Bubble Sort\n2. Comparing 3 and 1 — highlighted: [0, 1]\n3. Swapped — active: [0]\n"
+        );
+        assert_eq!(steps_to_markdown(&[]), "## Steps\n\n");
+    }
+}
@@ -1,6 +1,9 @@
 //! Color palettes and theming for DSAV GUI.
 
 use egui::Color32;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Theme {
@@ -14,11 +17,13 @@ pub enum Theme {
     TokyoNight,
     SolarizedDark,
     OneDark,
+    Custom(ColorPalette),
 }
 
 impl Theme {
-    pub fn all() -> &'static [Theme] {
-        &[
+    /// Built-in themes, followed by the saved custom theme if there is one.
+    pub fn all() -> Vec<Theme> {
+        let mut themes = vec![
             Theme::Vibrant,
             Theme::TokyoNight,
             Theme::Dracula,
@@ -29,7 +34,13 @@ impl Theme {
             Theme::CatppuccinMocha,
             Theme::CatppuccinLatte,
             Theme::HighContrast,
-        ]
+        ];
+
+        if let Some(palette) = saved_custom_palette() {
+            themes.push(Theme::Custom(palette));
+        }
+
+        themes
     }
 
     pub fn name(&self) -> &'static str {
@@ -44,6 +55,7 @@ impl Theme {
             Theme::TokyoNight => "🌃 Tokyo Night (Modern)",
             Theme::SolarizedDark => "☯️ Solarized Dark (Classic)",
             Theme::OneDark => "🌑 One Dark (Atom)",
+            Theme::Custom(_) => "🖌 Custom",
         }
     }
 
@@ -59,26 +71,119 @@ impl Theme {
             Theme::TokyoNight => ColorPalette::tokyo_night(),
             Theme::SolarizedDark => ColorPalette::solarized_dark(),
             Theme::OneDark => ColorPalette::one_dark(),
+            Theme::Custom(palette) => *palette,
         }
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+/// Colors are stored as `#rrggbbaa` hex strings when serialized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(dead_code)]
 pub struct ColorPalette {
+    #[serde(with = "hex_color")]
     pub background: Color32,
+    #[serde(with = "hex_color")]
     pub surface: Color32,
+    #[serde(with = "hex_color")]
     pub overlay: Color32,
+    #[serde(with = "hex_color")]
     pub blue: Color32,
+    #[serde(with = "hex_color")]
     pub green: Color32,
+    #[serde(with = "hex_color")]
     pub yellow: Color32,
+    #[serde(with = "hex_color")]
     pub peach: Color32,
+    #[serde(with = "hex_color")]
     pub red: Color32,
+    #[serde(with = "hex_color")]
     pub mauve: Color32,
+    #[serde(with = "hex_color")]
     pub teal: Color32,
+    #[serde(with = "hex_color")]
     pub text: Color32,
+    #[serde(with = "hex_color")]
     pub subtext: Color32,
 }
 
+impl ColorPalette {
+    /// Mutable references to every slot with a display name, for editors.
+    pub fn slots_mut(&mut self) -> [(&'static str, &mut Color32); 12] {
+        [
+            ("Background", &mut self.background),
+            ("Surface", &mut self.surface),
+            ("Overlay", &mut self.overlay),
+            ("Blue", &mut self.blue),
+            ("Green", &mut self.green),
+            ("Yellow", &mut self.yellow),
+            ("Peach", &mut self.peach),
+            ("Red", &mut self.red),
+            ("Mauve", &mut self.mauve),
+            ("Teal", &mut self.teal),
+            ("Text", &mut self.text),
+            ("Subtext", &mut self.subtext),
+        ]
+    }
+}
+
+/// Serde shim for `Color32`, which has no serde support without egui's feature flag.
+mod hex_color {
+    use egui::Color32;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(color: &Color32, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&color.to_hex())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color32, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        Color32::from_hex(&hex).map_err(|e| serde::de::Error::custom(format!("invalid color {:?}: {:?}", hex, e)))
+    }
+}
+
+/// Where the custom theme is saved: `$XDG_CONFIG_HOME/dsav`, falling back to
+/// `~/.config/dsav`, or the working directory if neither is set.
+fn custom_theme_path() -> PathBuf {
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")));
+
+    match config_dir {
+        Some(dir) => dir.join("dsav").join("custom_theme.json"),
+        None => PathBuf::from("dsav_custom_theme.json"),
+    }
+}
+
+/// The saved custom palette, loaded from disk on first use.
+fn custom_palette_cache() -> &'static Mutex<Option<ColorPalette>> {
+    static CACHE: OnceLock<Mutex<Option<ColorPalette>>> = OnceLock::new();
+
+    CACHE.get_or_init(|| {
+        let palette = std::fs::read_to_string(custom_theme_path())
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok());
+        Mutex::new(palette)
+    })
+}
+
+pub fn saved_custom_palette() -> Option<ColorPalette> {
+    *custom_palette_cache().lock().unwrap()
+}
+
+/// Persist `palette` as the custom theme so it appears in [`Theme::all`].
+pub fn save_custom_palette(palette: &ColorPalette) -> std::io::Result<()> {
+    let path = custom_theme_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+
+    let json = serde_json::to_string_pretty(palette)?;
+    std::fs::write(path, json)?;
+
+    *custom_palette_cache().lock().unwrap() = Some(*palette);
+    Ok(())
+}
+
 impl ColorPalette {
     pub fn mocha() -> Self {
         Self {
@@ -252,8 +357,10 @@ impl ColorPalette {
 }
 
 // Backwards compatibility - use mocha theme by default
+#[allow(dead_code)]
 pub struct Colors;
 
+#[allow(dead_code)]
 impl Colors {
     pub const BACKGROUND: Color32 = Color32::from_rgb(30, 30, 46);
     pub const SURFACE: Color32 = Color32::from_rgb(49, 50, 68);
@@ -313,3 +420,27 @@ pub fn apply_theme(ctx: &egui::Context, palette: &ColorPalette) {
 
     ctx.set_style(style);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_palette_serde_round_trip() {
+        let mut palette = ColorPalette::nord();
+        palette.blue = Color32::from_rgb(1, 2, 3);
+
+        let json = serde_json::to_string(&palette).unwrap();
+        assert!(json.contains("\"blue\":\"#010203ff\""));
+
+        let restored: ColorPalette = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, palette);
+        assert_eq!(Theme::Custom(restored).colors(), palette);
+    }
+
+    #[test]
+    fn test_invalid_hex_is_rejected() {
+        let json = serde_json::to_string(&ColorPalette::mocha()).unwrap().replacen("#", "not-hex", 1);
+        assert!(serde_json::from_str::<ColorPalette>(&json).is_err());
+    }
+}
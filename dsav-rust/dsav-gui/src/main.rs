@@ -153,17 +153,15 @@ impl ApplicationHandler for DsavApplication {
                 self.gl_surface.take();
                 event_loop.exit();
             }
-            WindowEvent::Resized(size) => {
-                if size.width > 0 && size.height > 0 {
-                    if let (Some(context), Some(surface)) =
-                        (self.gl_context.as_ref(), self.gl_surface.as_ref())
-                    {
-                        surface.resize(
-                            context,
-                            NonZeroU32::new(size.width).unwrap(),
-                            NonZeroU32::new(size.height).unwrap(),
-                        );
-                    }
+            WindowEvent::Resized(size) if size.width > 0 && size.height > 0 => {
+                if let (Some(context), Some(surface)) =
+                    (self.gl_context.as_ref(), self.gl_surface.as_ref())
+                {
+                    surface.resize(
+                        context,
+                        NonZeroU32::new(size.width).unwrap(),
+                        NonZeroU32::new(size.height).unwrap(),
+                    );
                 }
             }
             WindowEvent::RedrawRequested => {